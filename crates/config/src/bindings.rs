@@ -0,0 +1,364 @@
+//! Reviewable dumps of every binding reachable from the root mode.
+//!
+//! The tree is produced by the shared submenu walk: the root is rendered for one synthetic window
+//! context and each submenu is entered in turn, as a user pressing through the HUD would.
+
+use std::{fmt::Write, path::Path};
+
+use hotki_protocol::FocusSnapshot;
+use serde::Serialize;
+
+use crate::{
+    Error, load_dynamic_config,
+    script::{
+        Binding, BindingKind, LoadedConfig, ModeFrame, SourcePos,
+        walk::{ModeVisit, ModeVisitor, walk_modes},
+    },
+};
+
+/// One rendered mode and its bindings.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModeNode {
+    /// Mode title; `root` for the entry mode.
+    pub title: String,
+    /// Whether the mode requests capture-all while the HUD is visible.
+    pub capture: bool,
+    /// Bindings active in the mode, including those inherited from parents.
+    pub bindings: Vec<BindingNode>,
+}
+
+/// Kind of a dumped binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingNodeKind {
+    /// Runs an action or handler.
+    Action,
+    /// Enters a submenu.
+    Mode,
+}
+
+/// One binding in a rendered mode.
+#[derive(Debug, Clone, Serialize)]
+pub struct BindingNode {
+    /// Chord in canonical form.
+    pub chord: String,
+    /// Description shown in the HUD.
+    pub desc: String,
+    /// What the binding does.
+    pub kind: BindingNodeKind,
+    /// Hidden from the HUD.
+    pub hidden: bool,
+    /// Inherited by child modes.
+    pub global: bool,
+    /// Suppresses the automatic exit after running.
+    pub stay: bool,
+    /// Entering the submenu enables capture-all.
+    pub capture: bool,
+    /// Where the binding was declared, when known.
+    pub pos: Option<SourcePos>,
+    /// Set when the submenu is already open further up the path and was not expanded again.
+    pub recursive: bool,
+    /// Rendered submenu for mode bindings.
+    pub submenu: Option<ModeNode>,
+}
+
+/// Load the config at `path` and render every mode reachable from the root for `window`.
+pub fn binding_tree(path: &Path, window: Option<FocusSnapshot>) -> Result<ModeNode, Error> {
+    let mut cfg = load_dynamic_config(path)?;
+    let mut tree = Tree::default();
+    walk_modes(&mut cfg, window, &mut tree)?;
+    Ok(tree
+        .root
+        .expect("a successful walk always leaves the root mode"))
+}
+
+/// Tree under construction.
+#[derive(Default)]
+struct Tree {
+    /// Modes entered but not yet left, root first.
+    open: Vec<ModeNode>,
+    /// The root once it has been left.
+    root: Option<ModeNode>,
+}
+
+impl ModeVisitor for Tree {
+    fn enter(&mut self, _cfg: &mut LoadedConfig, mode: &ModeVisit<'_>) {
+        let title = mode.trail.last().cloned().unwrap_or_default();
+        if mode.depth() > 0 && mode.declared.is_empty() {
+            // A submenu that declares no bindings is dropped from the stack when entered.
+            self.open.push(ModeNode {
+                title,
+                capture: false,
+                bindings: Vec::new(),
+            });
+            return;
+        }
+        self.open.push(ModeNode {
+            title,
+            capture: mode.capture,
+            bindings: mode.active().map(binding_node).collect(),
+        });
+    }
+
+    fn leave(&mut self, entry: Option<&Binding>) {
+        let Some(node) = self.open.pop() else {
+            return;
+        };
+        match (entry, self.open.last_mut()) {
+            (Some(entry), Some(parent)) => {
+                if let Some(binding) = parent.binding_mut(entry) {
+                    binding.submenu = Some(node);
+                }
+            }
+            _ => self.root = Some(node),
+        }
+    }
+
+    fn recursive(&mut self, binding: &Binding) {
+        if let Some(node) = self
+            .open
+            .last_mut()
+            .and_then(|mode| mode.binding_mut(binding))
+        {
+            node.recursive = true;
+        }
+    }
+
+    fn failed(&mut self, _frame: &ModeFrame, err: Error) -> Result<(), Error> {
+        Err(err)
+    }
+}
+
+/// Dumped form of one active binding.
+fn binding_node(binding: &Binding) -> BindingNode {
+    BindingNode {
+        chord: binding.chord.to_string(),
+        desc: binding.desc.clone(),
+        kind: match binding.kind {
+            BindingKind::Handler(_) => BindingNodeKind::Action,
+            BindingKind::Mode(_) => BindingNodeKind::Mode,
+        },
+        hidden: binding.flags.hidden,
+        global: binding.flags.global,
+        stay: binding.flags.stay,
+        capture: binding.mode_capture,
+        pos: binding.pos.clone(),
+        recursive: false,
+        submenu: None,
+    }
+}
+
+impl ModeNode {
+    /// The dumped submenu binding declared by this mode for `binding`'s chord.
+    fn binding_mut(&mut self, binding: &Binding) -> Option<&mut BindingNode> {
+        let chord = binding.chord.to_string();
+        self.bindings
+            .iter_mut()
+            .find(|node| node.kind == BindingNodeKind::Mode && node.chord == chord)
+    }
+
+    /// Render the tree as indented plain text, one binding per line.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        self.write_text(&mut out, 0);
+        out
+    }
+
+    /// Render the tree as markdown, one table per mode.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        self.write_markdown(&mut out, &mut Vec::new());
+        out
+    }
+
+    /// Append this mode's text lines at `indent` levels.
+    fn write_text(&self, out: &mut String, indent: usize) {
+        let pad = "  ".repeat(indent);
+        if indent == 0 {
+            let _ignored = writeln!(out, "{}{}", self.title, capture_suffix(self.capture));
+        }
+        for binding in &self.bindings {
+            let _ignored = write!(out, "{pad}  {}  {}", binding.chord, binding.desc);
+            let flags = binding.flags();
+            if !flags.is_empty() {
+                let _ignored = write!(out, "  [{}]", flags.join(", "));
+            }
+            if let Some(pos) = binding.source() {
+                let _ignored = write!(out, "  {pos}");
+            }
+            out.push('\n');
+            if let Some(submenu) = &binding.submenu {
+                submenu.write_text(out, indent + 1);
+            }
+        }
+    }
+
+    /// Append this mode's table and then its submenus' tables.
+    fn write_markdown(&self, out: &mut String, trail: &mut Vec<String>) {
+        trail.push(self.title.clone());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let level = "#".repeat(trail.len().min(6));
+        let _ignored = writeln!(out, "{level} {}\n", trail.join(" › "));
+        out.push_str("| Key | Description | Flags | Source |\n");
+        out.push_str("| --- | --- | --- | --- |\n");
+        for binding in &self.bindings {
+            let _ignored = writeln!(
+                out,
+                "| `{}` | {} | {} | {} |",
+                binding.chord,
+                escape_cell(&binding.desc),
+                binding.flags().join(", "),
+                binding.source().unwrap_or_default()
+            );
+        }
+        for binding in &self.bindings {
+            if let Some(submenu) = &binding.submenu {
+                submenu.write_markdown(out, trail);
+            }
+        }
+        trail.pop();
+    }
+}
+
+impl BindingNode {
+    /// Short labels for the binding's kind and set flags.
+    fn flags(&self) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if self.kind == BindingNodeKind::Mode {
+            flags.push("mode");
+        }
+        for (set, label) in [
+            (self.hidden, "hidden"),
+            (self.global, "global"),
+            (self.stay, "stay"),
+            (self.capture, "capture"),
+            (self.recursive, "recursive"),
+        ] {
+            if set {
+                flags.push(label);
+            }
+        }
+        flags
+    }
+
+    /// Declaration site as `path:line:col`, when known.
+    fn source(&self) -> Option<String> {
+        let pos = self.pos.as_ref()?;
+        let mut out = pos
+            .path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "<config>".to_string());
+        if let Some(line) = pos.line {
+            let _ignored = write!(out, ":{line}");
+            if let Some(col) = pos.col {
+                let _ignored = write!(out, ":{col}");
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Suffix marking a capture-all mode in text output.
+fn capture_suffix(capture: bool) -> &'static str {
+    if capture { "  [capture]" } else { "" }
+}
+
+/// Escape pipe characters so a description stays in one markdown table cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tmp")
+            .join(format!("binding-tree-{name}-{id}"));
+        if root.exists() {
+            fs::remove_dir_all(&root).expect("remove stale tmp dir");
+        }
+        fs::create_dir_all(&root).expect("create tmp dir");
+        root
+    }
+
+    #[test]
+    fn tree_expands_submenus_for_the_window_and_stops_at_recursion() {
+        let root = test_dir("tree");
+        let config = root.join("config.luau");
+        fs::write(
+            &config,
+            r#"
+local a = hotki.actions
+local function windows(menu, ctx)
+    menu:bind("h", "left | half", a.relay("cmd+left"), { stay = true })
+    menu:submenu("w", "again", windows)
+end
+return function(menu, ctx)
+    local window = ctx.window
+    if window ~= nil and window.app == "Safari" then
+        menu:bind("r", "reload", a.relay("cmd+r"))
+    end
+    menu:bind("escape", "back", a.pop, { global = true, hidden = true })
+    menu:submenu("w", "windows", windows, { capture = true })
+end
+"#,
+        )
+        .expect("write config");
+
+        let plain = binding_tree(&config, None).expect("tree");
+        assert_eq!(plain.bindings.len(), 2);
+        let safari = binding_tree(
+            &config,
+            Some(FocusSnapshot {
+                id: 0,
+                app: "Safari".to_string(),
+                title: String::new(),
+                pid: 0,
+                display_id: None,
+            }),
+        )
+        .expect("tree");
+        let chords = safari
+            .bindings
+            .iter()
+            .map(|binding| binding.chord.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(chords, ["r", "esc", "w"]);
+
+        let windows = &safari.bindings[2];
+        assert_eq!(windows.kind, BindingNodeKind::Mode);
+        assert!(windows.capture);
+        let pos = windows.pos.as_ref().expect("binding position");
+        assert_eq!(pos.line, Some(13));
+        let submenu = windows.submenu.as_ref().expect("expanded submenu");
+        assert_eq!(submenu.title, "windows");
+        let again = submenu
+            .bindings
+            .iter()
+            .find(|binding| binding.desc == "again")
+            .expect("nested submenu");
+        assert!(again.recursive && again.submenu.is_none());
+        assert!(submenu.bindings.iter().any(|binding| binding.global));
+
+        let text = safari.to_text();
+        assert!(text.contains("    h  left | half  [stay]"), "{text}");
+        let markdown = safari.to_markdown();
+        assert!(markdown.contains("## root › windows"), "{markdown}");
+        assert!(markdown.contains("left \\| half"), "{markdown}");
+        let json = serde_json::to_value(&safari).expect("serialize");
+        assert_eq!(json["bindings"][2]["kind"], "mode");
+    }
+}
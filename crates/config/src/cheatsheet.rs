@@ -0,0 +1,430 @@
+//! Printable cheat sheets of a binding tree for `hotki cheatsheet`.
+//!
+//! The sheet lists each reachable mode once, headed by its breadcrumb trail. Global bindings are
+//! listed in the mode that declares them rather than repeated in every submenu that inherits them.
+//! Chords use the macOS modifier symbols, and the HTML form takes its colours from the HUD style.
+
+use std::fmt::Write;
+
+use mac_keycode::{Chord, Key, Modifier};
+
+use crate::{BindingNodeKind, FontWeight, Hud, ModeNode};
+
+/// Marker appended to submenu descriptions.
+const SUBMENU_MARKER: &str = "›";
+
+/// A cheat sheet over one binding tree.
+pub struct CheatSheet<'a> {
+    /// Tree to summarize.
+    tree: &'a ModeNode,
+    /// HUD style supplying colours, fonts, and key box geometry.
+    hud: &'a Hud,
+    /// Include bindings hidden from the HUD.
+    include_hidden: bool,
+}
+
+/// One mode's entries on the sheet.
+struct Section {
+    /// Mode titles from the root to this mode.
+    trail: Vec<String>,
+    /// Whether the mode captures unbound keys.
+    capture: bool,
+    /// Bindings listed for the mode.
+    rows: Vec<Row>,
+}
+
+/// One listed binding.
+struct Row {
+    /// Symbol tokens and whether each one is a modifier.
+    keys: Vec<(String, bool)>,
+    /// HUD description.
+    desc: String,
+    /// Enters a submenu.
+    submenu: bool,
+    /// Hidden from the HUD.
+    hidden: bool,
+    /// Reopens a submenu already open further up the trail.
+    recursive: bool,
+}
+
+impl<'a> CheatSheet<'a> {
+    /// Cheat sheet for `tree` styled with `hud`, leaving out hidden bindings.
+    pub fn new(tree: &'a ModeNode, hud: &'a Hud) -> Self {
+        Self {
+            tree,
+            hud,
+            include_hidden: false,
+        }
+    }
+
+    /// Set whether bindings hidden from the HUD are listed.
+    pub fn include_hidden(mut self, include: bool) -> Self {
+        self.include_hidden = include;
+        self
+    }
+
+    /// Render the sheet as markdown, one table per mode.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Hotki cheat sheet\n");
+        for section in self.sections() {
+            let _ignored = writeln!(out, "\n## {}\n", section.trail.join(" › "));
+            if section.capture {
+                out.push_str("_Captures all keys while open._\n\n");
+            }
+            out.push_str("| Keys | Action |\n| --- | --- |\n");
+            for row in &section.rows {
+                let keys = row
+                    .keys
+                    .iter()
+                    .map(|(token, _)| token.as_str())
+                    .collect::<String>();
+                let desc = row.label().replace('|', "\\|");
+                let _ignored = writeln!(out, "| `{keys}` | {desc} |");
+            }
+        }
+        out
+    }
+
+    /// Render the sheet as a self-contained HTML page using the HUD colours.
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Hotki cheat sheet</title>\n<style>\n",
+        );
+        out.push_str(&self.css());
+        out.push_str("</style>\n</head>\n<body>\n<h1>Hotki cheat sheet</h1>\n");
+        for section in self.sections() {
+            let _ignored = writeln!(
+                out,
+                "<section>\n<h2>{}</h2>",
+                escape_html(&section.trail.join(" › "))
+            );
+            if section.capture {
+                out.push_str("<p class=\"note\">Captures all keys while open.</p>\n");
+            }
+            for row in &section.rows {
+                write_html_row(&mut out, row);
+            }
+            out.push_str("</section>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Stylesheet derived from the HUD style.
+    fn css(&self) -> String {
+        let hud = self.hud;
+        format!(
+            "body {{ background: {bg}; color: {fg}; margin: 2em; \
+             font-size: {size}px; font-weight: {weight}; }}\n\
+             body, kbd {{ font-family: -apple-system, BlinkMacSystemFont, sans-serif; }}\n\
+             h1 {{ font-size: 1.6em; }}\n\
+             h2 {{ color: {tag}; font-size: 1.15em; margin: 0 0 0.5em; }}\n\
+             section {{ break-inside: avoid; margin-bottom: 1.5em; }}\n\
+             .row {{ display: flex; align-items: center; gap: 1em; padding: 2px 0; }}\n\
+             .keys {{ display: flex; gap: 4px; min-width: 9em; }}\n\
+             kbd {{ font-size: {key_size}px; \
+             padding: {pad_y}px {pad_x}px; border-radius: {radius}px; }}\n\
+             kbd.key {{ background: {key_bg}; color: {key_fg}; font-weight: {key_weight}; }}\n\
+             kbd.mod {{ background: {mod_bg}; color: {mod_fg}; font-weight: {mod_weight}; }}\n\
+             .tag {{ color: {tag}; }}\n\
+             .hidden {{ opacity: 0.6; }}\n\
+             .note {{ font-style: italic; margin: 0 0 0.5em; }}\n\
+             @media print {{ body {{ print-color-adjust: exact; \
+             -webkit-print-color-adjust: exact; }} }}\n",
+            bg = css_rgb(hud.bg),
+            fg = css_rgb(hud.title_fg),
+            weight = css_weight(hud.title_font_weight),
+            size = hud.font_size,
+            tag = css_rgb(hud.tag_fg),
+            key_size = hud.key_font_size * 0.75,
+            pad_y = hud.key_pad_y,
+            pad_x = hud.key_pad_x,
+            radius = hud.key_radius,
+            key_bg = css_rgb(hud.key_bg),
+            key_fg = css_rgb(hud.key_fg),
+            key_weight = css_weight(hud.key_font_weight),
+            mod_bg = css_rgb(hud.mod_bg),
+            mod_fg = css_rgb(hud.mod_fg),
+            mod_weight = css_weight(hud.mod_font_weight),
+        )
+    }
+
+    /// Flatten the tree into one section per listed mode, depth first.
+    fn sections(&self) -> Vec<Section> {
+        let mut sections = Vec::new();
+        self.collect(self.tree, &mut Vec::new(), &mut Vec::new(), &mut sections);
+        sections
+    }
+
+    /// Append `mode`'s section and then its submenus' sections.
+    ///
+    /// `inherited` holds the chord and description of globals declared by enclosing modes.
+    fn collect(
+        &self,
+        mode: &ModeNode,
+        trail: &mut Vec<String>,
+        inherited: &mut Vec<(String, String)>,
+        sections: &mut Vec<Section>,
+    ) {
+        trail.push(mode.title.clone());
+        let listed = mode
+            .bindings
+            .iter()
+            .filter(|binding| self.include_hidden || !binding.hidden)
+            .filter(|binding| {
+                !(binding.global
+                    && inherited
+                        .iter()
+                        .any(|(chord, desc)| *chord == binding.chord && *desc == binding.desc))
+            })
+            .collect::<Vec<_>>();
+        sections.push(Section {
+            trail: trail.clone(),
+            capture: mode.capture,
+            rows: listed
+                .iter()
+                .map(|binding| Row {
+                    keys: chord_symbols(&binding.chord),
+                    desc: binding.desc.clone(),
+                    submenu: binding.kind == BindingNodeKind::Mode,
+                    hidden: binding.hidden,
+                    recursive: binding.recursive,
+                })
+                .collect(),
+        });
+        let declared = inherited.len();
+        inherited.extend(
+            listed
+                .iter()
+                .filter(|binding| binding.global)
+                .map(|binding| (binding.chord.clone(), binding.desc.clone())),
+        );
+        for binding in listed {
+            if let Some(submenu) = &binding.submenu {
+                self.collect(submenu, trail, inherited, sections);
+            }
+        }
+        inherited.truncate(declared);
+        trail.pop();
+    }
+}
+
+impl Row {
+    /// Description with submenu, recursion, and hidden markers.
+    fn label(&self) -> String {
+        let mut label = self.desc.clone();
+        if self.submenu {
+            label.push(' ');
+            label.push_str(SUBMENU_MARKER);
+        }
+        if self.recursive {
+            label.push_str(" (reopens an enclosing menu)");
+        }
+        if self.hidden {
+            label.push_str(" (hidden)");
+        }
+        label
+    }
+}
+
+/// Append one binding row as HTML.
+fn write_html_row(out: &mut String, row: &Row) {
+    let class = if row.hidden { "row hidden" } else { "row" };
+    let _ignored = write!(out, "<div class=\"{class}\"><span class=\"keys\">");
+    for (token, modifier) in &row.keys {
+        let kind = if *modifier { "mod" } else { "key" };
+        let _ignored = write!(out, "<kbd class=\"{kind}\">{}</kbd>", escape_html(token));
+    }
+    let _ignored = write!(
+        out,
+        "</span><span class=\"desc\">{}</span>",
+        escape_html(&row.desc)
+    );
+    if row.submenu {
+        let _ignored = write!(out, " <span class=\"tag\">{SUBMENU_MARKER}</span>");
+    }
+    if row.recursive {
+        out.push_str(" <span class=\"note\">(reopens an enclosing menu)</span>");
+    }
+    out.push_str("</div>\n");
+}
+
+/// Split a canonical chord into macOS symbol tokens, modifiers first in Apple's ⌃⌥⇧⌘ order.
+///
+/// Chords that do not parse are returned as a single key token.
+pub fn chord_symbols(chord: &str) -> Vec<(String, bool)> {
+    let Some(parsed) = Chord::parse(chord) else {
+        return vec![(chord.to_string(), false)];
+    };
+    let mut modifiers = parsed.modifiers.iter().copied().collect::<Vec<_>>();
+    modifiers.sort_by_key(|modifier| modifier_symbol(*modifier).0);
+    let mut tokens = modifiers
+        .into_iter()
+        .map(|modifier| (modifier_symbol(modifier).1.to_string(), true))
+        .collect::<Vec<_>>();
+    tokens.push((
+        key_symbol(parsed.key),
+        Modifier::try_from(parsed.key).is_ok(),
+    ));
+    tokens
+}
+
+/// Display order and symbol for a modifier.
+fn modifier_symbol(modifier: Modifier) -> (usize, &'static str) {
+    match modifier {
+        Modifier::Function => (0, "fn"),
+        Modifier::Control | Modifier::RightControl => (1, "⌃"),
+        Modifier::Option | Modifier::RightOption => (2, "⌥"),
+        Modifier::Shift | Modifier::RightShift => (3, "⇧"),
+        Modifier::CapsLock => (4, "⇪"),
+        Modifier::Command | Modifier::RightCommand => (5, "⌘"),
+    }
+}
+
+/// Symbol or label for the non-modifier key of a chord.
+fn key_symbol(key: Key) -> String {
+    if let Ok(modifier) = Modifier::try_from(key) {
+        return modifier_symbol(modifier).1.to_string();
+    }
+    let symbol = match key {
+        Key::Return | Key::KeypadEnter => "↩",
+        Key::Tab => "⇥",
+        Key::Space => "Space",
+        Key::Delete => "⌫",
+        Key::ForwardDelete => "⌦",
+        Key::Escape => "⎋",
+        Key::LeftArrow => "←",
+        Key::RightArrow => "→",
+        Key::UpArrow => "↑",
+        Key::DownArrow => "↓",
+        Key::PageUp => "⇞",
+        Key::PageDown => "⇟",
+        Key::Home => "↖",
+        Key::End => "↘",
+        _ => return key.to_spec().to_uppercase(),
+    };
+    symbol.to_string()
+}
+
+/// CSS colour for an RGB triple.
+fn css_rgb((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// CSS numeric weight for a font weight.
+fn css_weight(weight: FontWeight) -> u16 {
+    match weight {
+        FontWeight::Thin => 100,
+        FontWeight::ExtraLight => 200,
+        FontWeight::Light => 300,
+        FontWeight::Regular => 400,
+        FontWeight::Medium => 500,
+        FontWeight::SemiBold => 600,
+        FontWeight::Bold => 700,
+        FontWeight::ExtraBold => 800,
+        FontWeight::Black => 900,
+    }
+}
+
+/// Escape text for HTML element content.
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BindingNode;
+
+    fn binding(chord: &str, desc: &str) -> BindingNode {
+        BindingNode {
+            chord: chord.to_string(),
+            desc: desc.to_string(),
+            kind: BindingNodeKind::Action,
+            hidden: false,
+            global: false,
+            stay: false,
+            capture: false,
+            pos: None,
+            recursive: false,
+            submenu: None,
+        }
+    }
+
+    fn tree() -> ModeNode {
+        let back = BindingNode {
+            hidden: true,
+            global: true,
+            ..binding("esc", "back")
+        };
+        let help = BindingNode {
+            global: true,
+            ..binding("shift+/", "help")
+        };
+        let windows = BindingNode {
+            kind: BindingNodeKind::Mode,
+            capture: true,
+            submenu: Some(ModeNode {
+                title: "windows".to_string(),
+                capture: true,
+                bindings: vec![
+                    binding("cmd+left", "left <half>"),
+                    back.clone(),
+                    help.clone(),
+                ],
+            }),
+            ..binding("cmd+shift+w", "windows")
+        };
+        ModeNode {
+            title: "root".to_string(),
+            capture: false,
+            bindings: vec![binding("ctrl+opt+space", "launcher"), back, help, windows],
+        }
+    }
+
+    #[test]
+    fn chords_use_mac_symbols_in_apple_order() {
+        let tokens = chord_symbols("cmd+shift+opt+ctrl+k");
+        let text = tokens
+            .iter()
+            .map(|(token, _)| token.as_str())
+            .collect::<String>();
+        assert_eq!(text, "⌃⌥⇧⌘K");
+        assert_eq!(chord_symbols("esc"), [("⎋".to_string(), false)]);
+        assert_eq!(chord_symbols("shift"), [("⇧".to_string(), true)]);
+    }
+
+    #[test]
+    fn sheet_groups_modes_and_styles_html() {
+        let tree = tree();
+        let hud = Hud::default();
+        let sheet = CheatSheet::new(&tree, &hud);
+
+        let markdown = sheet.to_markdown();
+        assert!(markdown.contains("## root › windows\n"), "{markdown}");
+        assert!(markdown.contains("| `⌃⌥Space` | launcher |"), "{markdown}");
+        assert!(markdown.contains("| `⇧⌘W` | windows › |"), "{markdown}");
+        assert!(!markdown.contains("back"), "{markdown}");
+        assert_eq!(markdown.matches("help").count(), 1, "{markdown}");
+
+        let html = sheet.include_hidden(true).to_html();
+        assert!(html.contains(&css_rgb(hud.key_bg)), "{html}");
+        assert!(html.contains(&css_rgb(hud.mod_bg)), "{html}");
+        assert!(html.contains("<h2>root › windows</h2>"), "{html}");
+        assert!(html.contains("left &lt;half&gt;"), "{html}");
+        assert!(html.contains("<div class=\"row hidden\">"), "{html}");
+        assert!(html.contains("<kbd class=\"mod\">⌘</kbd><kbd class=\"key\">←</kbd>"));
+        assert_eq!(html.matches(">back<").count(), 1, "{html}");
+    }
+}
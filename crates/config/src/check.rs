@@ -1,6 +1,10 @@
 //! Luau configuration validation helpers.
 
-use std::{fs, io::ErrorKind, path::Path};
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 
 use ruau::{
     bytecode::CompileOptions,
@@ -8,15 +12,18 @@ use ruau::{
     surface::{CheckOptions, Surface},
     typecheck::{Mode, Severity},
 };
+use serde::Serialize;
 
 use crate::{
-    Error, LuauApiSurface, StyleResolver, luau_api_surface,
-    script::{diagnostics, loader::load_dynamic_config_with_style},
+    BindingWarning, Diagnostic, Error, LuauApiSurface, Severity as DiagnosticSeverity,
+    StyleResolver, luau_api_surface,
+    script::{analysis::analyze_bindings, diagnostics, loader::load_dynamic_config_with_style},
+    stale_editor_definitions,
     style::{ResolvedStyle, eval_style_source},
 };
 
 /// Summary of a successful Luau validation run.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LuauCheckReport {
     /// Number of checked behavior modules, including the entry module.
     pub modules: usize,
@@ -24,6 +31,30 @@ pub struct LuauCheckReport {
     pub style: bool,
     /// Effective style resolved while validating this candidate.
     pub resolved_style: ResolvedStyle,
+    /// Binding conflicts and shadowing found across representative contexts.
+    pub warnings: Vec<BindingWarning>,
+    /// Editor definition files written by a different `hotki editor-setup`.
+    pub stale_definitions: Vec<PathBuf>,
+}
+
+impl LuauCheckReport {
+    /// Warnings from the check as structured diagnostics, binding warnings first.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let stale = self.stale_definitions.iter().map(|path| Diagnostic {
+            path: Some(path.clone()),
+            line: Some(1),
+            col: Some(1),
+            severity: DiagnosticSeverity::Warning,
+            code: "stale_definitions".to_string(),
+            message: "generated definitions are out of date; rerun `hotki editor-setup`"
+                .to_string(),
+        });
+        self.warnings
+            .iter()
+            .map(BindingWarning::to_diagnostic)
+            .chain(stale)
+            .collect()
+    }
 }
 
 /// Validate a filesystem-backed Luau config and optional sibling style.
@@ -39,7 +70,7 @@ pub fn check_luau_config(path: &Path) -> Result<LuauCheckReport, Error> {
     } else {
         false
     };
-    let config = load_dynamic_config_with_style(&canonical, style_candidate.resolve()?)?;
+    let mut config = load_dynamic_config_with_style(&canonical, style_candidate.resolve()?)?;
     let (entry_gas, validation_gas, retained_heap) = config.load_metrics();
     tracing::debug!(
         path = %canonical.display(),
@@ -49,10 +80,18 @@ pub fn check_luau_config(path: &Path) -> Result<LuauCheckReport, Error> {
         "validated Luau config graph"
     );
 
+    let warnings = analyze_bindings(&mut config);
+    let stale_definitions = canonical
+        .parent()
+        .map(stale_editor_definitions)
+        .unwrap_or_default();
+
     Ok(LuauCheckReport {
         modules: config.module_count(),
         style,
         resolved_style: config.resolved_style(),
+        warnings,
+        stale_definitions,
     })
 }
 
@@ -101,6 +140,7 @@ fn checker_surface() -> Result<Surface, Error> {
             col: None,
             message: err.to_string(),
             excerpt: None,
+            diagnostics: Vec::new(),
         })
 }
 
@@ -152,6 +192,7 @@ mod tests {
     };
 
     use super::{check_luau_config, check_luau_style_file};
+    use crate::{BindingWarningKind, Severity};
 
     fn test_dir(name: &str) -> PathBuf {
         static NEXT_ID: AtomicU64 = AtomicU64::new(0);
@@ -222,6 +263,96 @@ end
         );
     }
 
+    #[test]
+    fn check_reports_binding_conflicts_and_shadowing() {
+        let root = test_dir("binding-analysis");
+        fs::write(
+            root.join("config.luau"),
+            r#"
+local a = hotki.actions
+return function(menu, ctx)
+    menu:bind("cmd+space", "launcher", a.pop)
+    menu:bind("escape", "exit", a.exit, { global = true, hidden = true })
+    menu:submenu("w", "windows", function(child)
+        child:bind("escape", "back", a.pop)
+        child:bind("h", "left", a.pop)
+        child:bind("h", "left again", a.pop)
+    end)
+    menu:app_mode("Safari", "Safari", function(layer)
+        layer:bind("r", "reload", a.relay("cmd+r"))
+        layer:bind("r", "reload twice", a.relay("cmd+r"))
+    end)
+end
+"#,
+        )
+        .expect("write root config");
+
+        let report = check_luau_config(&root.join("config.luau")).expect("check config");
+        let kinds = report
+            .warnings
+            .iter()
+            .map(|warning| (warning.kind, warning.chord.as_str(), warning.app.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                (BindingWarningKind::SystemShortcut, "cmd+space", None),
+                (BindingWarningKind::DuplicateChord, "h", None),
+                (BindingWarningKind::ShadowedGlobal, "esc", None),
+                (BindingWarningKind::DuplicateChord, "r", Some("Safari")),
+            ]
+        );
+        let shadowed = &report.warnings[2];
+        assert_eq!(shadowed.mode, ["root", "windows"]);
+        let lines = shadowed
+            .locations
+            .iter()
+            .map(|pos| pos.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [Some(7), Some(5)]);
+        assert!(shadowed.to_string().contains("shadows the global binding"));
+    }
+
+    #[test]
+    fn check_reports_exec_specs_that_fail_validation() {
+        let root = test_dir("invalid-exec");
+        fs::write(
+            root.join("config.luau"),
+            r#"
+local a = hotki.actions
+return function(menu, ctx)
+    menu:bind("t", "timeout", a.exec({ program = "/bin/sleep", timeout_ms = 0 }))
+    menu:bind("e", "env", a.exec({ program = "/usr/bin/env", env = { ["A=B"] = "1" } }))
+    menu:bind("o", "ok", a.exec({ program = "/usr/bin/true" }))
+end
+"#,
+        )
+        .expect("write root config");
+
+        let report = check_luau_config(&root.join("config.luau")).expect("check config");
+        let found = report
+            .warnings
+            .iter()
+            .map(|warning| (warning.kind, warning.chord.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                (BindingWarningKind::InvalidExecSpec, "t"),
+                (BindingWarningKind::InvalidExecSpec, "e"),
+            ]
+        );
+        assert!(
+            report.warnings[0]
+                .message
+                .contains("timeout_ms must be at least 1")
+        );
+        assert!(report.warnings[1].message.contains("\"A=B\""));
+        let diagnostic = report.warnings[0].to_diagnostic();
+        assert_eq!(diagnostic.code, "invalid_exec_spec");
+        assert_eq!(diagnostic.line, Some(4));
+    }
+
     #[test]
     fn check_rejects_bare_module_requests() {
         let root = test_dir("bare-require");
@@ -446,6 +577,68 @@ end
         assert!(a < b, "diagnostics are not in dependency order: {pretty}");
     }
 
+    #[test]
+    fn check_errors_split_into_structured_diagnostics() {
+        let root = test_dir("structured-diagnostics");
+        fs::write(
+            root.join("config.luau"),
+            "require('./a')\nrequire('./b')\nreturn function(menu) end",
+        )
+        .expect("write root config");
+        for name in ["a", "b"] {
+            fs::write(
+                root.join(format!("{name}.luau")),
+                format!("local {name}: number = 'bad'\nreturn {name}"),
+            )
+            .expect("write invalid dependency");
+        }
+
+        let error = check_luau_config(&root.join("config.luau"))
+            .expect_err("child diagnostics should reject graph");
+        assert!(!error.is_io());
+        let diagnostics = error.diagnostics();
+        let files = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                assert_eq!(diagnostic.severity, Severity::Error);
+                assert!(diagnostic.line.is_some(), "{diagnostic:?}");
+                diagnostic
+                    .path
+                    .as_deref()
+                    .and_then(Path::file_name)
+                    .and_then(OsStr::to_str)
+                    .expect("diagnostic path")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(files, ["a.luau", "b.luau"]);
+
+        let missing = check_luau_config(&root.join("missing.luau")).expect_err("missing config");
+        assert!(missing.is_io());
+        assert_eq!(missing.diagnostics()[0].code, "io");
+
+        fs::write(root.join("config.toml"), "").expect("write wrong-format config");
+        let wrong_format =
+            check_luau_config(&root.join("config.toml")).expect_err("wrong-format config");
+        assert!(!wrong_format.is_io());
+        assert_eq!(wrong_format.diagnostics()[0].code, "config");
+
+        fs::write(
+            root.join("config.luau"),
+            r#"
+return function(menu, ctx)
+    menu:bind("a", "first", hotki.actions.pop)
+    menu:bind("a", "second", hotki.actions.pop)
+end
+"#,
+        )
+        .expect("write duplicate config");
+        let report = check_luau_config(&root.join("config.luau")).expect("check config");
+        let warning = report.warnings[0].to_diagnostic();
+        assert_eq!(warning.severity, Severity::Warning);
+        assert_eq!(warning.code, "duplicate_chord");
+        assert_eq!(warning.line, Some(3));
+    }
+
     #[test]
     fn check_follows_symlinks_inside_the_trusted_config_directory() {
         use std::os::unix::fs::symlink;
@@ -507,6 +700,28 @@ end
         assert!(pretty.contains("number"), "unexpected error: {pretty}");
     }
 
+    #[test]
+    fn check_enforces_exec_spec_field_types() {
+        let root = test_dir("exec-spec-fields");
+        fs::write(
+            root.join("config.luau"),
+            r#"
+return function(menu, ctx)
+    menu:bind("a", "Run", hotki.actions.exec({
+        program = "/usr/bin/env",
+        env = { LANG = "C" },
+        timeout_ms = "soon",
+    }))
+end
+"#,
+        )
+        .expect("write root config");
+
+        let err = check_luau_config(&root.join("config.luau")).expect_err("check should fail");
+        let pretty = err.pretty();
+        assert!(pretty.contains("timeout_ms"), "unexpected error: {pretty}");
+    }
+
     #[test]
     fn check_enforces_strict_mode_renderer_types() {
         let root = test_dir("strict-mode-renderer");
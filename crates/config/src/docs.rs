@@ -129,6 +129,7 @@ mod tests {
         assert!(filtered.contains("type MenuBuilder ="));
         assert!(filtered.contains("with: (self: MenuBuilder"));
         assert!(filtered.contains("submenu: ("));
+        assert!(filtered.contains("app_mode: ("));
         assert!(!filtered.contains("type Style ="));
     }
 
@@ -170,8 +171,22 @@ mod tests {
             "shell:",
             "exec:",
             "relay_to_app:",
+            "relay_to_bundle:",
             "relay_with:",
+            "relay_sequence:",
+            "type_text:",
             "launch_application:",
+            "clipboard_history:",
+            "media:",
+            "activate_app:",
+            "hide_app:",
+            "quit_app:",
+            "toggle_app:",
+            "set_clipboard:",
+            "with_clipboard:",
+            "focus_previous_app:",
+            "focus_previous_window:",
+            "cancel_processes:",
             "hold:",
             "select:",
         ] {
@@ -190,6 +205,18 @@ mod tests {
             filtered
                 .contains("applications: (ctx: ModeContext) -> SelectorItemList<ApplicationInfo>")
         );
+        assert!(
+            filtered.contains("recent_windows: (ctx: ModeContext) -> SelectorItemList<WindowInfo>")
+        );
+        assert!(filtered.contains("enable_clipboard_history: (options: ClipboardHistoryOptions?)"));
+        assert!(
+            filtered.contains("clipboard_entries: (ctx: ModeContext) -> SelectorItemList<string>")
+        );
+        assert!(filtered.contains("services: (spec: ServiceSpec) -> ()"));
+        assert!(
+            filtered.contains("schedule: (when: string | ScheduleOptions, action: Action) -> ()")
+        );
+        assert!(filtered.contains("test: (name: string, body: (t: TestContext) -> ()) -> ()"));
     }
 
     #[test]
@@ -0,0 +1,250 @@
+//! Editor integration files for luau-lsp, written by `hotki editor-setup`.
+//!
+//! The definition files are copies of the embedded API surfaces with a stamp on the first line.
+//! The stamp records the Hotki version and a fingerprint of the declarations, so `hotki check` can
+//! report copies left behind by an older binary.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, LuauApiSurface, luau_api_surface};
+
+/// File name of the behavior config definitions written into the config directory.
+pub const EDITOR_CONFIG_DEFINITIONS: &str = "hotki_config.d.luau";
+
+/// File name of the style definitions written into the config directory.
+pub const EDITOR_STYLE_DEFINITIONS: &str = "hotki_style.d.luau";
+
+/// Prefix of the stamp line at the top of each generated definition file.
+const STAMP_PREFIX: &str = "-- Generated by `hotki editor-setup`";
+
+/// luau-lsp setting listing definition files in `.vscode/settings.json`.
+const DEFINITION_FILES_SETTING: &str = "luau-lsp.types.definitionFiles";
+
+/// Contents of a `.luaurc` written when the directory has none.
+const LUAURC: &str = "{\n    \"languageMode\": \"strict\"\n}\n";
+
+/// Files touched by [`write_editor_setup`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorSetup {
+    /// Files created or replaced.
+    pub written: Vec<PathBuf>,
+    /// Existing files left unchanged because they belong to the user.
+    pub kept: Vec<PathBuf>,
+    /// Existing settings files left unchanged because they are not plain JSON, such as JSONC
+    /// with comments or trailing commas; the definition files must be added to them by hand.
+    pub manual: Vec<PathBuf>,
+}
+
+/// Write luau-lsp definition files and settings into the config directory `dir`.
+///
+/// Definition files are always replaced. An existing `.luaurc` is kept, and an existing
+/// `.vscode/settings.json` is updated in place so only the Hotki files are added to the
+/// definition file list. Settings that strict JSON parsing rejects are left for manual editing.
+pub fn write_editor_setup(dir: &Path) -> Result<EditorSetup, Error> {
+    let mut setup = EditorSetup::default();
+    for (name, surface) in definition_files() {
+        let path = dir.join(name);
+        write_file(&path, &stamped_definitions(surface))?;
+        setup.written.push(path);
+    }
+
+    let luaurc = dir.join(".luaurc");
+    if luaurc.exists() {
+        setup.kept.push(luaurc);
+    } else {
+        write_file(&luaurc, LUAURC)?;
+        setup.written.push(luaurc);
+    }
+
+    let settings = dir.join(".vscode").join("settings.json");
+    match vscode_settings(&settings)? {
+        Some(contents) => {
+            write_file(&settings, &contents)?;
+            setup.written.push(settings);
+        }
+        None => setup.manual.push(settings),
+    }
+    Ok(setup)
+}
+
+/// Generated definition files in `dir` whose stamp does not match this build.
+///
+/// Missing files are not reported; the editor setup is optional.
+pub fn stale_editor_definitions(dir: &Path) -> Vec<PathBuf> {
+    definition_files()
+        .into_iter()
+        .filter_map(|(name, surface)| {
+            let path = dir.join(name);
+            let source = fs::read_to_string(&path).ok()?;
+            let first = source.lines().next().unwrap_or_default();
+            (first != stamp(surface)).then_some(path)
+        })
+        .collect()
+}
+
+/// Definition file names and the API surface each one holds.
+fn definition_files() -> [(&'static str, LuauApiSurface); 2] {
+    [
+        (EDITOR_CONFIG_DEFINITIONS, LuauApiSurface::Config),
+        (EDITOR_STYLE_DEFINITIONS, LuauApiSurface::Style),
+    ]
+}
+
+/// One API surface with its stamp line prepended.
+fn stamped_definitions(surface: LuauApiSurface) -> String {
+    format!("{}\n{}", stamp(surface), luau_api_surface(surface))
+}
+
+/// Stamp line identifying the Hotki version and declarations behind a definition file.
+fn stamp(surface: LuauApiSurface) -> String {
+    format!(
+        "{STAMP_PREFIX} for hotki {} (api {:016x}); do not edit.",
+        env!("CARGO_PKG_VERSION"),
+        fingerprint(&luau_api_surface(surface))
+    )
+}
+
+/// FNV-1a fingerprint of declaration text; stable across builds and platforms.
+fn fingerprint(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// VS Code settings at `path` with the generated files added to the definition file list.
+///
+/// Returns `None` when the existing file is not a plain JSON object or its definition file list
+/// is not an array, so it cannot be rewritten without losing something.
+fn vscode_settings(path: &Path) -> Result<Option<String>, Error> {
+    let mut settings = match fs::read_to_string(path) {
+        Ok(source) => match serde_json::from_str::<Value>(&source) {
+            Ok(Value::Object(settings)) => settings,
+            _ => return Ok(None),
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => Map::new(),
+        Err(err) => {
+            return Err(Error::Read {
+                path: Some(path.to_path_buf()),
+                message: err.to_string(),
+            });
+        }
+    };
+    let files = settings
+        .entry(DEFINITION_FILES_SETTING)
+        .or_insert_with(|| Value::Array(Vec::new()));
+    let Value::Array(files) = files else {
+        return Ok(None);
+    };
+    for (name, _) in definition_files() {
+        if !files.iter().any(|file| file.as_str() == Some(name)) {
+            files.push(Value::String(name.to_string()));
+        }
+    }
+    let mut out = serde_json::to_string_pretty(&Value::Object(settings))
+        .expect("serializing a JSON value cannot fail");
+    out.push('\n');
+    Ok(Some(out))
+}
+
+/// Write `contents` to `path`, creating parent directories.
+fn write_file(path: &Path, contents: &str) -> Result<(), Error> {
+    let write = || {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    };
+    write().map_err(|err| Error::Read {
+        path: Some(path.to_path_buf()),
+        message: err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tmp")
+            .join(format!("editor-setup-{name}-{id}"));
+        if root.exists() {
+            fs::remove_dir_all(&root).expect("remove stale tmp dir");
+        }
+        fs::create_dir_all(&root).expect("create tmp dir");
+        root
+    }
+
+    #[test]
+    fn setup_writes_stamped_definitions_and_merges_settings() {
+        let root = test_dir("setup");
+        fs::create_dir_all(root.join(".vscode")).expect("create settings dir");
+        fs::write(
+            root.join(".vscode/settings.json"),
+            "{ \"editor.tabSize\": 4, \"luau-lsp.types.definitionFiles\": [\"extra.d.luau\", \
+             \"hotki_config.d.luau\"] }",
+        )
+        .expect("write settings");
+        fs::write(root.join(".luaurc"), "{}").expect("write luaurc");
+
+        let setup = write_editor_setup(&root).expect("editor setup");
+        assert_eq!(setup.kept, [root.join(".luaurc")]);
+        assert_eq!(
+            fs::read_to_string(root.join(".luaurc")).expect("luaurc"),
+            "{}"
+        );
+        let config = fs::read_to_string(root.join(EDITOR_CONFIG_DEFINITIONS)).expect("config");
+        assert!(config.starts_with(STAMP_PREFIX));
+        assert!(config.contains("type ActionContext = {"));
+        let settings: Value = serde_json::from_str(
+            &fs::read_to_string(root.join(".vscode/settings.json")).expect("settings"),
+        )
+        .expect("settings json");
+        assert_eq!(settings["editor.tabSize"], 4);
+        assert_eq!(
+            settings[DEFINITION_FILES_SETTING],
+            serde_json::json!([
+                "extra.d.luau",
+                EDITOR_CONFIG_DEFINITIONS,
+                EDITOR_STYLE_DEFINITIONS
+            ])
+        );
+        assert!(stale_editor_definitions(&root).is_empty());
+
+        let style = root.join(EDITOR_STYLE_DEFINITIONS);
+        fs::write(
+            &style,
+            "-- Generated by `hotki editor-setup` for hotki 0.0.0\n",
+        )
+        .expect("write stale copy");
+        assert_eq!(stale_editor_definitions(&root), [style]);
+    }
+
+    #[test]
+    fn setup_leaves_jsonc_settings_for_manual_editing() {
+        let root = test_dir("jsonc");
+        let settings = root.join(".vscode/settings.json");
+        fs::create_dir_all(root.join(".vscode")).expect("create settings dir");
+        let jsonc = "{\n    // Indentation\n    \"editor.tabSize\": 4,\n}\n";
+        fs::write(&settings, jsonc).expect("write settings");
+
+        let setup = write_editor_setup(&root).expect("editor setup");
+        assert_eq!(setup.manual, [settings.clone()]);
+        assert!(!setup.written.contains(&settings));
+        assert_eq!(fs::read_to_string(&settings).expect("settings"), jsonc);
+    }
+}
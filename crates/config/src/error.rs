@@ -6,6 +6,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
@@ -46,9 +47,38 @@ pub enum Error {
         message: String,
         /// Optional excerpt including a caret at the error location.
         excerpt: Option<String>,
+        /// Individual findings when the error aggregates several checker diagnostics.
+        diagnostics: Vec<Diagnostic>,
     },
 }
 
+/// Severity of a structured diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The configuration was rejected.
+    Error,
+    /// The configuration loads but probably does not do what was intended.
+    Warning,
+}
+
+/// One located finding in machine-readable form, for editors and CI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// File the finding applies to, when known.
+    pub path: Option<PathBuf>,
+    /// Optional 1-based line number.
+    pub line: Option<usize>,
+    /// Optional 1-based column number.
+    pub col: Option<usize>,
+    /// How serious the finding is.
+    pub severity: Severity,
+    /// Stable category: `io`, `parse`, `config`, a checker category, or a binding warning kind.
+    pub code: String,
+    /// Human-readable message without location or excerpt.
+    pub message: String,
+}
+
 impl Error {
     /// Render a human-friendly error message including location and an excerpt when available.
     pub fn pretty(&self) -> String {
@@ -83,6 +113,7 @@ impl Error {
                 col,
                 message,
                 excerpt,
+                ..
             } => {
                 let loc = match (line, col) {
                     (Some(l), Some(c)) => format!("{}:{}", l, c),
@@ -118,6 +149,38 @@ impl Error {
         }
     }
 
+    /// Split the error into structured diagnostics, one per underlying finding.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let (code, line, col) = match self {
+            Self::Read { .. } => ("io", None, None),
+            Self::Parse { line, col, .. } => ("parse", Some(*line), Some(*col)),
+            Self::Validation {
+                diagnostics,
+                line,
+                col,
+                ..
+            } => {
+                if !diagnostics.is_empty() {
+                    return diagnostics.clone();
+                }
+                ("config", *line, *col)
+            }
+        };
+        vec![Diagnostic {
+            path: self.path().map(Path::to_path_buf),
+            line,
+            col,
+            severity: Severity::Error,
+            code: code.to_string(),
+            message: self.to_string(),
+        }]
+    }
+
+    /// Whether the error came from reading the filesystem rather than from the config itself.
+    pub fn is_io(&self) -> bool {
+        matches!(self, Self::Read { .. })
+    }
+
     /// Access the optional path attached to this error.
     pub fn path(&self) -> Option<&Path> {
         match self {
@@ -7,12 +7,17 @@ use std::{
     path::{Path, PathBuf},
 };
 
+mod bindings;
+mod cheatsheet;
 mod check;
 mod docs;
+mod editor;
 mod error;
 mod mode;
 mod raw;
+mod schedule;
 mod script;
+mod skhd;
 mod style;
 mod types;
 
@@ -22,15 +27,35 @@ pub mod runtime;
 #[cfg(test)]
 mod test_merge;
 
+pub use bindings::{BindingNode, BindingNodeKind, ModeNode, binding_tree};
+pub use cheatsheet::{CheatSheet, chord_symbols};
 pub use check::{
     LuauCheckReport, check_luau_config, check_luau_style_file, check_luau_style_source,
 };
 pub use docs::{LuauApiSurface, luau_api, luau_api_markdown, luau_api_surface, luau_api_text};
-pub use error::Error;
-pub use hotki_protocol::{NotifyKind, Toggle};
-pub use mode::{Action, ExecSpec, RelaySpec, RelayTarget, ShellModifiers, ShellSpec};
+pub use editor::{
+    EDITOR_CONFIG_DEFINITIONS, EDITOR_STYLE_DEFINITIONS, EditorSetup, stale_editor_definitions,
+    write_editor_setup,
+};
+pub use error::{Diagnostic, Error, Severity};
+pub use hotki_protocol::{FocusSnapshot, NotifyKind, Toggle, rpc::ChordExplanation};
+pub use mac_keycode::MediaKey;
+pub use mode::{
+    Action, AppCommand, AppSpec, ClipboardHistoryConfig, ExecSpec, ExecStdin, FocusTarget,
+    ProcessConcurrency, RelaySequenceSpec, RelaySpec, RelayTarget, ServiceRestart, ServiceSpec,
+    ShellModifiers, ShellSpec, TypeTextSpec,
+};
+pub use schedule::{MissedRuns, Schedule};
 #[cfg(test)]
 pub(crate) use script::loader::load_dynamic_config;
+pub use script::{
+    analysis::{BindingWarning, BindingWarningKind},
+    explain::explain_chord,
+    lint::{LintFinding, LintKind, lint_config},
+    repl::ReplSession,
+    spec::{SpecCase, discover_specs, run_spec_file},
+};
+pub use skhd::{SkhdImport, import_skhd};
 pub use style::{
     Hud, Notify, ResolvedStyle, STYLE_FILE_NAME, Selector, Style, StyleProvenance, StyleResolver,
     default_style, default_style_source,
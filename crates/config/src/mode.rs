@@ -1,6 +1,10 @@
 //! Primitive actions and shell specs used by Hotki.
 
+use std::collections::BTreeMap;
+
 pub use hotki_protocol::NotifyKind;
+use mac_keycode::MediaKey;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::Toggle;
@@ -16,6 +20,10 @@ pub enum Action {
     /// Relay a keystroke (with optional modifiers) to the currently
     /// focused application. Example: relay("cmd+shift+n").
     Relay(RelaySpec),
+    /// Relay several chords in order to the focused application.
+    RelaySequence(RelaySequenceSpec),
+    /// Type literal text as Unicode keyboard events.
+    TypeText(TypeTextSpec),
     /// Ask host application to reload its configuration
     ReloadConfig,
     /// Ask host to clear all on-screen notifications
@@ -30,6 +38,211 @@ pub enum Action {
     ChangeVolume(i8),
     /// Control mute state: on/off/toggle
     Mute(Toggle),
+    /// Tap a system-defined media key.
+    Media(MediaKey),
+    /// Apply a lifecycle command to one application.
+    App(AppSpec),
+    /// Replace the system clipboard with plain text.
+    SetClipboard(String),
+    /// Activate an application chosen from the engine's focus history.
+    Focus(FocusTarget),
+    /// Kill every running process started by a binding and drop queued runs.
+    CancelProcesses,
+}
+
+/// Destination of a focus action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FocusTarget {
+    /// Most recently focused application other than the context window's application.
+    PreviousApp,
+    /// Most recently focused window other than the context window.
+    PreviousWindow,
+    /// One window reported by `hotki.recent_windows`.
+    Window {
+        /// Owning process identifier.
+        pid: i32,
+        /// Window identifier.
+        id: u32,
+    },
+}
+
+/// Default number of entries retained by the clipboard history.
+const DEFAULT_CLIPBOARD_HISTORY_CAPACITY: usize = 50;
+
+/// Settings for the clipboard history enabled by `hotki.enable_clipboard_history`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClipboardHistoryConfig {
+    /// Maximum number of distinct text entries retained, most recent first.
+    #[serde(default = "default_clipboard_history_capacity")]
+    pub capacity: usize,
+    /// Regular expressions matched against the focused application name; copies made while a
+    /// matching application is focused are not recorded. Focus is read when the change is
+    /// sampled, which can trail the copy by one sample interval.
+    #[serde(default)]
+    pub exclude_apps: Vec<String>,
+    /// Persist the history to an encrypted file so it survives restarts.
+    #[serde(default)]
+    pub persist: bool,
+}
+
+impl Default for ClipboardHistoryConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CLIPBOARD_HISTORY_CAPACITY,
+            exclude_apps: Vec::new(),
+            persist: false,
+        }
+    }
+}
+
+impl ClipboardHistoryConfig {
+    /// Check that the capacity is positive and every exclusion pattern compiles.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.capacity == 0 {
+            return Err("capacity must be at least 1".to_string());
+        }
+        for pattern in &self.exclude_apps {
+            Regex::new(pattern).map_err(|err| format!("exclude_apps {pattern:?}: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// Whether copies made while `app` is focused must not be recorded.
+    ///
+    /// Patterns that fail to compile exclude nothing; `validate` rejects them at load time.
+    pub fn excludes_app(&self, app: &str) -> bool {
+        self.exclude_apps.iter().any(|pattern| {
+            Regex::new(pattern)
+                .map(|regex| regex.is_match(app))
+                .unwrap_or(false)
+        })
+    }
+}
+
+/// Serde default: retained clipboard history entries.
+fn default_clipboard_history_capacity() -> usize {
+    DEFAULT_CLIPBOARD_HISTORY_CAPACITY
+}
+
+/// Default delay before a supervised service's first restart.
+const DEFAULT_SERVICE_BACKOFF_MS: u64 = 1000;
+
+/// A background process declared by `hotki.services` and supervised while the config is live.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceSpec {
+    /// Unique service name shown in the UI and logs.
+    pub name: String,
+    /// Program path or bare program name resolved by the operating system.
+    pub program: String,
+    /// Literal arguments passed to the program.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Optional working directory, relative to the config entry when relative.
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Environment variables set for the process, overriding inherited values.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// When an exited service is started again.
+    #[serde(default)]
+    pub restart: ServiceRestart,
+    /// Delay before the first restart; doubles on each consecutive quick exit.
+    #[serde(default = "default_service_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+impl ServiceSpec {
+    /// Check the fields that deserialization alone cannot constrain.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        if self.program.is_empty() {
+            return Err("program must not be empty".to_string());
+        }
+        for name in self.env.keys() {
+            if name.is_empty() || name.contains(['=', '\0']) {
+                return Err(format!("env name {name:?} is not a valid variable name"));
+            }
+        }
+        if let Some((name, _)) = self.env.iter().find(|(_, value)| value.contains('\0')) {
+            return Err(format!("env {name:?} value must not contain NUL"));
+        }
+        if self.backoff_ms == 0 {
+            return Err("backoff_ms must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Restart policy for a supervised service.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ServiceRestart {
+    /// Leave the service stopped once it exits.
+    Never,
+    /// Restart only after an unsuccessful exit or a signal.
+    #[default]
+    OnFailure,
+    /// Restart after every exit.
+    Always,
+}
+
+impl ServiceRestart {
+    /// Whether an exit with the given success flag should be followed by a restart.
+    pub fn restarts(self, success: bool) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnFailure => !success,
+            Self::Always => true,
+        }
+    }
+}
+
+/// Serde default: initial service restart delay.
+fn default_service_backoff_ms() -> u64 {
+    DEFAULT_SERVICE_BACKOFF_MS
+}
+
+/// Lifecycle command for an application action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppCommand {
+    /// Bring the running application to the front.
+    Activate,
+    /// Hide the running application.
+    Hide,
+    /// Ask the running application to quit.
+    Quit,
+    /// Hide when frontmost, activate when running, and launch otherwise.
+    Toggle,
+}
+
+/// Target and command for one application lifecycle action.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppSpec {
+    /// Exact AppKit localized name or exact bundle identifier.
+    pub target: String,
+    /// Lifecycle command to apply.
+    pub command: AppCommand,
+}
+
+impl AppSpec {
+    /// Whether `target` should be launched by bundle identifier rather than by name.
+    ///
+    /// Reverse-DNS identifiers have at least three dot-separated segments and no whitespace.
+    pub fn launches_by_bundle_id(&self) -> bool {
+        !self.target.contains(char::is_whitespace)
+            && self
+                .target
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .count()
+                >= 3
+    }
 }
 
 /// Configured target for a relayed key gesture.
@@ -39,6 +252,10 @@ pub enum RelayTarget {
     Focused,
     /// Resolve one exact AppKit localized application name at actuation time.
     ApplicationName(String),
+    /// Resolve one exact application bundle identifier at actuation time.
+    BundleId(String),
+    /// Deliver to one process captured when the action ran.
+    Pid(i32),
 }
 
 /// Chord and configured target for one relayed key gesture.
@@ -49,6 +266,9 @@ pub struct RelaySpec {
     pub chord: String,
     /// Configured destination identity.
     pub target: RelayTarget,
+    /// Whether process targets also receive separate modifier key transitions.
+    #[serde(default)]
+    pub modifier_events: bool,
 }
 
 impl RelaySpec {
@@ -57,6 +277,7 @@ impl RelaySpec {
         Self {
             chord: chord.into(),
             target: RelayTarget::Focused,
+            modifier_events: false,
         }
     }
 
@@ -65,8 +286,57 @@ impl RelaySpec {
         Self {
             chord: chord.into(),
             target: RelayTarget::ApplicationName(app_name.into()),
+            modifier_events: false,
         }
     }
+
+    /// Construct a relay to one exact application bundle identifier.
+    pub fn bundle(bundle_id: impl Into<String>, chord: impl Into<String>) -> Self {
+        Self {
+            chord: chord.into(),
+            target: RelayTarget::BundleId(bundle_id.into()),
+            modifier_events: false,
+        }
+    }
+
+    /// Construct a relay to one captured process identifier.
+    pub fn pid(pid: i32, chord: impl Into<String>) -> Self {
+        Self {
+            chord: chord.into(),
+            target: RelayTarget::Pid(pid),
+            modifier_events: false,
+        }
+    }
+
+    /// Set whether process targets receive separate modifier key transitions.
+    pub fn with_modifier_events(mut self, modifier_events: bool) -> Self {
+        self.modifier_events = modifier_events;
+        self
+    }
+}
+
+/// Ordered chords and pacing for one focused relay sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelaySequenceSpec {
+    /// Chord strings parsed by the engine at actuation time, in posting order.
+    pub chords: Vec<String>,
+    /// Optional delay between chords in milliseconds; `None` uses the engine default.
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+}
+
+/// Text, pacing, and configured target for one typed string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TypeTextSpec {
+    /// Literal text to type.
+    pub text: String,
+    /// Optional delay between characters in milliseconds; `None` types in bulk.
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+    /// Configured destination identity.
+    pub target: RelayTarget,
 }
 
 /// Specification for a direct process execution action.
@@ -87,6 +357,100 @@ pub struct ExecSpec {
     /// Notification type for error exit.
     #[serde(default = "default_err_notify")]
     pub err_notify: NotifyKind,
+    /// Optional text piped to the process's standard input, which is otherwise inherited.
+    #[serde(default)]
+    pub stdin: Option<ExecStdin>,
+    /// Environment variables set for the process, overriding inherited values.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Start from an empty environment instead of inheriting Hotki's.
+    #[serde(default)]
+    pub clear_env: bool,
+    /// Kill the process group when the process runs longer than this many milliseconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// What a press does while an earlier run of the same binding is still in flight.
+    #[serde(default)]
+    pub concurrency: ProcessConcurrency,
+}
+
+impl ExecSpec {
+    /// Check the fields that deserialization alone cannot constrain.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.program.is_empty() {
+            return Err("program must not be empty".to_string());
+        }
+        for name in self.env.keys() {
+            if name.is_empty() || name.contains(['=', '\0']) {
+                return Err(format!("env name {name:?} is not a valid variable name"));
+            }
+        }
+        if let Some((name, _)) = self.env.iter().find(|(_, value)| value.contains('\0')) {
+            return Err(format!("env {name:?} value must not contain NUL"));
+        }
+        if matches!(&self.stdin, Some(ExecStdin::Text(text)) if text.contains('\0')) {
+            return Err("stdin must not contain NUL".to_string());
+        }
+        if self.timeout_ms == Some(0) {
+            return Err("timeout_ms must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Text written to a direct process's standard input.
+///
+/// Written as `"clipboard"`, or as `{ text = "..." }` for literal text; any other string is
+/// rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", try_from = "ExecStdinRepr")]
+pub enum ExecStdin {
+    /// Plain-text clipboard contents captured when the action runs.
+    Clipboard,
+    /// Literal text.
+    Text(String),
+}
+
+/// Accepted spellings of [`ExecStdin`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExecStdinRepr {
+    /// A named source.
+    Source(String),
+    /// A `{ text = "..." }` table.
+    Text {
+        /// Literal text.
+        text: String,
+    },
+}
+
+impl TryFrom<ExecStdinRepr> for ExecStdin {
+    type Error = String;
+
+    fn try_from(repr: ExecStdinRepr) -> Result<Self, Self::Error> {
+        match repr {
+            ExecStdinRepr::Source(source) if source == "clipboard" => Ok(Self::Clipboard),
+            ExecStdinRepr::Source(source) => Err(format!(
+                "unknown stdin source {source:?}; write literal text as {{ text = ... }}"
+            )),
+            ExecStdinRepr::Text { text } => Ok(Self::Text(text)),
+        }
+    }
+}
+
+/// Policy for a process action pressed while its previous run is still in flight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessConcurrency {
+    /// Start another copy alongside the running one.
+    #[default]
+    Parallel,
+    /// Drop the press.
+    Ignore,
+    /// Kill the running process group, then start a fresh run.
+    Restart,
+    /// Run after every earlier run of the binding has finished.
+    Queue,
 }
 
 /// Optional modifiers applied to Shell actions
@@ -102,6 +466,11 @@ pub struct ShellModifiers {
     /// Defaults to Warn
     #[serde(default = "default_err_notify")]
     pub err_notify: NotifyKind,
+
+    /// Policy for presses while an earlier run is in flight
+    /// Defaults to Parallel
+    #[serde(default)]
+    pub concurrency: ProcessConcurrency,
 }
 
 /// Serde default: successful shell command produces no notification.
@@ -116,7 +485,7 @@ fn default_err_notify() -> NotifyKind {
 
 #[cfg(test)]
 mod tests {
-    use super::Action;
+    use super::{Action, AppCommand, AppSpec, ClipboardHistoryConfig, ExecSpec, ExecStdin};
 
     #[test]
     fn action_serde_surface_excludes_navigation_requests() {
@@ -132,6 +501,47 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn app_targets_launch_by_bundle_id_only_for_reverse_dns() {
+        for (target, bundle) in [
+            ("com.apple.Music", true),
+            ("org.mozilla.firefox", true),
+            ("Music", false),
+            ("draw.io", false),
+            ("Visual Studio Code", false),
+            ("My App v1.2.3", false),
+        ] {
+            let spec = AppSpec {
+                target: target.to_string(),
+                command: AppCommand::Toggle,
+            };
+            assert_eq!(spec.launches_by_bundle_id(), bundle, "{target}");
+        }
+    }
+
+    #[test]
+    fn clipboard_history_exclusions_match_app_names_and_validate_patterns() {
+        let config = ClipboardHistoryConfig {
+            exclude_apps: vec!["^1Password".to_string(), "Bitwarden".to_string()],
+            ..ClipboardHistoryConfig::default()
+        };
+        assert_eq!(config.validate(), Ok(()));
+        assert!(config.excludes_app("1Password 7"));
+        assert!(config.excludes_app("Bitwarden"));
+        assert!(!config.excludes_app("Safari"));
+
+        let invalid = ClipboardHistoryConfig {
+            exclude_apps: vec!["(".to_string()],
+            ..ClipboardHistoryConfig::default()
+        };
+        assert!(invalid.validate().is_err());
+        let empty = ClipboardHistoryConfig {
+            capacity: 0,
+            ..ClipboardHistoryConfig::default()
+        };
+        assert!(empty.validate().is_err());
+    }
 }
 
 impl Default for ShellModifiers {
@@ -139,6 +549,7 @@ impl Default for ShellModifiers {
         Self {
             ok_notify: default_ok_notify(),
             err_notify: default_err_notify(),
+            concurrency: ProcessConcurrency::default(),
         }
     }
 }
@@ -178,4 +589,48 @@ impl ShellSpec {
             Self::WithMods(_, m) => m.err_notify,
         }
     }
+
+    /// Get the policy for presses while an earlier run is in flight
+    pub fn concurrency(&self) -> ProcessConcurrency {
+        match self {
+            Self::Cmd(_) => ProcessConcurrency::default(),
+            Self::WithMods(_, m) => m.concurrency,
+        }
+    }
+
+    #[test]
+    fn exec_spec_parses_process_environment_and_rejects_invalid_fields() {
+        let spec: ExecSpec = serde_json::from_str(
+            r#"{
+                "program": "/usr/bin/env",
+                "env": { "LANG": "C" },
+                "clear_env": true,
+                "stdin": { "text": "hello" },
+                "timeout_ms": 500
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(spec.env.get("LANG").map(String::as_str), Some("C"));
+        assert!(spec.clear_env);
+        assert_eq!(spec.stdin, Some(ExecStdin::Text("hello".to_string())));
+        assert_eq!(spec.timeout_ms, Some(500));
+        assert_eq!(spec.validate(), Ok(()));
+
+        let clipboard: ExecSpec =
+            serde_json::from_str(r#"{ "program": "cat", "stdin": "clipboard" }"#).unwrap();
+        assert_eq!(clipboard.stdin, Some(ExecStdin::Clipboard));
+        let err = serde_json::from_str::<ExecSpec>(r#"{ "program": "cat", "stdin": "hello" }"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("\"hello\""), "{err}");
+
+        for (field, json) in [
+            ("env name", r#"{ "program": "env", "env": { "A=B": "1" } }"#),
+            ("timeout_ms", r#"{ "program": "env", "timeout_ms": 0 }"#),
+            ("program", r#"{ "program": "" }"#),
+        ] {
+            let spec: ExecSpec = serde_json::from_str(json).unwrap();
+            let err = spec.validate().unwrap_err();
+            assert!(err.contains(field), "{err}");
+        }
+    }
 }
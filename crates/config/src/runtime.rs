@@ -2,6 +2,7 @@
 
 use std::path::Path;
 
+use hotki_protocol::rpc::ChordExplanation;
 use mac_keycode::Chord;
 use script::{config::LoadedConfig, types::ModeFrame};
 pub use script::{
@@ -9,11 +10,11 @@ pub use script::{
     selector::{SelectorConfig, SelectorItem},
     types::{
         ActionRepeatPermission, Binding, BindingKind, Effect, HandlerRef, ModeCtx, ModeId, ModeRef,
-        NavRequest, RenderedState, RepeatSpec,
+        NavRequest, RenderedState, RepeatSpec, ScheduledAction, SourcePos,
     },
 };
 
-use crate::{Error, Style, script};
+use crate::{ClipboardHistoryConfig, Error, ServiceSpec, Style, script};
 
 /// Loaded, retained configuration runtime used by the Hotki engine.
 pub struct ConfigRuntime(LoadedConfig);
@@ -33,6 +34,21 @@ impl ConfigRuntime {
         self.0.base_style.clone()
     }
 
+    /// Return the clipboard history settings, or `None` when the config does not enable it.
+    pub fn clipboard_history(&self) -> Option<ClipboardHistoryConfig> {
+        self.0.clipboard_history.clone()
+    }
+
+    /// Return the supervised services declared by the config, in declaration order.
+    pub fn services(&self) -> Vec<ServiceSpec> {
+        self.0.services.clone()
+    }
+
+    /// Return the actions declared by `hotki.schedule`, in declaration order.
+    pub fn schedules(&self) -> Vec<ScheduledAction> {
+        self.0.schedules.clone()
+    }
+
     /// Ensure this runtime's root mode is installed in an empty stack.
     pub fn ensure_stack(&self, stack: &mut ModeStack) {
         if stack.0.is_empty() {
@@ -74,6 +90,16 @@ impl ConfigRuntime {
         script::handler::execute_handler_with_permission(&mut self.0, handler, ctx, repeat)
     }
 
+    /// Execute a `ctx:with_clipboard` callback with the clipboard's plain text, if any.
+    pub fn execute_clipboard_handler(
+        &mut self,
+        handler: &HandlerRef,
+        ctx: &ModeCtx,
+        text: Option<String>,
+    ) -> Result<HandlerResult, Error> {
+        script::handler::execute_clipboard_handler(&mut self.0, handler, ctx, text)
+    }
+
     /// Execute a selector's selection callback with the chosen item and final query.
     pub fn execute_selector_selection(
         &mut self,
@@ -117,6 +143,17 @@ impl ConfigRuntime {
     pub fn resolve_binding<'a>(rendered: &'a RenderedState, chord: &Chord) -> Option<&'a Binding> {
         script::render::resolve_binding(rendered, chord)
     }
+
+    /// Explain how `chord` resolves against `stack` without changing it.
+    pub fn explain(
+        &mut self,
+        stack: &ModeStack,
+        ctx: &ModeCtx,
+        hud_visible: bool,
+        chord: &Chord,
+    ) -> Result<ChordExplanation, Error> {
+        script::explain::explain_frames(&mut self.0, &stack.0, ctx, hud_visible, chord)
+    }
 }
 
 impl ModeStack {
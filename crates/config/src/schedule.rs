@@ -0,0 +1,278 @@
+//! Wall-clock schedules declared by `hotki.schedule`.
+//!
+//! A schedule is a five-field cron expression (`minute hour day-of-month month day-of-week`) or
+//! a daily `HH:MM` time, evaluated against local wall-clock time at minute resolution.
+
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// Days searched for the next match before a schedule is treated as unsatisfiable.
+///
+/// Eight years covers `29 2` schedules across the century-year leap rule.
+const SEARCH_DAYS: i64 = 366 * 8;
+/// Day-of-month mask accepting every day from 1 to 31.
+const ALL_DAYS: u32 = !1;
+/// Day-of-week mask accepting every day once Sunday's two spellings are folded together.
+const ALL_WEEKDAYS: u8 = 0x7f;
+
+/// What happens to runs missed while the machine slept or Hotki was not running the timer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedRuns {
+    /// Drop missed runs and wait for the next scheduled time.
+    #[default]
+    Skip,
+    /// Run once on wake for any number of missed runs.
+    Coalesce,
+}
+
+/// A parsed wall-clock schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// Matching minutes, bit `n` for minute `n`.
+    minutes: u64,
+    /// Matching hours, bit `n` for hour `n`.
+    hours: u32,
+    /// Matching days of the month, bit `n` for day `n`.
+    days: u32,
+    /// Matching months, bit `n` for month `n`.
+    months: u16,
+    /// Matching weekdays, bit `n` for days after Sunday.
+    weekdays: u8,
+    /// Whether the day-of-month field accepts every day.
+    any_day: bool,
+    /// Whether the day-of-week field accepts every day.
+    any_weekday: bool,
+    /// Source text shown in logs and diagnostics.
+    source: String,
+}
+
+impl Schedule {
+    /// Parse a five-field cron expression.
+    ///
+    /// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,3`), and steps (`*/15`, `0-30/10`).
+    /// Day-of-week runs from 0 (Sunday) to 7 (also Sunday). As in cron, when both day fields are
+    /// restricted a time matches if either one does. A field counts as restricted unless it
+    /// accepts every value, however it is spelled: `1-31` and `*/1` behave like `*`, while
+    /// `*/2` restricts the field, unlike Vixie cron, which treats any field starting with `*` as
+    /// unrestricted.
+    pub fn cron(expr: &str) -> Result<Self, String> {
+        let fields = expr.split_whitespace().collect::<Vec<_>>();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(format!(
+                "cron expression {expr:?} must have 5 fields, found {}",
+                fields.len()
+            ));
+        };
+        let days = parse_field(day, "day-of-month", 1, 31)? as u32;
+        let weekdays = parse_field(weekday, "day-of-week", 0, 7)?;
+        let weekdays = ((weekdays | (weekdays >> 7)) & 0x7f) as u8;
+        Ok(Self {
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)? as u32,
+            days,
+            months: parse_field(month, "month", 1, 12)? as u16,
+            weekdays,
+            any_day: days == ALL_DAYS,
+            any_weekday: weekdays == ALL_WEEKDAYS,
+            source: fields.join(" "),
+        })
+    }
+
+    /// Parse a daily `HH:MM` time in 24-hour form.
+    pub fn at(time: &str) -> Result<Self, String> {
+        let parsed = NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|_| format!("at {time:?} must be a 24-hour HH:MM time"))?;
+        let mut schedule = Self::cron(&format!("{} {} * * *", parsed.minute(), parsed.hour()))?;
+        schedule.source = format!("at {time}");
+        Ok(schedule)
+    }
+
+    /// Whether `time` falls on a scheduled minute.
+    pub fn matches(&self, time: NaiveDateTime) -> bool {
+        self.matches_date(time.date())
+            && self.hours & (1 << time.hour()) != 0
+            && self.minutes & (1 << time.minute()) != 0
+    }
+
+    /// First scheduled minute strictly after `after`, or `None` when no date ever matches.
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date();
+        for offset in 0..SEARCH_DAYS {
+            if self.matches_date(date) {
+                let from = if offset == 0 {
+                    (start.hour(), start.minute())
+                } else {
+                    (0, 0)
+                };
+                if let Some(time) = self.first_time_from(from) {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// Whether the day-of-month, month, and day-of-week fields accept `date`.
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+
+    /// Earliest scheduled time of day at or after `(hour, minute)`.
+    fn first_time_from(&self, (hour, minute): (u32, u32)) -> Option<NaiveTime> {
+        (hour..24)
+            .filter(|h| self.hours & (1 << h) != 0)
+            .find_map(|h| {
+                let first = if h == hour { minute } else { 0 };
+                (first..60)
+                    .find(|m| self.minutes & (1 << m) != 0)
+                    .and_then(|m| NaiveTime::from_hms_opt(h, m, 0))
+            })
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Parse one cron field into a bitmask over `min..=max`.
+fn parse_field(field: &str, name: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("{name} step {step:?} must be a positive number"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (
+                parse_value(low, name, min, max)?,
+                parse_value(high, name, min, max)?,
+            )
+        } else {
+            let value = parse_value(range, name, min, max)?;
+            (value, if step > 1 { max } else { value })
+        };
+        if low > high {
+            return Err(format!("{name} range {range:?} is reversed"));
+        }
+        for value in (low..=high).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// Parse one numeric cron value and check it against the field's bounds.
+fn parse_value(text: &str, name: &str, min: u32, max: u32) -> Result<u32, String> {
+    text.parse::<u32>()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or_else(|| format!("{name} value {text:?} must be a number from {min} to {max}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2026-01-05 is a Monday.
+        NaiveDate::from_ymd_opt(2026, 1, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .expect("valid time")
+    }
+
+    #[test]
+    fn weekday_cron_skips_the_weekend() {
+        let schedule = Schedule::cron("0 9 * * 1-5").expect("parse");
+        assert_eq!(schedule.next_after(time(5, 8, 30)), Some(time(5, 9, 0)));
+        assert_eq!(schedule.next_after(time(5, 9, 0)), Some(time(6, 9, 0)));
+        assert_eq!(schedule.next_after(time(9, 10, 0)), Some(time(12, 9, 0)));
+        assert!(schedule.matches(time(7, 9, 0)));
+        assert!(!schedule.matches(time(10, 9, 0)));
+    }
+
+    #[test]
+    fn steps_lists_and_sunday_aliases_parse() {
+        let schedule = Schedule::cron("*/20 8,17 * * 7").expect("parse");
+        assert_eq!(schedule.next_after(time(5, 8, 0)), Some(time(11, 8, 0)));
+        assert_eq!(schedule.next_after(time(11, 8, 40)), Some(time(11, 17, 0)));
+        assert_eq!(schedule.to_string(), "*/20 8,17 * * 7");
+
+        let either = Schedule::cron("0 0 1 * 1").expect("parse");
+        assert!(either.matches(time(1, 0, 0)));
+        assert!(either.matches(time(5, 0, 0)));
+        assert!(!either.matches(time(6, 0, 0)));
+    }
+
+    #[test]
+    fn day_fields_accepting_every_value_are_unrestricted() {
+        let mondays = Schedule::cron("0 0 1-31 * 1").expect("parse");
+        assert!(mondays.matches(time(5, 0, 0)));
+        assert!(!mondays.matches(time(6, 0, 0)));
+        assert_eq!(
+            Schedule::cron("0 0 15 * 0-7")
+                .expect("parse")
+                .next_after(time(5, 0, 0)),
+            Some(time(15, 0, 0))
+        );
+
+        let odd_days_or_mondays = Schedule::cron("0 0 */2 * 1").expect("parse");
+        assert!(odd_days_or_mondays.matches(time(3, 0, 0)));
+        assert!(odd_days_or_mondays.matches(time(12, 0, 0)));
+        assert!(!odd_days_or_mondays.matches(time(6, 0, 0)));
+    }
+
+    #[test]
+    fn daily_time_runs_next_at_that_minute() {
+        let schedule = Schedule::at("17:30").expect("parse");
+        assert_eq!(schedule.next_after(time(5, 17, 29)), Some(time(5, 17, 30)));
+        assert_eq!(schedule.next_after(time(5, 17, 30)), Some(time(6, 17, 30)));
+        assert_eq!(schedule.to_string(), "at 17:30");
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        for (expr, expected) in [
+            ("0 9 * *", "must have 5 fields"),
+            ("60 9 * * *", "minute value \"60\""),
+            ("0 9 * * 1-8", "day-of-week value \"8\""),
+            ("*/0 9 * * *", "minute step"),
+            ("0 17-9 * * *", "reversed"),
+        ] {
+            let err = Schedule::cron(expr).expect_err(expr);
+            assert!(err.contains(expected), "{expr}: {err}");
+        }
+        assert!(Schedule::at("25:00").is_err());
+        assert!(
+            Schedule::cron("0 0 31 2 *")
+                .expect("parse")
+                .next_after(time(5, 0, 0))
+                .is_none()
+        );
+    }
+}
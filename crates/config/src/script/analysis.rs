@@ -0,0 +1,288 @@
+//! Binding conflict and shadowing analysis run by `hotki check`.
+//!
+//! The pass renders the root and every reachable submenu for a set of representative contexts:
+//! no focused window, plus one window for each application declared with `app_mode`. Each mode is
+//! inspected before duplicate chords are dropped, so both declaration sites can be reported. Each
+//! handler binding also runs once per context so the exec specs it passes to `ctx:exec` are
+//! validated; the actions it queues are discarded.
+
+use std::fmt;
+
+use mac_keycode::Chord;
+use serde::Serialize;
+
+use super::{
+    Binding, BindingKind, LoadedConfig, ModeFrame, SourcePos,
+    handler::execute_handler,
+    render::location_string,
+    walk::{ModeVisit, ModeVisitor, app_mode_apps, synthetic_window, walk_modes},
+};
+use crate::{Diagnostic, Error, Severity};
+
+/// Well-known macOS shortcuts that a root binding would take over system-wide.
+const SYSTEM_SHORTCUTS: &[(&str, &str)] = &[
+    ("cmd+space", "Spotlight"),
+    ("cmd+tab", "the application switcher"),
+    ("cmd+shift+tab", "the application switcher"),
+    ("cmd+`", "window cycling"),
+    ("cmd+q", "Quit"),
+    ("cmd+w", "Close Window"),
+    ("cmd+h", "Hide"),
+    ("cmd+m", "Minimize"),
+    ("cmd+c", "Copy"),
+    ("cmd+v", "Paste"),
+    ("cmd+x", "Cut"),
+    ("cmd+z", "Undo"),
+    ("cmd+a", "Select All"),
+    ("cmd+s", "Save"),
+    ("cmd+opt+esc", "Force Quit"),
+    ("ctrl+cmd+q", "Lock Screen"),
+    ("ctrl+cmd+f", "Full Screen"),
+    ("cmd+shift+3", "the screenshot tools"),
+    ("cmd+shift+4", "the screenshot tools"),
+    ("cmd+shift+5", "the screenshot tools"),
+    ("ctrl+space", "input source switching"),
+    ("ctrl+up", "Mission Control"),
+    ("ctrl+down", "App Exposé"),
+    ("ctrl+left", "Spaces navigation"),
+    ("ctrl+right", "Spaces navigation"),
+];
+
+/// Category of a binding analysis warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingWarningKind {
+    /// Two bindings in one mode use the same chord; the first wins.
+    DuplicateChord,
+    /// A child mode binds a chord that an ancestor's `global` binding also binds.
+    ShadowedGlobal,
+    /// A root binding takes over a well-known system shortcut.
+    SystemShortcut,
+    /// A handler passes `ctx:exec` a spec it rejects, so the binding fails whenever it runs.
+    InvalidExecSpec,
+}
+
+/// One finding from the binding analysis pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BindingWarning {
+    /// What was found.
+    pub kind: BindingWarningKind,
+    /// Mode titles from the root to the mode where the finding applies.
+    pub mode: Vec<String>,
+    /// Application of the context the mode was rendered for, if any.
+    pub app: Option<String>,
+    /// Chord in canonical form.
+    pub chord: String,
+    /// Declaration sites involved, winning binding first.
+    pub locations: Vec<SourcePos>,
+    /// Human-readable summary.
+    pub message: String,
+}
+
+impl BindingWarningKind {
+    /// Stable snake_case code used in structured diagnostics.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::DuplicateChord => "duplicate_chord",
+            Self::ShadowedGlobal => "shadowed_global",
+            Self::SystemShortcut => "system_shortcut",
+            Self::InvalidExecSpec => "invalid_exec_spec",
+        }
+    }
+}
+
+impl BindingWarning {
+    /// Convert the warning into a structured diagnostic located at the winning binding.
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let pos = self.locations.first();
+        let mut message = format!("{}: {}", self.mode.join(" › "), self.message);
+        if let Some(app) = &self.app {
+            message.push_str(&format!(" (when {app} is focused)"));
+        }
+        Diagnostic {
+            path: pos.and_then(|pos| pos.path.clone()),
+            line: pos.and_then(|pos| pos.line),
+            col: pos.and_then(|pos| pos.col),
+            severity: Severity::Warning,
+            code: self.kind.code().to_string(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for BindingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.mode.join(" › "), self.message)?;
+        if let Some(app) = &self.app {
+            write!(f, " (when {app} is focused)")?;
+        }
+        for pos in &self.locations {
+            write!(f, "\n  at {}", location_string(pos))?;
+        }
+        Ok(())
+    }
+}
+
+/// Analyze every reachable mode of `cfg` for conflicting and shadowed bindings.
+///
+/// Modes whose renderer fails for a synthetic context are skipped; loading already validated
+/// the root for the default context.
+pub(crate) fn analyze_bindings(cfg: &mut LoadedConfig) -> Vec<BindingWarning> {
+    let mut apps = vec![None];
+    apps.extend(app_mode_apps(cfg).into_iter().map(Some));
+
+    let mut analysis = Analysis {
+        app: None,
+        warnings: Vec::new(),
+    };
+    for app in apps {
+        analysis.app = app.clone();
+        if let Err(err) = walk_modes(cfg, app.map(synthetic_window), &mut analysis) {
+            tracing::debug!(error = %err, "binding analysis stopped");
+        }
+    }
+    analysis.warnings
+}
+
+/// Analysis state shared across contexts.
+struct Analysis {
+    /// Application focused in the current context.
+    app: Option<String>,
+    /// Findings so far, without repeats across contexts.
+    warnings: Vec<BindingWarning>,
+}
+
+impl ModeVisitor for Analysis {
+    fn enter(&mut self, cfg: &mut LoadedConfig, mode: &ModeVisit<'_>) {
+        for (dropped, first) in mode.dropped {
+            let message = format!(
+                "duplicate chord '{}': \"{}\" is ignored in favor of \"{}\"",
+                dropped.chord, dropped.desc, first.desc
+            );
+            self.report(
+                mode,
+                BindingWarningKind::DuplicateChord,
+                first,
+                dropped,
+                message,
+            );
+        }
+
+        for binding in mode.own {
+            if let Some(global) = mode
+                .inherited
+                .iter()
+                .find(|global| global.chord == binding.chord)
+            {
+                let message = format!(
+                    "\"{}\" shadows the global binding \"{}\" on '{}'",
+                    binding.desc, global.desc, binding.chord
+                );
+                self.report(
+                    mode,
+                    BindingWarningKind::ShadowedGlobal,
+                    binding,
+                    global,
+                    message,
+                );
+            }
+            if mode.depth() == 0
+                && let Some(owner) = system_shortcut(&binding.chord)
+            {
+                let message = format!(
+                    "'{}' (\"{}\") takes over the system shortcut for {owner}",
+                    binding.chord, binding.desc
+                );
+                self.push(BindingWarning {
+                    kind: BindingWarningKind::SystemShortcut,
+                    mode: mode.trail.to_vec(),
+                    app: self.app.clone(),
+                    chord: binding.chord.to_string(),
+                    locations: binding.pos.iter().cloned().collect(),
+                    message,
+                });
+            }
+        }
+        for binding in mode.own {
+            self.exec_spec(cfg, mode, binding);
+        }
+    }
+
+    fn failed(&mut self, frame: &ModeFrame, err: Error) -> Result<(), Error> {
+        tracing::debug!(mode = %frame.title, error = %err, "skipping binding analysis");
+        Ok(())
+    }
+}
+
+impl Analysis {
+    /// Run a handler binding and report an exec spec that `ctx:exec` rejects.
+    ///
+    /// Queued actions are collected, never executed. Other handler errors usually depend on the
+    /// synthetic context and are left to surface when the binding runs.
+    fn exec_spec(&mut self, cfg: &mut LoadedConfig, mode: &ModeVisit<'_>, binding: &Binding) {
+        let BindingKind::Handler(handler) = &binding.kind else {
+            return;
+        };
+        let Err(err) = execute_handler(cfg, handler, mode.ctx) else {
+            return;
+        };
+        let code = BindingWarningKind::InvalidExecSpec.code();
+        let Some(rejection) = err
+            .diagnostics()
+            .into_iter()
+            .find(|diagnostic| diagnostic.code == code)
+        else {
+            tracing::debug!(binding = %binding.desc, error = %err, "skipping handler");
+            return;
+        };
+        self.push(BindingWarning {
+            kind: BindingWarningKind::InvalidExecSpec,
+            mode: mode.trail.to_vec(),
+            app: self.app.clone(),
+            chord: binding.chord.to_string(),
+            locations: binding.pos.iter().cloned().collect(),
+            message: format!("\"{}\" always fails: {}", binding.desc, rejection.message),
+        });
+    }
+
+    /// Record a finding that involves two declarations.
+    fn report(
+        &mut self,
+        mode: &ModeVisit<'_>,
+        kind: BindingWarningKind,
+        first: &Binding,
+        second: &Binding,
+        message: String,
+    ) {
+        let locations = first.pos.iter().chain(&second.pos).cloned().collect();
+        self.push(BindingWarning {
+            kind,
+            mode: mode.trail.to_vec(),
+            app: self.app.clone(),
+            chord: first.chord.to_string(),
+            locations,
+            message,
+        });
+    }
+
+    /// Record a finding unless the same one was already found for another context.
+    fn push(&mut self, warning: BindingWarning) {
+        let repeated = self.warnings.iter().any(|seen| {
+            seen.kind == warning.kind
+                && seen.mode == warning.mode
+                && seen.chord == warning.chord
+                && seen.locations == warning.locations
+        });
+        if !repeated {
+            self.warnings.push(warning);
+        }
+    }
+}
+
+/// Name of the system feature owning `chord`, if it is a well-known shortcut.
+fn system_shortcut(chord: &Chord) -> Option<&'static str> {
+    SYSTEM_SHORTCUTS
+        .iter()
+        .find(|(spec, _)| Chord::parse(spec).as_ref() == Some(chord))
+        .map(|(_, owner)| *owner)
+}
@@ -10,12 +10,12 @@ use ruau::{
 };
 
 use super::{
-    ModeRef,
+    ModeRef, ScheduledAction,
     callback::{CallbackContext, CallbackRegistry, SharedCallbackRegistry},
     diagnostics,
     util::lock_unpoisoned,
 };
-use crate::{Error, Style, StyleProvenance};
+use crate::{ClipboardHistoryConfig, Error, ServiceSpec, Style, StyleProvenance};
 
 /// Gas budget for each dynamic config entrypoint.
 pub const SCRIPT_GAS_LIMIT: u64 = 4_000_000;
@@ -48,6 +48,12 @@ pub struct LoadedConfig {
     pub(crate) entry_gas: u64,
     /// Gas spent by the initial root validation render.
     pub(crate) validation_gas: u64,
+    /// Clipboard history settings declared by the entry, or `None` when history is disabled.
+    pub(crate) clipboard_history: Option<ClipboardHistoryConfig>,
+    /// Supervised services declared by the entry, in declaration order.
+    pub(crate) services: Vec<ServiceSpec>,
+    /// Scheduled actions declared by the entry, in declaration order.
+    pub(crate) schedules: Vec<ScheduledAction>,
 }
 
 impl LoadedConfig {
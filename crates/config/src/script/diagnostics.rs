@@ -13,7 +13,7 @@ use ruau::{
 };
 
 use super::{config::SourceMap, util::lock_unpoisoned};
-use crate::{Error, error::excerpt_at};
+use crate::{Diagnostic, Error, Severity, error::excerpt_at};
 
 /// Build a locationless `Error::Validation` with only a path and message.
 pub fn config_validation(path: Option<PathBuf>, err: impl fmt::Display) -> Error {
@@ -23,6 +23,7 @@ pub fn config_validation(path: Option<PathBuf>, err: impl fmt::Display) -> Error
         col: None,
         message: err.to_string(),
         excerpt: None,
+        diagnostics: Vec::new(),
     }
 }
 
@@ -89,6 +90,7 @@ pub fn config_script_error<'s>(
         col,
         message,
         excerpt,
+        diagnostics: Vec::new(),
     }
 }
 
@@ -104,12 +106,27 @@ pub fn config_type_error(
         .and_then(|diagnostic| source_position(diagnostic.primary_location, line_offset))
         .map(|(line, col)| (Some(line), Some(col), Some(excerpt_at(source, line, col))))
         .unwrap_or((None, None, None));
+    let findings = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let position = source_position(diagnostic.primary_location, line_offset);
+            Diagnostic {
+                path: Some(path.to_path_buf()),
+                line: position.map(|(line, _)| line),
+                col: position.map(|(_, col)| col),
+                severity: Severity::Error,
+                code: diagnostic.category.to_string(),
+                message: type_diagnostic_message(diagnostic).to_string(),
+            }
+        })
+        .collect();
     Error::Validation {
         path: Some(path.to_path_buf()),
         line,
         col,
         message: render_type_diagnostics(path, diagnostics, line_offset),
         excerpt,
+        diagnostics: findings,
     }
 }
 
@@ -203,16 +220,21 @@ fn render_type_diagnostics(
                 "{} {}: {}",
                 site,
                 diagnostic.category,
-                diagnostic
-                    .context
-                    .as_deref()
-                    .unwrap_or("type checker diagnostic")
+                type_diagnostic_message(diagnostic)
             )
         })
         .collect::<Vec<_>>()
         .join("; ")
 }
 
+/// Checker context text for one diagnostic, with a generic fallback.
+fn type_diagnostic_message(diagnostic: &TypeDiagnostic) -> &str {
+    diagnostic
+        .context
+        .as_deref()
+        .unwrap_or("type checker diagnostic")
+}
+
 /// Convert a checker source location into 1-based line and column coordinates.
 fn source_position(location: DiagnosticLocation, line_offset: usize) -> Option<(usize, usize)> {
     if location == DiagnosticLocation::missing() {
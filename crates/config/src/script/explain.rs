@@ -0,0 +1,174 @@
+//! Chord resolution explanations for `hotki explain` and the `explain_chord` RPC.
+//!
+//! An explanation renders the stack the same way dispatch does, then renders each frame once more
+//! while recording the window conditions its renderer consults, such as reading `ctx.window.app`
+//! or calling `window:app_matches`, so a `when_app` renderer for another application shows up
+//! without any cooperation from the config.
+
+use std::{collections::HashSet, mem, path::Path};
+
+use hotki_protocol::{
+    FocusSnapshot,
+    rpc::{ChordExplanation, ExplainedBinding, RenderCondition},
+};
+use mac_keycode::Chord;
+
+use super::{
+    Binding, BindingKind, LoadedConfig, ModeCtx, ModeFrame, diagnostics,
+    host_userdata::ConditionLog,
+    render::{location_string, render_mode_recorded, render_stack, resolve_binding},
+    util::lock_unpoisoned,
+    walk::context,
+};
+use crate::{
+    Error,
+    runtime::{ConfigRuntime, ModeStack},
+};
+
+/// Load the config at `config`, enter the submenus titled `path` from the root, and explain how
+/// `chord` resolves there for an optional synthetic focused window.
+///
+/// The HUD counts as visible once `path` enters a submenu.
+pub fn explain_chord(
+    config: &Path,
+    window: Option<FocusSnapshot>,
+    path: &[String],
+    chord: &str,
+) -> Result<ChordExplanation, Error> {
+    let Some(chord) = Chord::parse(chord) else {
+        return Err(diagnostics::config_validation(
+            Some(config.to_path_buf()),
+            format!("invalid chord '{chord}'"),
+        ));
+    };
+    let mut runtime = ConfigRuntime::load(config)?;
+    let mut stack = ModeStack::default();
+    runtime.ensure_stack(&mut stack);
+    for title in path {
+        let ctx = context(window.clone(), stack.depth());
+        let rendered = runtime.render(&mut stack, &ctx)?.state;
+        let entry = rendered
+            .bindings
+            .into_iter()
+            .find_map(|(entry_chord, binding)| match binding.kind {
+                BindingKind::Mode(mode) if binding.desc == *title => Some((
+                    mode,
+                    binding.mode_id.map(|id| (entry_chord, id)),
+                    binding.mode_capture,
+                )),
+                _ => None,
+            });
+        let Some((mode, entered_via, capture)) = entry else {
+            return Err(diagnostics::config_validation(
+                Some(config.to_path_buf()),
+                format!("no submenu titled '{title}' in {}", stack_title(&stack)),
+            ));
+        };
+        stack.push(title.clone(), mode, entered_via, capture);
+    }
+    let ctx = context(window, stack.depth());
+    runtime.explain(&stack, &ctx, stack.depth() > 0, &chord)
+}
+
+/// Explain how `chord` resolves against `frames` without changing the caller's stack.
+pub(crate) fn explain_frames(
+    cfg: &mut LoadedConfig,
+    frames: &[ModeFrame],
+    ctx: &ModeCtx,
+    hud_visible: bool,
+    chord: &Chord,
+) -> Result<ChordExplanation, Error> {
+    let mut frames = frames.to_vec();
+    let base_style = cfg.base_style.clone();
+    let rendered = render_stack(cfg, &mut frames, ctx, &base_style)?.rendered;
+    let Some((top, parents)) = frames.split_last() else {
+        return Err(diagnostics::config_validation(
+            cfg.path.clone(),
+            "mode stack is empty",
+        ));
+    };
+
+    let matched = resolve_binding(&rendered, chord).map(|binding| {
+        let declared_in = if top.rendered.iter().any(|own| own.chord == *chord) {
+            top
+        } else {
+            parents
+                .iter()
+                .rev()
+                .find(|frame| {
+                    frame
+                        .rendered
+                        .iter()
+                        .any(|own| own.chord == *chord && own.flags.global)
+                })
+                .unwrap_or(top)
+        };
+        explained(binding, &declared_in.title, false)
+    });
+
+    let mut seen: HashSet<String> = top
+        .rendered
+        .iter()
+        .map(|own| own.chord.to_string())
+        .collect();
+    let mut inherited = Vec::new();
+    for frame in parents.iter().rev() {
+        for binding in frame.rendered.iter().filter(|binding| binding.flags.global) {
+            let shadowed = !seen.insert(binding.chord.to_string());
+            inherited.push(explained(binding, &frame.title, shadowed));
+        }
+    }
+
+    let mut conditions = Vec::new();
+    for (depth, frame) in frames.iter().enumerate() {
+        let log = ConditionLog::default();
+        let output = render_mode_recorded(cfg, frame, ctx, depth == 0, Some(log.clone()))?;
+        let app = ctx.window.as_ref().map(|window| window.app.as_str());
+        let inactive = output
+            .app_modes
+            .iter()
+            .filter(|app_mode| Some(app_mode.app.as_str()) != app)
+            .map(|app_mode| format!("app_mode(\"{}\") is inactive", app_mode.app));
+        let consulted = mem::take(&mut *lock_unpoisoned(&log));
+        conditions.extend(
+            consulted
+                .into_iter()
+                .chain(inactive)
+                .map(|condition| RenderCondition {
+                    mode: frame.title.clone(),
+                    condition,
+                }),
+        );
+    }
+
+    Ok(ChordExplanation {
+        chord: chord.to_string(),
+        mode: frames.iter().map(|frame| frame.title.clone()).collect(),
+        matched,
+        inherited,
+        capture: hud_visible && rendered.capture,
+        conditions,
+    })
+}
+
+/// Describe one binding declared in the mode titled `mode`.
+fn explained(binding: &Binding, mode: &str, shadowed: bool) -> ExplainedBinding {
+    ExplainedBinding {
+        chord: binding.chord.to_string(),
+        desc: binding.desc.clone(),
+        mode: mode.to_string(),
+        is_mode: matches!(binding.kind, BindingKind::Mode(_)),
+        global: binding.flags.global,
+        hidden: binding.flags.hidden,
+        stay: binding.flags.stay,
+        source: binding.pos.as_ref().map(location_string),
+        shadowed,
+    }
+}
+
+/// Title path of the active mode for error messages.
+fn stack_title(stack: &ModeStack) -> String {
+    let mut titles = vec!["root".to_string()];
+    titles.extend(stack.breadcrumbs());
+    titles.join("/")
+}
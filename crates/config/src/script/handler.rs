@@ -1,9 +1,10 @@
 use ruau::vm::ScriptError;
 
 use super::{
-    ActionCtx, ActionRepeatPermission, HandlerRef, LoadedConfig, ModeCtx, SelectorItem, diagnostics,
+    ActionCtx, ActionRepeatPermission, HandlerRef, LoadedConfig, ModeCtx, SelectorItem,
+    analysis::BindingWarningKind, diagnostics,
 };
-use crate::Error;
+use crate::{Diagnostic, Error, Severity};
 
 /// Result of executing a handler closure.
 #[derive(Debug)]
@@ -20,6 +21,41 @@ fn collect_handler_result(action_ctx: &ActionCtx) -> HandlerResult {
     HandlerResult { effects, stay }
 }
 
+/// Give a handler failure caused by `ctx:exec` rejecting its spec an `invalid_exec_spec`
+/// diagnostic carrying the rejection.
+fn tag_exec_rejection(action_ctx: &ActionCtx, err: Error) -> Error {
+    let Some(reason) = action_ctx.exec_rejection() else {
+        return err;
+    };
+    let Error::Validation {
+        path,
+        line,
+        col,
+        message,
+        excerpt,
+        ..
+    } = err
+    else {
+        return err;
+    };
+    let diagnostics = vec![Diagnostic {
+        path: path.clone(),
+        line,
+        col,
+        severity: Severity::Error,
+        code: BindingWarningKind::InvalidExecSpec.code().to_string(),
+        message: reason,
+    }];
+    Error::Validation {
+        path,
+        line,
+        col,
+        message,
+        excerpt,
+        diagnostics,
+    }
+}
+
 /// Execute a handler closure and collect its queued effects.
 pub fn execute_handler(
     cfg: &mut LoadedConfig,
@@ -72,6 +108,61 @@ fn execute_handler_inner(
         });
 
     if let Some(err) = script_error {
+        let err = tag_exec_rejection(&action_ctx, err);
+        action_ctx.invalidate();
+        drop(action_ctx);
+        cfg.synchronize_callbacks()?;
+        return Err(err);
+    }
+    if let Err(error) = step {
+        action_ctx.invalidate();
+        drop(action_ctx);
+        cfg.synchronize_callbacks()?;
+        return Err(diagnostics::config_retained_error(cfg.path.clone(), &error));
+    }
+    let result = collect_handler_result(&action_ctx);
+    drop(action_ctx);
+    cfg.synchronize_callbacks()?;
+    Ok(result)
+}
+
+/// Execute a clipboard callback with `(ctx, text)` arguments.
+///
+/// `text` is `nil` in Luau when the clipboard holds no plain text.
+pub fn execute_clipboard_handler(
+    cfg: &mut LoadedConfig,
+    handler: &HandlerRef,
+    ctx: &ModeCtx,
+    text: Option<String>,
+) -> Result<HandlerResult, Error> {
+    let action_ctx = ActionCtx::new(ctx.clone(), ActionRepeatPermission::Keyless);
+    let mut script_error = None;
+    let path = cfg.path.clone();
+    let sources = cfg.sources.clone();
+
+    let options = LoadedConfig::entry_options();
+    let mut context = cfg.callback_context();
+    let step = cfg
+        .runtime
+        .step_with_context(&mut context, &options, |scope| {
+            let ctx_value =
+                super::host_userdata::action_context_userdata(scope, action_ctx.clone())?;
+            let handler = handler.func.resolve(scope)?;
+            let result: Result<(), ScriptError<'_>> =
+                scope.call_protected(handler, (ctx_value, text.clone()))?;
+            if let Err(err) = result {
+                script_error = Some(diagnostics::config_script_error(
+                    path.as_deref(),
+                    &sources,
+                    scope,
+                    &err,
+                ));
+            }
+            Ok(())
+        });
+
+    if let Some(err) = script_error {
+        let err = tag_exec_rejection(&action_ctx, err);
         action_ctx.invalidate();
         drop(action_ctx);
         cfg.synchronize_callbacks()?;
@@ -141,6 +232,7 @@ fn execute_selector_handler_inner(
         });
 
     if let Some(err) = script_error {
+        let err = tag_exec_rejection(&action_ctx, err);
         action_ctx.invalidate();
         drop(action_ctx);
         cfg.synchronize_callbacks()?;
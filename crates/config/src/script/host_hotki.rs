@@ -8,16 +8,33 @@ use ruau::{
     vm::{MultiValue, NativeModule, RuntimeError, Scope, ScopedValue, Table},
 };
 
-use super::{SelectorItem, apps, host_runtime::SharedApplicationCache, util::lock_unpoisoned};
+use super::{
+    HandlerRef, ScheduledAction, SelectorItem, apps,
+    host_args::HostArgs,
+    host_parse::{ScheduleOptionsSpec, parse_optional},
+    host_runtime::{
+        SharedApplicationCache, SharedClipboardHistorySettings, SharedScheduleSettings,
+        SharedServiceSettings, SharedSpecHarness,
+    },
+    spec,
+    util::lock_unpoisoned,
+};
+use crate::{ClipboardHistoryConfig, MissedRuns, Schedule, ServiceSpec};
 
 /// Pure-Luau implementation installed as the typed `hotki.actions` value.
 const ACTIONS_SOURCE: &[u8] = include_bytes!("../../luau/actions.luau");
 /// Pure-Luau renderer composition helpers installed as the typed `hotki.renderers` value.
 const RENDERERS_SOURCE: &[u8] = include_bytes!("../../luau/renderers.luau");
+/// Maximum characters shown in a clipboard history selector label.
+const CLIPBOARD_LABEL_CHARS: usize = 80;
 
 /// Build the declaration-coupled native module backing the `hotki` library.
 pub(super) fn build_hotki_module(
     applications: SharedApplicationCache,
+    clipboard_history: SharedClipboardHistorySettings,
+    services: SharedServiceSettings,
+    schedules: SharedScheduleSettings,
+    specs: SharedSpecHarness,
 ) -> Result<Arc<dyn NativeModule>, module::BuildError> {
     let mut builder =
         module::Builder::from_declaration("hotki", DeclarationSource::Text(crate::luau_api()));
@@ -36,10 +53,41 @@ pub(super) fn build_hotki_module(
         Binding::declared_library("hotki"),
         move |scope, args| hotki_applications(&applications, scope, args),
     );
+    builder.borrowed_function(
+        "recent_windows",
+        Binding::declared_library("hotki"),
+        |scope, args| hotki_recent_windows(scope, args),
+    );
+    builder.borrowed_function(
+        "enable_clipboard_history",
+        Binding::declared_library("hotki"),
+        move |scope, args| hotki_enable_clipboard_history(&clipboard_history, scope, args),
+    );
+    builder.borrowed_function(
+        "clipboard_entries",
+        Binding::declared_library("hotki"),
+        |scope, args| hotki_clipboard_entries(scope, args),
+    );
+    builder.borrowed_function(
+        "services",
+        Binding::declared_library("hotki"),
+        move |scope, args| hotki_services(&services, scope, args),
+    );
+    builder.borrowed_function(
+        "schedule",
+        Binding::declared_library("hotki"),
+        move |scope, args| hotki_schedule(&schedules, scope, args),
+    );
+    builder.borrowed_function(
+        "test",
+        Binding::declared_library("hotki"),
+        move |scope, args| spec::hotki_test(&specs, scope, args),
+    );
     builder.declared_host_type(Arc::new(super::host_userdata::mode_builder_type()));
     builder.declared_host_type(Arc::new(super::host_userdata::window_context_type()));
     builder.declared_host_type(Arc::new(super::host_userdata::mode_context_type()));
     builder.declared_host_type(Arc::new(super::host_userdata::action_context_type()));
+    builder.declared_host_type(Arc::new(spec::test_context_type()));
     builder.build()
 }
 
@@ -62,6 +110,180 @@ fn hotki_applications<'s>(
     Ok(MultiValue::from_values(vec![ScopedValue::Table(table)]))
 }
 
+/// Host implementation of `hotki.recent_windows`.
+fn hotki_recent_windows<'s>(
+    scope: &Scope<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let ctx = args.required("hotki.recent_windows ctx")?;
+    args.finish("hotki.recent_windows")?;
+    let windows =
+        super::host_userdata::mode_context_recent_windows(scope, ctx, "hotki.recent_windows ctx")?;
+    let table = scope.create_table()?;
+    for (idx, window) in windows.iter().enumerate() {
+        let data = scope.create_table()?;
+        data.set(scope, "id", f64::from(window.id))?;
+        data.set(scope, "pid", f64::from(window.pid))?;
+        data.set(scope, "app", window.app.clone())?;
+        data.set(scope, "title", window.title.clone())?;
+        let label = if window.title.is_empty() {
+            &window.app
+        } else {
+            &window.title
+        };
+        let row = scope.create_table()?;
+        row.set(scope, "label", label.clone())?;
+        row.set(scope, "sublabel", window.app.clone())?;
+        row.set(scope, "data", ScopedValue::Table(data))?;
+        table.set(scope, (idx + 1) as f64, row)?;
+    }
+    Ok(MultiValue::from_values(vec![ScopedValue::Table(table)]))
+}
+
+/// Host implementation of `hotki.enable_clipboard_history`.
+fn hotki_enable_clipboard_history<'s>(
+    settings: &SharedClipboardHistorySettings,
+    scope: &Scope<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let config = parse_optional::<ClipboardHistoryConfig>(scope, args.optional())?;
+    args.finish("hotki.enable_clipboard_history")?;
+    let config = config.unwrap_or_default();
+    config
+        .validate()
+        .map_err(|err| RuntimeError::runtime(format!("hotki.enable_clipboard_history {err}")))?;
+    let mut settings = lock_unpoisoned(settings);
+    if settings.sealed {
+        return Err(RuntimeError::runtime(
+            "hotki.enable_clipboard_history must be called while the config loads",
+        ));
+    }
+    if settings.config.is_some() {
+        return Err(RuntimeError::runtime(
+            "hotki.enable_clipboard_history can only be called once",
+        ));
+    }
+    settings.config = Some(config);
+    Ok(MultiValue::new())
+}
+
+/// Host implementation of `hotki.services`.
+fn hotki_services<'s>(
+    settings: &SharedServiceSettings,
+    scope: &Scope<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let spec = args.serde::<ServiceSpec>(scope, "hotki.services spec")?;
+    args.finish("hotki.services")?;
+    spec.validate()
+        .map_err(|err| RuntimeError::runtime(format!("hotki.services {err}")))?;
+    let mut settings = lock_unpoisoned(settings);
+    if settings.sealed {
+        return Err(RuntimeError::runtime(
+            "hotki.services must be called while the config loads",
+        ));
+    }
+    if settings
+        .specs
+        .iter()
+        .any(|existing| existing.name == spec.name)
+    {
+        return Err(RuntimeError::runtime(format!(
+            "hotki.services {:?} is already declared",
+            spec.name
+        )));
+    }
+    settings.specs.push(spec);
+    Ok(MultiValue::new())
+}
+
+/// Host implementation of `hotki.schedule`.
+fn hotki_schedule<'s>(
+    settings: &SharedScheduleSettings,
+    scope: &Scope<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let when = args.required("hotki.schedule when")?;
+    let (schedule, missed) = if matches!(when, ScopedValue::Table(_)) {
+        let options = parse_optional::<ScheduleOptionsSpec>(scope, when)?
+            .ok_or_else(|| RuntimeError::runtime("hotki.schedule when must not be nil"))?;
+        let schedule = match (options.cron, options.at) {
+            (Some(cron), None) => Schedule::cron(&cron),
+            (None, Some(at)) => Schedule::at(&at),
+            _ => Err("options need exactly one of cron or at".to_string()),
+        };
+        (schedule, options.missed)
+    } else {
+        let cron = parse_optional::<String>(scope, when)?
+            .ok_or_else(|| RuntimeError::runtime("hotki.schedule when must not be nil"))?;
+        (Schedule::cron(&cron), MissedRuns::default())
+    };
+    let schedule =
+        schedule.map_err(|err| RuntimeError::runtime(format!("hotki.schedule {err}")))?;
+    let action = args.function("hotki.schedule action")?;
+    args.finish("hotki.schedule")?;
+    let mut settings = lock_unpoisoned(settings);
+    if settings.sealed {
+        return Err(RuntimeError::runtime(
+            "hotki.schedule must be called while the config loads",
+        ));
+    }
+    settings.actions.push(ScheduledAction {
+        schedule,
+        missed,
+        handler: HandlerRef::from_function(scope, action)?,
+    });
+    Ok(MultiValue::new())
+}
+
+/// Host implementation of `hotki.clipboard_entries`.
+fn hotki_clipboard_entries<'s>(
+    scope: &Scope<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let ctx = args.required("hotki.clipboard_entries ctx")?;
+    args.finish("hotki.clipboard_entries")?;
+    let entries = super::host_userdata::mode_context_clipboard_history(
+        scope,
+        ctx,
+        "hotki.clipboard_entries ctx",
+    )?;
+    let table = scope.create_table()?;
+    for (idx, text) in entries.iter().enumerate() {
+        let lines = text.lines().count();
+        let row = scope.create_table()?;
+        row.set(scope, "label", clipboard_label(text))?;
+        row.set(
+            scope,
+            "sublabel",
+            (lines > 1).then(|| format!("{lines} lines")),
+        )?;
+        row.set(scope, "data", text.clone())?;
+        table.set(scope, (idx + 1) as f64, row)?;
+    }
+    Ok(MultiValue::from_values(vec![ScopedValue::Table(table)]))
+}
+
+/// Single-line selector label for one clipboard entry.
+fn clipboard_label(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= CLIPBOARD_LABEL_CHARS {
+        return line.to_string();
+    }
+    let mut label: String = line.chars().take(CLIPBOARD_LABEL_CHARS - 1).collect();
+    label.push('…');
+    label
+}
+
 /// Convert selector items into a Luau array table.
 fn selector_items_table<'s>(
     scope: &Scope<'s>,
@@ -5,7 +5,7 @@ use ruau::vm::{RuntimeError, Scope, ScopedValue, serde::from_scoped_value};
 use serde::Deserialize;
 
 use super::Binding;
-use crate::NotifyKind;
+use crate::{MissedRuns, NotifyKind, ProcessConcurrency};
 
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -25,6 +25,8 @@ pub(super) struct ShellOptionsSpec {
     pub(super) ok_notify: Option<NotifyKind>,
     /// Notification kind used for failing shell exits.
     pub(super) err_notify: Option<NotifyKind>,
+    /// Policy for presses while an earlier run is in flight.
+    pub(super) concurrency: Option<ProcessConcurrency>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -61,6 +63,94 @@ pub(super) struct SubmenuOptionsSpec {
     pub(super) capture: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Application-mode options parsed from Luau tables.
+pub(super) struct AppModeOptionsSpec {
+    /// Whether the UI shows a badge while the application mode is active.
+    pub(super) badge: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Process-targeted relay options parsed from Luau tables.
+pub(super) struct ProcessRelayOptionsSpec {
+    /// Whether separate modifier key transitions wrap the main key.
+    pub(super) modifier_events: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Relay sequence options parsed from Luau tables.
+pub(super) struct RelaySequenceOptionsSpec {
+    /// Optional delay between chords in milliseconds.
+    pub(super) delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Text typing options parsed from Luau tables.
+pub(super) struct TypeTextOptionsSpec {
+    /// Optional delay between characters in milliseconds.
+    pub(super) delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+/// Window identity parsed from a `hotki.recent_windows` item payload.
+///
+/// Unknown fields are accepted so callers can pass the whole `WindowInfo` record.
+pub(super) struct WindowInfoSpec {
+    /// Owning process identifier.
+    pub(super) pid: i32,
+    /// Window identifier.
+    pub(super) id: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Table form of the `hotki.schedule` time argument.
+pub(super) struct ScheduleOptionsSpec {
+    /// Five-field cron expression.
+    pub(super) cron: Option<String>,
+    /// Daily `HH:MM` time.
+    pub(super) at: Option<String>,
+    /// Policy for runs missed while the machine slept.
+    #[serde(default)]
+    pub(super) missed: MissedRuns,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Fake focused window passed to `TestContext:focus`.
+pub(super) struct TestWindowSpec {
+    /// Application name.
+    pub(super) app: String,
+    /// Window title.
+    #[serde(default)]
+    pub(super) title: String,
+    /// Owning process identifier.
+    #[serde(default)]
+    pub(super) pid: i32,
+    /// Window identifier.
+    #[serde(default)]
+    pub(super) id: u32,
+    /// Display containing the window.
+    pub(super) display_id: Option<u32>,
+}
+
+impl TestWindowSpec {
+    /// Convert the fake window into the focus snapshot seen by renderers and actions.
+    pub(super) fn into_snapshot(self) -> hotki_protocol::FocusSnapshot {
+        hotki_protocol::FocusSnapshot {
+            id: self.id,
+            app: self.app,
+            title: self.title,
+            pid: self.pid,
+            display_id: self.display_id,
+        }
+    }
+}
+
 /// Deserialize an optional Luau record, treating `nil` as `None`.
 pub(super) fn parse_optional<'s, T>(
     scope: &Scope<'s>,
@@ -1,11 +1,13 @@
 //! Application caching and source-name utilities for Luau host modules.
 
 use std::{
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
-use super::SelectorItem;
+use super::{ScheduledAction, SelectorItem, config::SourceMap};
+use crate::{ClipboardHistoryConfig, ServiceSpec, SpecCase, runtime::ConfigRuntime};
+
 /// Application cache shared by native host functions installed into one VM.
 pub(super) type SharedApplicationCache = Arc<Mutex<ApplicationCache>>;
 
@@ -16,6 +18,58 @@ pub(super) struct ApplicationCache {
     pub(super) items: Option<Arc<[SelectorItem]>>,
 }
 
+/// Clipboard history settings shared by native host functions installed into one VM.
+pub(super) type SharedClipboardHistorySettings = Arc<Mutex<ClipboardHistorySettings>>;
+
+/// Clipboard history settings declared while the config entry loads.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ClipboardHistorySettings {
+    /// Whether the entry has finished loading, after which settings are frozen.
+    pub(super) sealed: bool,
+    /// Settings from `hotki.enable_clipboard_history`, when it was called.
+    pub(super) config: Option<ClipboardHistoryConfig>,
+}
+
+/// Service declarations shared by native host functions installed into one VM.
+pub(super) type SharedServiceSettings = Arc<Mutex<ServiceSettings>>;
+
+/// Supervised services declared while the config entry loads.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ServiceSettings {
+    /// Whether the entry has finished loading, after which declarations are frozen.
+    pub(super) sealed: bool,
+    /// Services from `hotki.services`, in declaration order.
+    pub(super) specs: Vec<ServiceSpec>,
+}
+
+/// Scheduled actions shared by native host functions installed into one VM.
+pub(super) type SharedScheduleSettings = Arc<Mutex<ScheduleSettings>>;
+
+/// Scheduled actions declared while the config entry loads.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ScheduleSettings {
+    /// Whether the entry has finished loading, after which declarations are frozen.
+    pub(super) sealed: bool,
+    /// Actions from `hotki.schedule`, in declaration order.
+    pub(super) actions: Vec<ScheduledAction>,
+}
+
+/// Spec harness shared by native host functions installed into one VM.
+pub(super) type SharedSpecHarness = Arc<Mutex<SpecHarness>>;
+
+/// Config under test and results collected while `hotki test` runs one spec file.
+#[derive(Default)]
+pub(super) struct SpecHarness {
+    /// Loaded config shared by every case, or `None` outside `hotki test`.
+    pub(super) config: Option<Arc<Mutex<ConfigRuntime>>>,
+    /// Spec file being run, used to locate case failures.
+    pub(super) path: Option<PathBuf>,
+    /// Spec source text for failure excerpts.
+    pub(super) sources: SourceMap,
+    /// Cases from `hotki.test`, in the order they ran.
+    pub(super) cases: Vec<SpecCase>,
+}
+
 /// Render a display name for an optional source path.
 pub(super) fn chunk_name(path: Option<&Path>) -> String {
     path.map(|path| format!("@{}", path.display()))
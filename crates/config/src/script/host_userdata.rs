@@ -12,17 +12,21 @@ use ruau::vm::{
 };
 
 use super::{
-    ActionCtx, Binding, BindingFlags, BindingKind, Effect, HandlerRef, ModeCtx, ModeRef,
+    ActionCtx, AppMode, Binding, BindingFlags, BindingKind, Effect, HandlerRef, ModeCtx, ModeRef,
     NavRequest, RepeatSpec, SourcePos,
     host_args::HostArgs,
     host_parse::{
-        BindingOptionsSpec, RepeatOptionsSpec, ShellOptionsSpec, SubmenuOptionsSpec,
-        apply_binding_options, parse_chord, parse_optional,
+        AppModeOptionsSpec, BindingOptionsSpec, ProcessRelayOptionsSpec, RelaySequenceOptionsSpec,
+        RepeatOptionsSpec, ShellOptionsSpec, SubmenuOptionsSpec, TypeTextOptionsSpec,
+        WindowInfoSpec, apply_binding_options, parse_chord, parse_optional,
     },
     selector,
     util::lock_unpoisoned,
 };
-use crate::{Action, ExecSpec, NotifyKind, RelaySpec, ShellModifiers, ShellSpec, Toggle};
+use crate::{
+    Action, AppCommand, AppSpec, ExecSpec, FocusTarget, MediaKey, NotifyKind, RelaySequenceSpec,
+    RelaySpec, RelayTarget, ShellModifiers, ShellSpec, Toggle, TypeTextSpec,
+};
 
 /// Luau userdata used to build one rendered mode.
 #[derive(Clone, Debug)]
@@ -40,11 +44,34 @@ struct ModeBuildState {
     bindings: Vec<Binding>,
     /// Whether the mode requested capture-all behavior.
     capture: bool,
+    /// Application modes declared by the root render.
+    app_modes: Vec<AppMode>,
+    /// Whether this builder renders the root mode.
+    root: bool,
+}
+
+/// Output collected from one finished mode render.
+#[derive(Debug)]
+pub(crate) struct ModeBuildOutput {
+    /// Bindings declared by the mode render.
+    pub(crate) bindings: Vec<Binding>,
+    /// Whether the mode requested capture-all behavior.
+    pub(crate) capture: bool,
+    /// Application modes declared by a root render.
+    pub(crate) app_modes: Vec<AppMode>,
 }
 
+/// Window conditions consulted by a renderer, recorded only while a chord is being explained.
+pub(crate) type ConditionLog = Arc<Mutex<Vec<String>>>;
+
 /// Luau userdata wrapper for mode render contexts.
 #[derive(Clone, Debug)]
-struct ModeContextUserData(ModeCtx);
+struct ModeContextUserData {
+    /// Render context snapshot.
+    ctx: ModeCtx,
+    /// Where window conditions are recorded, if anywhere.
+    conditions: Option<ConditionLog>,
+}
 
 /// Luau userdata wrapper for action handler contexts.
 #[derive(Clone, Debug)]
@@ -52,7 +79,27 @@ struct ActionContextUserData(ActionCtx);
 
 /// Luau userdata wrapper for an immutable focused-window snapshot.
 #[derive(Clone, Debug)]
-struct WindowContextUserData(hotki_protocol::FocusSnapshot);
+struct WindowContextUserData {
+    /// Focused-window snapshot.
+    window: hotki_protocol::FocusSnapshot,
+    /// Where conditions on the window are recorded, if anywhere.
+    conditions: Option<ConditionLog>,
+}
+
+impl WindowContextUserData {
+    /// Wrap a window snapshot whose reads are not recorded.
+    fn unrecorded(window: hotki_protocol::FocusSnapshot) -> Self {
+        Self {
+            window,
+            conditions: None,
+        }
+    }
+
+    /// Record a condition a renderer checked against this window.
+    fn record(&self, condition: impl FnOnce() -> String) {
+        record_condition(self.conditions.as_ref(), condition);
+    }
+}
 
 impl<'s> IntoLua<'s> for WindowContextUserData {
     fn into_lua(self, scope: &Scope<'s>) -> Result<ScopedValue<'s>, RuntimeError> {
@@ -62,9 +109,12 @@ impl<'s> IntoLua<'s> for WindowContextUserData {
 
 impl ModeBuilder {
     /// Create a mode builder seeded with inherited capture state.
-    pub(crate) fn new_for_render(capture: bool) -> Self {
+    ///
+    /// Only root builders accept `menu:app_mode` declarations.
+    pub(crate) fn new_for_render(capture: bool, root: bool) -> Self {
         let state = ModeBuildState {
             capture,
+            root,
             ..ModeBuildState::default()
         };
         Self {
@@ -73,12 +123,14 @@ impl ModeBuilder {
         }
     }
 
-    /// Finish the builder and return bindings plus capture flag.
-    pub(crate) fn finish(self) -> (Vec<Binding>, bool) {
+    /// Finish the builder and return its bindings, capture flag, and application modes.
+    pub(crate) fn finish(self) -> ModeBuildOutput {
         let mut guard = lock_unpoisoned(&self.state);
-        let bindings = mem::take(&mut guard.bindings);
-        let capture = guard.capture;
-        (bindings, capture)
+        ModeBuildOutput {
+            bindings: mem::take(&mut guard.bindings),
+            capture: guard.capture,
+            app_modes: mem::take(&mut guard.app_modes),
+        }
     }
 }
 
@@ -90,12 +142,13 @@ pub fn mode_builder_userdata<'s>(
     scope.create_userdata(builder)
 }
 
-/// Wrap a render context snapshot as Luau userdata.
+/// Wrap a render context snapshot as Luau userdata, recording window conditions in `conditions`.
 pub fn mode_context_userdata<'s>(
     scope: &Scope<'s>,
     ctx: ModeCtx,
+    conditions: Option<ConditionLog>,
 ) -> Result<Userdata<'s>, RuntimeError> {
-    scope.create_userdata(ModeContextUserData(ctx))
+    scope.create_userdata(ModeContextUserData { ctx, conditions })
 }
 
 /// Wrap an action context snapshot as Luau userdata.
@@ -106,6 +159,54 @@ pub fn action_context_userdata<'s>(
     scope.create_userdata(ActionContextUserData(ctx))
 }
 
+/// Read the focus history carried by a `ModeContext` argument, excluding its own window.
+pub(super) fn mode_context_recent_windows<'s>(
+    scope: &Scope<'s>,
+    value: ScopedValue<'s>,
+    context: &str,
+) -> Result<Vec<hotki_protocol::FocusSnapshot>, RuntimeError> {
+    let userdata = match value {
+        ScopedValue::Userdata(userdata) => userdata,
+        other => {
+            return Err(RuntimeError::runtime(format!(
+                "{context} must be a ModeContext, got {}",
+                other.type_name()
+            )));
+        }
+    };
+    let data = userdata.borrow::<ModeContextUserData>(scope)?;
+    let current = data.ctx.window.as_ref();
+    Ok(data
+        .ctx
+        .recent_windows
+        .iter()
+        .filter(|window| current.is_none_or(|cur| cur.pid != window.pid || cur.id != window.id))
+        .cloned()
+        .collect())
+}
+
+/// Read the clipboard history carried by a `ModeContext` argument.
+pub(super) fn mode_context_clipboard_history<'s>(
+    scope: &Scope<'s>,
+    value: ScopedValue<'s>,
+    context: &str,
+) -> Result<Vec<String>, RuntimeError> {
+    let userdata = match value {
+        ScopedValue::Userdata(userdata) => userdata,
+        other => {
+            return Err(RuntimeError::runtime(format!(
+                "{context} must be a ModeContext, got {}",
+                other.type_name()
+            )));
+        }
+    };
+    Ok(userdata
+        .borrow::<ModeContextUserData>(scope)?
+        .ctx
+        .clipboard_history
+        .clone())
+}
+
 /// Build the host userdata type definition for mode builders.
 pub(super) fn mode_builder_type() -> HostType {
     HostTypeBuilder::<ModeBuilder>::new("ModeBuilder")
@@ -113,6 +214,7 @@ pub(super) fn mode_builder_type() -> HostType {
         .method_raw("submenu", mode_builder_submenu)
         .method_raw("with", mode_builder_with)
         .method_raw("capture", mode_builder_capture)
+        .method_raw("app_mode", mode_builder_app_mode)
         .declaration(
             "declare class ModeBuilder\n\
     with: (self: ModeBuilder, defaults: BindingOptions) -> ModeBuilder\n\
@@ -125,10 +227,17 @@ end\n",
 pub(super) fn mode_context_type() -> HostType {
     HostTypeBuilder::<ModeContextUserData>::new("ModeContext")
         .getter("window", |_, this| {
-            Ok(this.0.window.clone().map(WindowContextUserData))
+            let Some(window) = this.ctx.window.clone() else {
+                record_condition(this.conditions.as_ref(), || "no focused window".to_string());
+                return Ok(None);
+            };
+            Ok(Some(WindowContextUserData {
+                window,
+                conditions: this.conditions.clone(),
+            }))
         })
-        .getter("hud", |_, this| Ok(this.0.hud))
-        .getter("depth", |_, this| Ok(this.0.depth))
+        .getter("hud", |_, this| Ok(this.ctx.hud))
+        .getter("depth", |_, this| Ok(this.ctx.depth))
         .declaration("declare class ModeContext\nend\n")
         .build()
 }
@@ -136,16 +245,32 @@ pub(super) fn mode_context_type() -> HostType {
 /// Build the host userdata type definition for immutable focused-window snapshots.
 pub(super) fn window_context_type() -> HostType {
     HostTypeBuilder::<WindowContextUserData>::new("WindowContext")
-        .getter("id", |_, this| Ok(f64::from(this.0.id)))
-        .getter("pid", |_, this| Ok(f64::from(this.0.pid)))
-        .getter("app", |_, this| Ok(this.0.app.clone()))
-        .getter("title", |_, this| Ok(this.0.title.clone()))
-        .getter("display_id", |_, this| Ok(this.0.display_id.map(f64::from)))
+        .getter("id", |_, this| Ok(f64::from(this.window.id)))
+        .getter("pid", |_, this| Ok(f64::from(this.window.pid)))
+        .getter("app", |_, this| {
+            this.record(|| format!("window.app is {:?}", this.window.app));
+            Ok(this.window.app.clone())
+        })
+        .getter("title", |_, this| {
+            this.record(|| format!("window.title is {:?}", this.window.title));
+            Ok(this.window.title.clone())
+        })
+        .getter("display_id", |_, this| {
+            Ok(this.window.display_id.map(f64::from))
+        })
         .method("app_matches", |_, this, pattern: String| {
-            regex_matches(&this.0.app, &pattern)
+            let matched = regex_matches(&this.window.app, &pattern)?;
+            if !matched {
+                this.record(|| format!("window:app_matches({pattern:?}) is false"));
+            }
+            Ok(matched)
         })
         .method("title_matches", |_, this, pattern: String| {
-            regex_matches(&this.0.title, &pattern)
+            let matched = regex_matches(&this.window.title, &pattern)?;
+            if !matched {
+                this.record(|| format!("window:title_matches({pattern:?}) is false"));
+            }
+            Ok(matched)
         })
         .declaration("declare class WindowContext\nend\n")
         .build()
@@ -155,7 +280,12 @@ pub(super) fn window_context_type() -> HostType {
 pub(super) fn action_context_type() -> HostType {
     HostTypeBuilder::<ActionContextUserData>::new("ActionContext")
         .getter("window", |_, this| {
-            Ok(this.0.snapshot.window.clone().map(WindowContextUserData))
+            Ok(this
+                .0
+                .snapshot
+                .window
+                .clone()
+                .map(WindowContextUserData::unrecorded))
         })
         .getter("hud", |_, this| Ok(this.0.hud()))
         .getter("depth", |_, this| Ok(this.0.depth()))
@@ -181,10 +311,34 @@ pub(super) fn action_context_type() -> HostType {
         .method_raw("open", action_context_open)
         .method_raw("relay", action_context_relay)
         .method_raw("relay_to_app", action_context_relay_to_app)
+        .method_raw("relay_to_bundle", action_context_relay_to_bundle)
+        .method_raw("relay_to_window", action_context_relay_to_window)
+        .method_raw("relay_sequence", action_context_relay_sequence)
+        .method_raw("type_text", action_context_type_text)
+        .method_raw("type_text_to_app", action_context_type_text_to_app)
         .method_raw("show_main_window", action_context_show_main_window)
         .method_raw("set_volume", action_context_set_volume)
         .method_raw("change_volume", action_context_change_volume)
         .method_raw("mute", action_context_mute)
+        .method_raw("media", action_context_media)
+        .method_raw("activate_app", action_context_activate_app)
+        .method_raw("hide_app", action_context_hide_app)
+        .method_raw("quit_app", action_context_quit_app)
+        .method_raw("toggle_app", action_context_toggle_app)
+        .method_raw("set_clipboard", action_context_set_clipboard)
+        .method_raw("with_clipboard", action_context_with_clipboard)
+        .method("focus_previous_app", |_, this, (): ()| {
+            this.0
+                .push_effect(Effect::Exec(Action::Focus(FocusTarget::PreviousApp)))
+        })
+        .method("focus_previous_window", |_, this, (): ()| {
+            this.0
+                .push_effect(Effect::Exec(Action::Focus(FocusTarget::PreviousWindow)))
+        })
+        .method("cancel_processes", |_, this, (): ()| {
+            this.0.push_effect(Effect::Exec(Action::CancelProcesses))
+        })
+        .method_raw("focus_window", action_context_focus_window)
         .method_raw("until_keyup", action_context_until_keyup)
         .method_raw("select", action_context_select)
         .declaration("declare class ActionContext\nend\n")
@@ -254,6 +408,18 @@ fn regex_matches(text: &str, pattern: &str) -> Result<bool, RuntimeError> {
         .map_err(|err| RuntimeError::runtime(err.to_string()))
 }
 
+/// Append a condition to `log`, once, when conditions are being recorded.
+fn record_condition(log: Option<&ConditionLog>, condition: impl FnOnce() -> String) {
+    let Some(log) = log else {
+        return;
+    };
+    let condition = condition();
+    let mut log = lock_unpoisoned(log);
+    if !log.contains(&condition) {
+        log.push(condition);
+    }
+}
+
 /// Implement `menu:bind`.
 fn mode_builder_bind<'s>(
     scope: &Scope<'s>,
@@ -342,6 +508,38 @@ fn mode_builder_capture<'s>(
     Ok(MultiValue::new())
 }
 
+/// Implement `menu:app_mode`.
+fn mode_builder_app_mode<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let app = args.string(scope, "menu:app_mode app")?;
+    let title = args.string(scope, "menu:app_mode title")?;
+    let render = args.function("menu:app_mode render")?;
+    let opts = args.optional();
+    args.finish("menu:app_mode")?;
+    if app.is_empty() {
+        return Err(RuntimeError::runtime("menu:app_mode app must not be empty"));
+    }
+    let state = Arc::clone(&receiver.borrow::<ModeBuilder>(scope)?.state);
+    if !lock_unpoisoned(&state).root {
+        return Err(RuntimeError::runtime(
+            "menu:app_mode is only available in the root menu",
+        ));
+    }
+    let options = parse_optional::<AppModeOptionsSpec>(scope, opts)?;
+    let mode = ModeRef::from_function(scope, render, Some(title.clone()))?;
+    lock_unpoisoned(&state).app_modes.push(AppMode {
+        app,
+        title,
+        mode,
+        badge: options.and_then(|opts| opts.badge).unwrap_or(false),
+    });
+    Ok(MultiValue::new())
+}
+
 /// Implement `ctx:notify`.
 fn action_context_notify<'s>(
     scope: &Scope<'s>,
@@ -387,6 +585,14 @@ fn action_context_exec<'s>(
     let mut args = HostArgs::new(args);
     let spec = args.serde::<ExecSpec>(scope, "ctx:exec spec")?;
     args.finish("ctx:exec")?;
+    if let Err(err) = spec.validate() {
+        let reason = format!("ctx:exec {err}");
+        receiver
+            .borrow::<ActionContextUserData>(scope)?
+            .0
+            .reject_exec(reason.clone());
+        return Err(RuntimeError::runtime(reason));
+    }
     push_exec(scope, receiver, Action::Exec(spec))
 }
 
@@ -444,6 +650,7 @@ fn action_context_relay_to_app<'s>(
     let mut args = HostArgs::new(args);
     let app_name = args.string(scope, "ctx:relay_to_app app_name")?;
     let spec = args.string(scope, "ctx:relay_to_app spec")?;
+    let opts = parse_optional::<ProcessRelayOptionsSpec>(scope, args.optional())?;
     args.finish("ctx:relay_to_app")?;
     if app_name.is_empty() {
         return Err(RuntimeError::runtime(
@@ -453,7 +660,162 @@ fn action_context_relay_to_app<'s>(
     push_exec(
         scope,
         receiver,
-        Action::Relay(RelaySpec::application(app_name, spec)),
+        Action::Relay(process_relay(RelaySpec::application(app_name, spec), opts)),
+    )
+}
+
+/// Implement `ctx:relay_to_bundle`.
+fn action_context_relay_to_bundle<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let bundle_id = args.string(scope, "ctx:relay_to_bundle bundle_id")?;
+    let spec = args.string(scope, "ctx:relay_to_bundle spec")?;
+    let opts = parse_optional::<ProcessRelayOptionsSpec>(scope, args.optional())?;
+    args.finish("ctx:relay_to_bundle")?;
+    if bundle_id.is_empty() {
+        return Err(RuntimeError::runtime(
+            "ctx:relay_to_bundle bundle_id must not be empty",
+        ));
+    }
+    push_exec(
+        scope,
+        receiver,
+        Action::Relay(process_relay(RelaySpec::bundle(bundle_id, spec), opts)),
+    )
+}
+
+/// Implement `ctx:relay_to_window` using the pinned context window's process.
+fn action_context_relay_to_window<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let spec = args.string(scope, "ctx:relay_to_window spec")?;
+    let opts = parse_optional::<ProcessRelayOptionsSpec>(scope, args.optional())?;
+    args.finish("ctx:relay_to_window")?;
+    let pid = receiver
+        .borrow::<ActionContextUserData>(scope)?
+        .0
+        .snapshot
+        .window
+        .as_ref()
+        .map(|window| window.pid)
+        .ok_or_else(|| RuntimeError::runtime("ctx:relay_to_window requires ctx.window"))?;
+    push_exec(
+        scope,
+        receiver,
+        Action::Relay(process_relay(RelaySpec::pid(pid, spec), opts)),
+    )
+}
+
+/// Apply parsed process-relay options to a relay spec.
+fn process_relay(spec: RelaySpec, opts: Option<ProcessRelayOptionsSpec>) -> RelaySpec {
+    let modifier_events = opts.and_then(|opts| opts.modifier_events).unwrap_or(false);
+    spec.with_modifier_events(modifier_events)
+}
+
+/// Implement `ctx:relay_sequence`.
+fn action_context_relay_sequence<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let chords = args.serde::<Vec<String>>(scope, "ctx:relay_sequence chords")?;
+    let opts = parse_optional::<RelaySequenceOptionsSpec>(scope, args.optional())?;
+    args.finish("ctx:relay_sequence")?;
+    if chords.is_empty() {
+        return Err(RuntimeError::runtime(
+            "ctx:relay_sequence chords must not be empty",
+        ));
+    }
+    push_exec(
+        scope,
+        receiver,
+        Action::RelaySequence(RelaySequenceSpec {
+            chords,
+            delay_ms: opts.and_then(|opts| opts.delay_ms),
+        }),
+    )
+}
+
+/// Implement `ctx:type_text`.
+fn action_context_type_text<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let text = args.string(scope, "ctx:type_text text")?;
+    let opts = parse_optional::<TypeTextOptionsSpec>(scope, args.optional())?;
+    args.finish("ctx:type_text")?;
+    push_type_text(scope, receiver, text, opts, RelayTarget::Focused)
+}
+
+/// Implement `ctx:type_text_to_app`.
+fn action_context_type_text_to_app<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let app_name = args.string(scope, "ctx:type_text_to_app app_name")?;
+    let text = args.string(scope, "ctx:type_text_to_app text")?;
+    let opts = parse_optional::<TypeTextOptionsSpec>(scope, args.optional())?;
+    args.finish("ctx:type_text_to_app")?;
+    if app_name.is_empty() {
+        return Err(RuntimeError::runtime(
+            "ctx:type_text_to_app app_name must not be empty",
+        ));
+    }
+    push_type_text(
+        scope,
+        receiver,
+        text,
+        opts,
+        RelayTarget::ApplicationName(app_name),
+    )
+}
+
+/// Queue one text-typing action with parsed options.
+fn push_type_text<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    text: String,
+    opts: Option<TypeTextOptionsSpec>,
+    target: RelayTarget,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    push_exec(
+        scope,
+        receiver,
+        Action::TypeText(TypeTextSpec {
+            text,
+            delay_ms: opts.and_then(|opts| opts.delay_ms),
+            target,
+        }),
+    )
+}
+
+/// Implement `ctx:focus_window`.
+fn action_context_focus_window<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let window = args.serde::<WindowInfoSpec>(scope, "ctx:focus_window window")?;
+    args.finish("ctx:focus_window")?;
+    push_exec(
+        scope,
+        receiver,
+        Action::Focus(FocusTarget::Window {
+            pid: window.pid,
+            id: window.id,
+        }),
     )
 }
 
@@ -505,6 +867,109 @@ fn action_context_mute<'s>(
     push_exec(scope, receiver, Action::Mute(toggle))
 }
 
+/// Implement `ctx:media`.
+fn action_context_media<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let key = args.serde::<MediaKey>(scope, "ctx:media key")?;
+    args.finish("ctx:media")?;
+    push_exec(scope, receiver, Action::Media(key))
+}
+
+/// Implement `ctx:set_clipboard`.
+fn action_context_set_clipboard<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let text = args.string(scope, "ctx:set_clipboard text")?;
+    args.finish("ctx:set_clipboard")?;
+    push_exec(scope, receiver, Action::SetClipboard(text))
+}
+
+/// Implement `ctx:with_clipboard`.
+fn action_context_with_clipboard<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let callback = args.function("ctx:with_clipboard callback")?;
+    args.finish("ctx:with_clipboard")?;
+    receiver
+        .borrow::<ActionContextUserData>(scope)?
+        .0
+        .push_effect(Effect::WithClipboard(HandlerRef::from_function(
+            scope, callback,
+        )?))?;
+    Ok(MultiValue::new())
+}
+
+/// Implement `ctx:activate_app`.
+fn action_context_activate_app<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    action_context_app(
+        scope,
+        receiver,
+        args,
+        AppCommand::Activate,
+        "ctx:activate_app",
+    )
+}
+
+/// Implement `ctx:hide_app`.
+fn action_context_hide_app<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    action_context_app(scope, receiver, args, AppCommand::Hide, "ctx:hide_app")
+}
+
+/// Implement `ctx:quit_app`.
+fn action_context_quit_app<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    action_context_app(scope, receiver, args, AppCommand::Quit, "ctx:quit_app")
+}
+
+/// Implement `ctx:toggle_app`.
+fn action_context_toggle_app<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    action_context_app(scope, receiver, args, AppCommand::Toggle, "ctx:toggle_app")
+}
+
+/// Queue one application lifecycle action for a non-empty target.
+fn action_context_app<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+    command: AppCommand,
+    name: &str,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let target = args.string(scope, &format!("{name} target"))?;
+    args.finish(name)?;
+    if target.is_empty() {
+        return Err(RuntimeError::runtime(format!(
+            "{name} target must not be empty"
+        )));
+    }
+    push_exec(scope, receiver, Action::App(AppSpec { target, command }))
+}
+
 /// Implement `ctx:until_keyup`.
 fn action_context_until_keyup<'s>(
     scope: &Scope<'s>,
@@ -566,6 +1031,7 @@ fn shell_spec(cmd: String, opts: Option<ShellOptionsSpec>) -> ShellSpec {
                 ShellModifiers {
                     ok_notify: opts.ok_notify.unwrap_or(defaults.ok_notify),
                     err_notify: opts.err_notify.unwrap_or(defaults.err_notify),
+                    concurrency: opts.concurrency.unwrap_or(defaults.concurrency),
                 },
             )
         }
@@ -0,0 +1,557 @@
+//! Opinionated best-practice lints run by `hotki lint`.
+//!
+//! The pass walks the same contexts as binding analysis plus one probe window whose application
+//! name no real application has, so a `relay_to_app` target equal to it can only have been copied
+//! from `ctx.window.app`. Every handler binding runs against each context to collect the actions
+//! it queues; the actions are discarded, never executed, but the handler's own Luau code runs
+//! and may change the config's in-memory state. Findings about programs are purely syntactic and
+//! never consult the filesystem. A finding is suppressed by a `-- hotki-lint: allow(<id>, ...)`
+//! comment on the binding's line or the line above it.
+
+use std::{cmp::Ordering, fmt, path::Path};
+
+use serde::Serialize;
+
+use super::{
+    Binding, BindingKind, Effect, LoadedConfig, ModeFrame, SourcePos,
+    handler::execute_handler,
+    loader::load_dynamic_config,
+    render::location_string,
+    walk::{ModeVisit, ModeVisitor, app_mode_apps, synthetic_window, walk_modes},
+};
+use crate::{Action, Diagnostic, Error, ExecSpec, RelaySpec, RelayTarget, Severity};
+
+/// Deepest submenu level that does not trigger `deep_nesting`.
+const MAX_NESTING: usize = 4;
+
+/// Application name of the probe window used to detect names copied from `ctx.window.app`.
+const PROBE_APP: &str = "HotkiLintProbe";
+
+/// Characters that give a shell command meaning beyond splitting on whitespace.
+const SHELL_SYNTAX: &[char] = &[
+    '|', '&', ';', '<', '>', '(', ')', '$', '`', '\\', '"', '\'', '*', '?', '[', ']', '{', '}',
+    '~', '#', '=', '!', '\n',
+];
+
+/// Shell builtins and keywords that have no program to execute directly.
+const SHELL_BUILTINS: &[&str] = &[
+    ".", "alias", "case", "cd", "eval", "exec", "exit", "export", "for", "if", "read", "set",
+    "source", "test", "trap", "ulimit", "umask", "unset", "wait", "while",
+];
+
+/// Programs macOS ships in `/usr/bin`, `/bin`, `/usr/sbin`, and `/sbin`, the `PATH` an app started
+/// from Finder or a login item inherits. Not exhaustive; it covers what configs commonly run.
+const DEFAULT_PATH_PROGRAMS: &[&str] = &[
+    "afplay",
+    "awk",
+    "base64",
+    "basename",
+    "bash",
+    "bc",
+    "caffeinate",
+    "cat",
+    "chmod",
+    "chown",
+    "cp",
+    "curl",
+    "cut",
+    "date",
+    "dd",
+    "defaults",
+    "df",
+    "diff",
+    "dirname",
+    "diskutil",
+    "ditto",
+    "du",
+    "echo",
+    "env",
+    "expr",
+    "false",
+    "file",
+    "find",
+    "git",
+    "grep",
+    "hdiutil",
+    "head",
+    "hostname",
+    "id",
+    "ifconfig",
+    "ioreg",
+    "kill",
+    "killall",
+    "launchctl",
+    "less",
+    "ln",
+    "ls",
+    "lsof",
+    "md5",
+    "mdfind",
+    "mdls",
+    "mkdir",
+    "mktemp",
+    "mv",
+    "nc",
+    "networksetup",
+    "nohup",
+    "open",
+    "osascript",
+    "pbcopy",
+    "pbpaste",
+    "perl",
+    "pgrep",
+    "ping",
+    "pkill",
+    "plutil",
+    "pmset",
+    "printf",
+    "ps",
+    "pwd",
+    "python3",
+    "rm",
+    "rmdir",
+    "rsync",
+    "ruby",
+    "say",
+    "screencapture",
+    "scutil",
+    "sed",
+    "sh",
+    "shasum",
+    "shortcuts",
+    "shutdown",
+    "sips",
+    "sleep",
+    "sort",
+    "sqlite3",
+    "ssh",
+    "stat",
+    "sw_vers",
+    "sysctl",
+    "tail",
+    "tar",
+    "tee",
+    "textutil",
+    "tmutil",
+    "top",
+    "touch",
+    "tr",
+    "true",
+    "uname",
+    "uniq",
+    "unzip",
+    "uptime",
+    "wc",
+    "which",
+    "whoami",
+    "xargs",
+    "xattr",
+    "yes",
+    "zip",
+    "zsh",
+];
+
+/// Prefix of a suppression comment; the lint ids follow, comma-separated, before `)`.
+const ALLOW_MARKER: &str = "hotki-lint: allow(";
+
+/// Category of a lint finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// `a.shell` runs a command that uses no shell syntax.
+    ShellCouldBeExec,
+    /// `a.exec` names a program without a `/` that is not in the default `PATH`, and sets no
+    /// `PATH` of its own.
+    ExecRelativeProgram,
+    /// `relay_to_app` targets the focused window's `ctx.window.app`.
+    RelayToWindowApp,
+    /// A submenu loses its chord to an earlier binding in every context.
+    UnreachableSubmenu,
+    /// A submenu declares no bindings in any context it is entered in.
+    EmptyMenu,
+    /// Two visible bindings in one mode share a description.
+    DuplicateDescription,
+    /// A submenu is nested deeper than [`MAX_NESTING`] levels.
+    DeepNesting,
+}
+
+impl LintKind {
+    /// Stable snake_case id used in structured diagnostics and allow comments.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ShellCouldBeExec => "shell_could_be_exec",
+            Self::ExecRelativeProgram => "exec_relative_program",
+            Self::RelayToWindowApp => "relay_to_window_app",
+            Self::UnreachableSubmenu => "unreachable_submenu",
+            Self::EmptyMenu => "empty_menu",
+            Self::DuplicateDescription => "duplicate_description",
+            Self::DeepNesting => "deep_nesting",
+        }
+    }
+
+    /// Errors mark bindings that cannot work as written; warnings mark questionable style.
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::UnreachableSubmenu => Severity::Error,
+            Self::ShellCouldBeExec
+            | Self::ExecRelativeProgram
+            | Self::RelayToWindowApp
+            | Self::EmptyMenu
+            | Self::DuplicateDescription
+            | Self::DeepNesting => Severity::Warning,
+        }
+    }
+}
+
+/// One finding from the lint pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintFinding {
+    /// What was found.
+    pub kind: LintKind,
+    /// Mode titles from the root to the mode declaring the binding.
+    pub mode: Vec<String>,
+    /// Declaration site of the binding the finding applies to.
+    pub location: Option<SourcePos>,
+    /// Human-readable summary.
+    pub message: String,
+}
+
+impl LintFinding {
+    /// Convert the finding into a structured diagnostic.
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let pos = self.location.as_ref();
+        Diagnostic {
+            path: pos.and_then(|pos| pos.path.clone()),
+            line: pos.and_then(|pos| pos.line),
+            col: pos.and_then(|pos| pos.col),
+            severity: self.kind.severity(),
+            code: self.kind.code().to_string(),
+            message: format!("{}: {}", self.mode.join(" › "), self.message),
+        }
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} [{}]",
+            self.mode.join(" › "),
+            self.message,
+            self.kind.code()
+        )?;
+        if let Some(pos) = &self.location {
+            write!(f, "\n  at {}", location_string(pos))?;
+        }
+        Ok(())
+    }
+}
+
+/// Load the config at `config` and lint every reachable mode, ordered by declaration site.
+///
+/// Findings suppressed by an allow comment are left out.
+pub fn lint_config(config: &Path) -> Result<Vec<LintFinding>, Error> {
+    let mut cfg = load_dynamic_config(config)?;
+    let mut findings = lint_loaded(&mut cfg);
+    findings.retain(|finding| !suppressed(&cfg, finding));
+    findings.sort_by(|a, b| site_order(a.location.as_ref(), b.location.as_ref()));
+    Ok(findings)
+}
+
+/// Walk every context of `cfg` and collect findings, suppressed ones included.
+fn lint_loaded(cfg: &mut LoadedConfig) -> Vec<LintFinding> {
+    let mut apps = vec![None, Some(PROBE_APP.to_string())];
+    apps.extend(app_mode_apps(cfg).into_iter().map(Some));
+
+    let mut lint = Lint {
+        findings: Vec::new(),
+        submenus: Vec::new(),
+    };
+    for app in apps {
+        if let Err(err) = walk_modes(cfg, app.map(synthetic_window), &mut lint) {
+            tracing::debug!(error = %err, "lint walk stopped");
+        }
+    }
+    let Lint {
+        mut findings,
+        submenus,
+    } = lint;
+    findings.extend(submenus.into_iter().filter_map(SubmenuSite::finding));
+    findings
+}
+
+/// How one submenu declaration fared across every context it was rendered in.
+struct SubmenuSite {
+    /// Mode titles from the root to the mode declaring the submenu.
+    mode: Vec<String>,
+    /// Declaration site of the submenu binding.
+    location: Option<SourcePos>,
+    /// Chord in canonical form.
+    chord: String,
+    /// Submenu title.
+    title: String,
+    /// Description of the binding that took the chord when the submenu lost it.
+    taken_by: Option<String>,
+    /// True once the submenu won its chord in some context.
+    reachable: bool,
+    /// True once the submenu was entered and rendered in some context.
+    entered: bool,
+    /// True while every render of the submenu declared nothing.
+    empty: bool,
+}
+
+impl SubmenuSite {
+    /// The finding this declaration earned once every context has been walked.
+    fn finding(self) -> Option<LintFinding> {
+        let (kind, message) = if !self.reachable {
+            let taken_by = self.taken_by?;
+            let message = format!(
+                "submenu \"{}\" can never be opened: \"{taken_by}\" takes '{}' first",
+                self.title, self.chord
+            );
+            (LintKind::UnreachableSubmenu, message)
+        } else if self.entered && self.empty {
+            let message = format!("submenu \"{}\" declares no bindings", self.title);
+            (LintKind::EmptyMenu, message)
+        } else {
+            return None;
+        };
+        Some(LintFinding {
+            kind,
+            mode: self.mode,
+            location: self.location,
+            message,
+        })
+    }
+}
+
+/// Lint state shared across contexts.
+struct Lint {
+    /// Findings about single bindings so far, without repeats across contexts.
+    findings: Vec<LintFinding>,
+    /// Every submenu declaration seen so far.
+    submenus: Vec<SubmenuSite>,
+}
+
+impl ModeVisitor for Lint {
+    fn enter(&mut self, cfg: &mut LoadedConfig, mode: &ModeVisit<'_>) {
+        if let (Some(entry), Some((_, parent))) = (mode.entry, mode.trail.split_last()) {
+            let site = self.submenu(parent, entry);
+            site.entered = true;
+            site.empty &= mode.declared.is_empty();
+        }
+        for (dropped, first) in mode.dropped {
+            if matches!(dropped.kind, BindingKind::Mode(_)) {
+                self.submenu(mode.trail, dropped).taken_by = Some(first.desc.clone());
+            }
+        }
+
+        self.descriptions(mode);
+        for binding in mode.own {
+            self.handler(cfg, mode, binding);
+        }
+        for binding in mode.own {
+            self.nesting(mode, binding);
+        }
+    }
+
+    fn failed(&mut self, frame: &ModeFrame, err: Error) -> Result<(), Error> {
+        tracing::debug!(mode = %frame.title, error = %err, "skipping lint");
+        Ok(())
+    }
+}
+
+impl Lint {
+    /// Report visible bindings whose description repeats an earlier one in the same mode.
+    fn descriptions(&mut self, mode: &ModeVisit<'_>) {
+        let visible: Vec<&Binding> = mode
+            .own
+            .iter()
+            .copied()
+            .filter(|binding| !binding.flags.hidden && !binding.desc.is_empty())
+            .collect();
+        for (index, binding) in visible.iter().enumerate() {
+            if let Some(first) = visible[..index]
+                .iter()
+                .find(|first| first.desc == binding.desc)
+            {
+                let message = format!(
+                    "'{}' and '{}' are both described as \"{}\"",
+                    first.chord, binding.chord, binding.desc
+                );
+                self.push(mode, LintKind::DuplicateDescription, binding, message);
+            }
+        }
+    }
+
+    /// Run a handler binding and lint the actions it queues.
+    fn handler(&mut self, cfg: &mut LoadedConfig, mode: &ModeVisit<'_>, binding: &Binding) {
+        let BindingKind::Handler(handler) = &binding.kind else {
+            return;
+        };
+        let effects = match execute_handler(cfg, handler, mode.ctx) {
+            Ok(result) => result.effects,
+            Err(err) => {
+                tracing::debug!(binding = %binding.desc, error = %err, "skipping handler lint");
+                return;
+            }
+        };
+        for effect in effects {
+            let Effect::Exec(action) = effect else {
+                continue;
+            };
+            if let Some((kind, message)) = action_finding(&action) {
+                self.push(mode, kind, binding, message);
+            }
+        }
+    }
+
+    /// Mark a submenu binding that won its chord as reachable and check its nesting.
+    fn nesting(&mut self, mode: &ModeVisit<'_>, binding: &Binding) {
+        if !matches!(binding.kind, BindingKind::Mode(_)) {
+            return;
+        }
+        self.submenu(mode.trail, binding).reachable = true;
+        let depth = mode.depth();
+        if depth == MAX_NESTING {
+            let message = format!(
+                "submenu \"{}\" is nested {} levels deep; consider a flatter layout",
+                binding.desc,
+                depth + 1
+            );
+            self.push(mode, LintKind::DeepNesting, binding, message);
+        }
+    }
+
+    /// The tracked site for a submenu binding declared in the mode at `trail`.
+    fn submenu(&mut self, trail: &[String], binding: &Binding) -> &mut SubmenuSite {
+        let chord = binding.chord.to_string();
+        let index = self.submenus.iter().position(|site| {
+            site.mode == trail && site.location == binding.pos && site.chord == chord
+        });
+        let index = index.unwrap_or_else(|| {
+            self.submenus.push(SubmenuSite {
+                mode: trail.to_vec(),
+                location: binding.pos.clone(),
+                chord,
+                title: binding.desc.clone(),
+                taken_by: None,
+                reachable: false,
+                entered: false,
+                empty: true,
+            });
+            self.submenus.len() - 1
+        });
+        &mut self.submenus[index]
+    }
+
+    /// Record a finding unless the same one was already found for another context.
+    fn push(&mut self, mode: &ModeVisit<'_>, kind: LintKind, binding: &Binding, message: String) {
+        let finding = LintFinding {
+            kind,
+            mode: mode.trail.to_vec(),
+            location: binding.pos.clone(),
+            message,
+        };
+        if !self.findings.contains(&finding) {
+            self.findings.push(finding);
+        }
+    }
+}
+
+/// The lint, if any, earned by one queued action.
+fn action_finding(action: &Action) -> Option<(LintKind, String)> {
+    match action {
+        Action::Shell(spec) => shell_finding(spec.command()),
+        Action::Exec(spec) => exec_finding(spec),
+        Action::Relay(RelaySpec {
+            target: RelayTarget::ApplicationName(name),
+            ..
+        }) if name == PROBE_APP => Some((
+            LintKind::RelayToWindowApp,
+            "relay_to_app is given ctx.window.app, which can differ from the application's \
+             localized name; name the application literally or use relay_to_bundle"
+                .to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// Flag a shell command that is a plain program and arguments.
+fn shell_finding(command: &str) -> Option<(LintKind, String)> {
+    if command.contains(SHELL_SYNTAX) {
+        return None;
+    }
+    let program = command.split_whitespace().next()?;
+    if SHELL_BUILTINS.contains(&program) {
+        return None;
+    }
+    let message = format!(
+        "shell command \"{}\" uses no shell syntax; a.exec({{ program = \"{program}\", args = \
+         {{ ... }} }}) avoids starting a login shell",
+        command.trim()
+    );
+    Some((LintKind::ShellCouldBeExec, message))
+}
+
+/// Flag an exec program named without a path that the default `PATH` does not provide.
+fn exec_finding(spec: &ExecSpec) -> Option<(LintKind, String)> {
+    let program = spec.program.as_str();
+    if program.is_empty()
+        || program.contains('/')
+        || spec.env.contains_key("PATH")
+        || DEFAULT_PATH_PROGRAMS.contains(&program)
+    {
+        return None;
+    }
+    let message = format!(
+        "exec program \"{program}\" has no path and is not a macOS system program, so it may not \
+         be on Hotki's PATH, which for an app started from Finder or a login item is only \
+         /usr/bin:/bin:/usr/sbin:/sbin; use an absolute path or set PATH in env"
+    );
+    Some((LintKind::ExecRelativeProgram, message))
+}
+
+/// Whether an allow comment on the finding's line or the line above names its lint.
+fn suppressed(cfg: &LoadedConfig, finding: &LintFinding) -> bool {
+    let Some(pos) = &finding.location else {
+        return false;
+    };
+    let (Some(path), Some(line)) = (&pos.path, pos.line) else {
+        return false;
+    };
+    let Some(source) = cfg.source_for(path) else {
+        return false;
+    };
+    let code = finding.kind.code();
+    source
+        .lines()
+        .skip(line.saturating_sub(2))
+        .take(if line > 1 { 2 } else { 1 })
+        .any(|text| allows(text, code))
+}
+
+/// Whether a source line carries an allow comment naming `code`.
+fn allows(text: &str, code: &str) -> bool {
+    let Some((_, comment)) = text.split_once("--") else {
+        return false;
+    };
+    let Some((_, rest)) = comment.split_once(ALLOW_MARKER) else {
+        return false;
+    };
+    let Some((ids, _)) = rest.split_once(')') else {
+        return false;
+    };
+    ids.split(',').any(|id| id.trim() == code)
+}
+
+/// Order declaration sites by file, line, and column, with unknown sites last.
+fn site_order(a: Option<&SourcePos>, b: Option<&SourcePos>) -> Ordering {
+    let key = |pos: Option<&SourcePos>| pos.map(|pos| (pos.path.clone(), pos.line, pos.col));
+    match (key(a), key(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
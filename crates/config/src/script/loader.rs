@@ -22,12 +22,18 @@ use super::{
     config::SourceMap,
     diagnostics,
     host_hotki::build_hotki_module,
-    host_runtime::{ApplicationCache, chunk_name},
+    host_runtime::{
+        ApplicationCache, ClipboardHistorySettings, ScheduleSettings, ServiceSettings, SpecHarness,
+        chunk_name,
+    },
     host_userdata::{ModeBuilder, mode_builder_userdata, mode_context_userdata},
     module_source::ConfigModuleSource,
     util::lock_unpoisoned,
 };
-use crate::{Error, ResolvedStyle, StyleResolver, error::excerpt_at};
+use crate::{Diagnostic, Error, ResolvedStyle, Severity, StyleResolver, error::excerpt_at};
+
+/// Declared type every config entry module must return.
+const ROOT_TYPE: &str = "ModeRenderer";
 
 /// Load a Luau config from a file at `path`.
 pub fn load_dynamic_config(path: &Path) -> Result<LoadedConfig, Error> {
@@ -41,10 +47,10 @@ pub fn load_dynamic_config_with_style(
     resolved_style: ResolvedStyle,
 ) -> Result<LoadedConfig, Error> {
     if path.extension() != Some(OsStr::new("luau")) {
-        return Err(Error::Read {
-            path: Some(path.to_path_buf()),
-            message: "Unsupported config format (expected a .luau file)".to_string(),
-        });
+        return Err(diagnostics::config_validation(
+            Some(path.to_path_buf()),
+            "Unsupported config format (expected a .luau file)",
+        ));
     }
 
     let path = fs::canonicalize(path).map_err(|err| Error::Read {
@@ -92,11 +98,20 @@ fn load_dynamic_config_from_string_with_style(
 
     let applications = Arc::new(Mutex::new(ApplicationCache::default()));
     let callbacks = LoadedConfig::callback_registry();
-    let module = build_hotki_module(applications)
-        .map_err(|err| diagnostics::config_validation(path.clone(), err))?;
+    let clipboard_history = Arc::new(Mutex::new(ClipboardHistorySettings::default()));
+    let services = Arc::new(Mutex::new(ServiceSettings::default()));
+    let schedules = Arc::new(Mutex::new(ScheduleSettings::default()));
+    let module = build_hotki_module(
+        applications,
+        Arc::clone(&clipboard_history),
+        Arc::clone(&services),
+        Arc::clone(&schedules),
+        Arc::new(Mutex::new(SpecHarness::default())),
+    )
+    .map_err(|err| diagnostics::config_validation(path.clone(), err))?;
     let (surface, program, module_source, module_count) = if let Some(path) = path.as_deref() {
         let (surface, prepared, module_source, module_count) =
-            prepare_filesystem_config(source, path, module, &sources)?;
+            prepare_filesystem_config(source, path, module, &sources, Some(ROOT_TYPE))?;
         (
             surface,
             RootProgram::Prepared(Box::new(prepared)),
@@ -104,7 +119,7 @@ fn load_dynamic_config_from_string_with_style(
             module_count,
         )
     } else {
-        let surface = build_surface(module, None, path.as_deref())?;
+        let surface = build_surface(module, None, path.as_deref(), Some(ROOT_TYPE))?;
         let chunk = surface
             .runtime_capabilities()
             .compile_source(source.as_bytes(), &CompileOptions::new())
@@ -146,6 +161,21 @@ fn load_dynamic_config_from_string_with_style(
         Ok(())
     });
     let entry_gas = runtime.gas_spent();
+    let clipboard_history = {
+        let mut settings = lock_unpoisoned(&clipboard_history);
+        settings.sealed = true;
+        settings.config.clone()
+    };
+    let services = {
+        let mut settings = lock_unpoisoned(&services);
+        settings.sealed = true;
+        settings.specs.clone()
+    };
+    let schedules = {
+        let mut settings = lock_unpoisoned(&schedules);
+        settings.sealed = true;
+        settings.actions.clone()
+    };
     let synchronized = super::callback::CallbackRegistry::synchronize(&callbacks, &mut runtime)
         .map_err(|err| diagnostics::config_retained_error(path.clone(), &err));
     let unloaded = runtime
@@ -183,19 +213,27 @@ fn load_dynamic_config_from_string_with_style(
         module_count,
         entry_gas,
         validation_gas,
+        clipboard_history,
+        services,
+        schedules,
     })
 }
 
 /// Build the shared typed surface, optionally granting filesystem modules.
+///
+/// `root_type` constrains the entry module's return type when set.
 fn build_surface(
     module: Arc<dyn NativeModule>,
     source: Option<Arc<dyn SourceProvider>>,
     path: Option<&Path>,
+    root_type: Option<&str>,
 ) -> Result<Surface, Error> {
     let mut builder = Surface::builder()
         .enable_runtime_compilation()
-        .module(module)
-        .require_return("ModeRenderer");
+        .module(module);
+    if let Some(root_type) = root_type {
+        builder = builder.require_return(root_type);
+    }
     if let Some(source) = source {
         builder = builder.module_source(source);
     }
@@ -205,11 +243,12 @@ fn build_surface(
 }
 
 /// Prepare a contextual root and its exact cached filesystem graph.
-fn prepare_filesystem_config(
+pub(super) fn prepare_filesystem_config(
     source: &str,
     path: &Path,
     module: Arc<dyn NativeModule>,
     sources: &SourceMap,
+    root_type: Option<&str>,
 ) -> Result<(Surface, PreparedGraph, Arc<ConfigModuleSource>, usize), Error> {
     let root_dir = path.parent().ok_or_else(|| Error::Read {
         path: Some(path.to_path_buf()),
@@ -238,6 +277,7 @@ fn prepare_filesystem_config(
         module,
         Some(Arc::clone(&module_source) as Arc<dyn SourceProvider>),
         Some(path),
+        root_type,
     )?;
     let root = Source::text(entry_id, source.to_owned())
         .with_metadata(SourceMetadata::new(path.display().to_string()));
@@ -264,14 +304,7 @@ fn graph_prepare_error(path: &Path, sources: &SourceMap, error: &PrepareGraphErr
     let Some(view) = views.next() else {
         return diagnostics::config_validation(Some(path.to_path_buf()), error);
     };
-    let display_path = PathBuf::from(view.display_name);
-    let diagnostic_path = if display_path.is_absolute() {
-        display_path
-    } else {
-        path.parent()
-            .unwrap_or_else(|| Path::new(""))
-            .join(display_path)
-    };
+    let diagnostic_path = graph_diagnostic_path(path, &view.display_name);
     let location = view.diagnostic.primary_location;
     let (line, col) = if location.is_missing() {
         (None, None)
@@ -286,6 +319,14 @@ fn graph_prepare_error(path: &Path, sources: &SourceMap, error: &PrepareGraphErr
             .get(&diagnostic_path)
             .map(|source| excerpt_at(source, line, col))
     });
+    let mut findings = vec![Diagnostic {
+        path: Some(diagnostic_path.clone()),
+        line,
+        col,
+        severity: Severity::Error,
+        code: view.diagnostic.category.to_string(),
+        message: view.diagnostic.message.clone(),
+    }];
     let mut message = view.diagnostic.message;
     for additional in views {
         let location = additional.diagnostic.primary_location;
@@ -307,6 +348,15 @@ fn graph_prepare_error(path: &Path, sources: &SourceMap, error: &PrepareGraphErr
             )
             .expect("writing a diagnostic to String cannot fail");
         }
+        let located = !location.is_missing();
+        findings.push(Diagnostic {
+            path: Some(graph_diagnostic_path(path, &additional.display_name)),
+            line: located.then_some(location.begin.line as usize),
+            col: located.then_some(location.begin.column as usize),
+            severity: Severity::Error,
+            code: additional.diagnostic.category.to_string(),
+            message: additional.diagnostic.message,
+        });
     }
     Error::Validation {
         path: Some(diagnostic_path),
@@ -314,11 +364,24 @@ fn graph_prepare_error(path: &Path, sources: &SourceMap, error: &PrepareGraphErr
         col,
         message,
         excerpt,
+        diagnostics: findings,
+    }
+}
+
+/// Resolve a graph diagnostic's display name against the entry config's directory.
+fn graph_diagnostic_path(path: &Path, display_name: &str) -> PathBuf {
+    let display_path = PathBuf::from(display_name);
+    if display_path.is_absolute() {
+        display_path
+    } else {
+        path.parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(display_path)
     }
 }
 
 /// Build the sandboxed retained runtime used by a dynamic config.
-fn build_runtime(surface: Surface, path: Option<&Path>) -> Result<Runtime, Error> {
+pub(super) fn build_runtime(surface: Surface, path: Option<&Path>) -> Result<Runtime, Error> {
     Runtime::new(
         surface,
         &VmConfig::untrusted(Ambient::deterministic(0), Limits::unlimited()),
@@ -334,18 +397,20 @@ fn validate_root(
     path: Option<&Path>,
     sources: &SourceMap,
 ) -> Result<(), Error> {
-    let builder = ModeBuilder::new_for_render(false);
+    let builder = ModeBuilder::new_for_render(false, true);
     let ctx = ModeCtx {
         window: None,
         hud: false,
         depth: 0,
+        recent_windows: Vec::new(),
+        clipboard_history: Vec::new(),
     };
     let mut script_error = None;
     let options = LoadedConfig::entry_options();
     let mut context = super::callback::CallbackContext::new(Arc::clone(callbacks));
     let step = runtime.step_with_context(&mut context, &options, |scope| {
         let builder = mode_builder_userdata(scope, builder.clone())?;
-        let ctx = mode_context_userdata(scope, ctx.clone())?;
+        let ctx = mode_context_userdata(scope, ctx.clone(), None)?;
         let root = root.func.resolve(scope)?;
         let result: Result<(), ScriptError<'_>> = scope.call_protected(root, (builder, ctx))?;
         if let Err(err) = result {
@@ -1,5 +1,7 @@
 //! Luau-backed configuration runtime.
 
+/// Binding conflict and shadowing analysis.
+pub mod analysis;
 /// Application discovery for selector providers.
 mod apps;
 /// Retained callback ownership and call-context plumbing.
@@ -7,6 +9,8 @@ mod callback;
 /// Loaded config state and source tracking.
 pub mod config;
 pub mod diagnostics;
+/// Chord resolution explanations.
+pub mod explain;
 /// Handler execution bridge.
 pub mod handler;
 mod host_args;
@@ -14,16 +18,24 @@ mod host_hotki;
 mod host_parse;
 mod host_runtime;
 pub mod host_userdata;
+/// Best-practice lints for `hotki lint`.
+pub mod lint;
 pub mod loader;
 /// Checked and cached filesystem module source.
 mod module_source;
 pub mod render;
+/// Interactive evaluation for `hotki repl`.
+pub mod repl;
 /// Selector parsing and runtime types.
 pub mod selector;
+/// Luau spec files run by `hotki test`.
+pub mod spec;
 /// Shared runtime data types.
 pub mod types;
 /// Small synchronization and locking helpers.
 mod util;
+/// Shared depth-first walk over every reachable submenu.
+pub mod walk;
 
 #[cfg(test)]
 mod test_script;
@@ -37,6 +49,7 @@ pub use render::render_stack;
 pub use selector::SelectorItems;
 pub use selector::{SelectorConfig, SelectorData, SelectorItem};
 pub use types::{
-    ActionCtx, ActionRepeatPermission, Binding, BindingFlags, BindingKind, Effect, HandlerRef,
-    ModeCtx, ModeFrame, ModeRef, NavRequest, RenderedState, RepeatSpec, SourcePos,
+    ActionCtx, ActionRepeatPermission, AppMode, Binding, BindingFlags, BindingKind, Effect,
+    HandlerRef, ModeCtx, ModeFrame, ModeRef, NavRequest, RenderedState, RepeatSpec,
+    ScheduledAction, SourcePos,
 };
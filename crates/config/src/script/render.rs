@@ -1,13 +1,15 @@
 //! Mode-stack rendering.
 
-use std::collections::HashSet;
+use std::{collections::HashSet, mem};
 
 use mac_keycode::Chord;
 use ruau::vm::ScriptError;
 use tracing::warn;
 
 use super::{
-    Binding, BindingKind, Effect, LoadedConfig, ModeCtx, ModeFrame, RenderedState, diagnostics,
+    AppMode, Binding, BindingKind, Effect, LoadedConfig, ModeCtx, ModeFrame, RenderedState,
+    diagnostics,
+    host_userdata::{ConditionLog, ModeBuildOutput},
     types::{HudRow, SourcePos},
 };
 use crate::{Error, NotifyKind, Style, error::excerpt_at};
@@ -28,6 +30,8 @@ struct ModeView {
     bindings: Vec<Binding>,
     /// Whether this mode requested capture-all behavior.
     capture: bool,
+    /// Application modes declared by the root renderer.
+    app_modes: Vec<AppMode>,
 }
 
 /// Render the full mode stack, applying empty/orphan truncation and producing HUD rows.
@@ -50,11 +54,21 @@ fn render_stack_inner(
     base_style: &Style,
 ) -> Result<RenderOutput, Error> {
     let mut warnings = Vec::new();
+    let mut badge = None;
 
     for depth in 0..stack.len() {
-        let (view, mut local_warnings) = render_mode(cfg, &stack[depth], ctx)?;
+        let (mut view, mut local_warnings) = render_mode(cfg, &stack[depth], ctx, depth == 0)?;
         warnings.append(&mut local_warnings);
 
+        if depth == 0
+            && let Some(app_mode) = active_app_mode(&view.app_modes, ctx).cloned()
+        {
+            let (layer, mut layer_warnings) = render_app_mode(cfg, &app_mode, ctx)?;
+            warnings.append(&mut layer_warnings);
+            view.bindings = overlay_bindings(layer.bindings, mem::take(&mut view.bindings));
+            badge = app_mode.badge.then_some(app_mode.title);
+        }
+
         let frame = &mut stack[depth];
         frame.rendered = view.bindings;
         frame.capture = view.capture;
@@ -91,18 +105,88 @@ fn render_stack_inner(
             hud_rows,
             style: base_style.clone(),
             capture,
+            badge,
         },
         warnings,
     })
 }
 
+/// Select the first declared application mode matching the focused application.
+fn active_app_mode<'a>(app_modes: &'a [AppMode], ctx: &ModeCtx) -> Option<&'a AppMode> {
+    let window = ctx.window.as_ref()?;
+    app_modes.iter().find(|app_mode| app_mode.app == window.app)
+}
+
+/// Render an application mode as a layer over the root frame.
+fn render_app_mode(
+    cfg: &mut LoadedConfig,
+    app_mode: &AppMode,
+    ctx: &ModeCtx,
+) -> Result<(ModeView, Vec<Effect>), Error> {
+    let frame = ModeFrame {
+        title: app_mode.title.clone(),
+        closure: app_mode.mode.clone(),
+        entered_via: None,
+        rendered: Vec::new(),
+        capture: false,
+    };
+    render_mode(cfg, &frame, ctx, false)
+}
+
+/// Merge application-mode bindings over root bindings, letting the layer win on chord clashes.
+fn overlay_bindings(layer: Vec<Binding>, root: Vec<Binding>) -> Vec<Binding> {
+    let mut seen = HashSet::new();
+    layer
+        .into_iter()
+        .chain(root)
+        .filter(|binding| seen.insert(binding.chord.to_string()))
+        .collect()
+}
+
 /// Render one mode frame and collect duplicate-chord warnings.
 fn render_mode(
     cfg: &mut LoadedConfig,
     frame: &ModeFrame,
     ctx: &ModeCtx,
+    root: bool,
 ) -> Result<(ModeView, Vec<Effect>), Error> {
-    let builder = super::host_userdata::ModeBuilder::new_for_render(frame.capture);
+    let ModeBuildOutput {
+        bindings,
+        capture,
+        app_modes,
+    } = render_mode_output(cfg, frame, ctx, root)?;
+    let (bindings, warnings) = dedup_mode_bindings(cfg, &bindings);
+
+    Ok((
+        ModeView {
+            bindings,
+            capture,
+            app_modes,
+        },
+        warnings,
+    ))
+}
+
+/// Run one mode renderer and return its declarations before duplicate chords are dropped.
+pub(crate) fn render_mode_output(
+    cfg: &mut LoadedConfig,
+    frame: &ModeFrame,
+    ctx: &ModeCtx,
+    root: bool,
+) -> Result<ModeBuildOutput, Error> {
+    render_mode_recorded(cfg, frame, ctx, root, None)
+}
+
+/// Run one mode renderer like [`render_mode_output`], recording the window conditions it
+/// consults in `conditions`.
+pub(crate) fn render_mode_recorded(
+    cfg: &mut LoadedConfig,
+    frame: &ModeFrame,
+    ctx: &ModeCtx,
+    root: bool,
+    conditions: Option<ConditionLog>,
+) -> Result<ModeBuildOutput, Error> {
+    let builder = super::host_userdata::ModeBuilder::new_for_render(frame.capture, root);
     let mut script_error = None;
     let path = cfg.path.clone();
     let sources = cfg.sources.clone();
@@ -114,7 +198,11 @@ fn render_mode(
         .step_with_context(&mut context, &options, |scope| {
             let builder_value =
                 super::host_userdata::mode_builder_userdata(scope, builder.clone())?;
-            let ctx_value = super::host_userdata::mode_context_userdata(scope, ctx.clone())?;
+            let ctx_value = super::host_userdata::mode_context_userdata(
+                scope,
+                ctx.clone(),
+                conditions.clone(),
+            )?;
             let render = frame.closure.func.resolve(scope)?;
             let result: Result<(), ScriptError<'_>> =
                 scope.call_protected(render, (builder_value, ctx_value))?;
@@ -136,11 +224,9 @@ fn render_mode(
 
     step.map_err(|err| diagnostics::config_retained_error(cfg.path.clone(), &err))?;
 
-    let (bindings, capture) = builder.finish();
+    let output = builder.finish();
     cfg.synchronize_callbacks()?;
-    let (bindings, warnings) = dedup_mode_bindings(cfg, &bindings);
-
-    Ok((ModeView { bindings, capture }, warnings))
+    Ok(output)
 }
 
 /// Keep the first binding for each chord and surface warnings for duplicates.
@@ -237,7 +323,7 @@ fn excerpt_for(cfg: &LoadedConfig, pos: &SourcePos) -> Option<String> {
 }
 
 /// Format a source position for user-facing warning messages.
-fn location_string(pos: &SourcePos) -> String {
+pub(crate) fn location_string(pos: &SourcePos) -> String {
     match (&pos.path, pos.line, pos.col) {
         (Some(path), Some(line), Some(col)) => format!("{}:{}:{}", path.display(), line, col),
         (Some(path), Some(line), None) => format!("{}:{}", path.display(), line),
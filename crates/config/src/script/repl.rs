@@ -0,0 +1,167 @@
+//! Interactive Luau evaluation against a loaded config for `hotki repl`.
+//!
+//! Each input is strictly type-checked and run as the entry module of its own graph in a fresh VM
+//! next to the config, under the same per-entrypoint limits as the config itself. Relative
+//! `require` resolves against the config directory, so module exports can be called directly.
+//! Every input sees two predeclared locals: `t`, a `TestContext` whose focused window and mode
+//! stack persist between inputs, and `ctx`, the matching `ModeContext`. Effects returned by
+//! `t:press` are reported, never executed.
+
+use std::{
+    mem,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use ruau::{
+    surface::{PreparedGraph, Surface},
+    vm::{MultiValue, ScriptError, serde::from_scoped_value},
+};
+use serde_json::Value;
+
+use super::{
+    LoadedConfig,
+    callback::{CallbackContext, CallbackRegistry},
+    diagnostics,
+    host_hotki::build_hotki_module,
+    host_runtime::{ApplicationCache, SharedApplicationCache, SpecHarness},
+    host_userdata::mode_context_userdata,
+    loader::{build_runtime, prepare_filesystem_config},
+    spec::{TestSession, test_context_userdata},
+    util::lock_unpoisoned,
+};
+use crate::{Error, runtime::ConfigRuntime};
+
+/// File name reported for REPL inputs; it sits in the config directory so requires resolve there.
+const REPL_FILE: &str = "repl.luau";
+
+/// Declarations placed on the same line as each input so reported lines match the input.
+const PRELUDE: &str = "local t: TestContext, ctx: ModeContext = ...; ";
+
+/// A REPL session over one loaded config.
+pub struct ReplSession {
+    /// Virtual path of REPL inputs inside the config directory.
+    path: PathBuf,
+    /// Harness giving `t` and `hotki.test` access to the loaded config.
+    harness: Arc<Mutex<SpecHarness>>,
+    /// Focused window and mode stack shared by every input.
+    session: Arc<Mutex<TestSession>>,
+    /// Application listing cached across inputs, as the config runtime caches it.
+    applications: SharedApplicationCache,
+}
+
+impl ReplSession {
+    /// Load the config at `config` and start a session at its root mode with no focused window.
+    pub fn new(config: &Path) -> Result<Self, Error> {
+        let runtime = Arc::new(Mutex::new(ConfigRuntime::load(config)?));
+        let dir = config.parent().unwrap_or_else(|| Path::new("."));
+        let path = dir.join(REPL_FILE);
+        let harness = Arc::new(Mutex::new(SpecHarness {
+            config: Some(Arc::clone(&runtime)),
+            path: Some(path.clone()),
+            sources: Arc::default(),
+            cases: Vec::new(),
+        }));
+        Ok(Self {
+            path,
+            harness,
+            session: Arc::new(Mutex::new(TestSession::new(runtime))),
+            applications: Arc::new(Mutex::new(ApplicationCache::default())),
+        })
+    }
+
+    /// Evaluate one input and describe what it returned.
+    ///
+    /// The input is run as an expression when it parses and type-checks as one, and as a
+    /// statement otherwise; a runtime error is reported as is. Values are rendered as JSON where
+    /// possible and by type name otherwise; `hotki.test` cases run by the input are reported as
+    /// `PASS` or `FAIL` lines.
+    pub fn eval(&mut self, input: &str) -> Result<Vec<String>, Error> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (surface, prepared) = match self.prepare(&format!("{PRELUDE}return {input}")) {
+            Ok(chunk) => chunk,
+            Err(_) => self.prepare(&format!("{PRELUDE}{input}"))?,
+        };
+        let mut output = self.run(surface, &prepared)?;
+        for case in mem::take(&mut lock_unpoisoned(&self.harness).cases) {
+            match case.failure {
+                None => output.push(format!("PASS {}", case.name)),
+                Some(failure) => output.push(format!("FAIL {}\n{failure}", case.name)),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Parse and type-check one chunk.
+    fn prepare(&self, source: &str) -> Result<(Surface, PreparedGraph), Error> {
+        let sources = Arc::clone(&lock_unpoisoned(&self.harness).sources);
+        lock_unpoisoned(&sources).insert(self.path.clone(), Arc::from(source));
+        let module = build_hotki_module(
+            Arc::clone(&self.applications),
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            Arc::clone(&self.harness),
+        )
+        .map_err(|err| diagnostics::config_validation(Some(self.path.clone()), err))?;
+        let (surface, prepared, _, _) =
+            prepare_filesystem_config(source, &self.path, module, &sources, None)?;
+        Ok((surface, prepared))
+    }
+
+    /// Load and run a prepared chunk, returning its rendered results.
+    fn run(&self, surface: Surface, prepared: &PreparedGraph) -> Result<Vec<String>, Error> {
+        let sources = Arc::clone(&lock_unpoisoned(&self.harness).sources);
+        let path = Some(self.path.as_path());
+        let mut runtime = build_runtime(surface, path)?;
+        let loaded = runtime
+            .load_prepared(prepared)
+            .map_err(|err| diagnostics::config_retained_error(Some(self.path.clone()), &err))?;
+
+        let callbacks = LoadedConfig::callback_registry();
+        let mut context = CallbackContext::new(Arc::clone(&callbacks));
+        let options = LoadedConfig::entry_options();
+        let ctx = lock_unpoisoned(&self.session).ctx();
+        let mut output = Vec::new();
+        let mut script_error = None;
+        let run =
+            runtime.step_root_with_context(&loaded, &mut context, &options, |scope, entry| {
+                let t = test_context_userdata(scope, Arc::clone(&self.session))?;
+                let ctx = mode_context_userdata(scope, ctx.clone(), None)?;
+                let result: Result<MultiValue<'_>, ScriptError<'_>> =
+                    scope.call_protected(entry, (t, ctx))?;
+                match result {
+                    Ok(values) => {
+                        for value in values.into_vec() {
+                            let type_name = value.type_name();
+                            output.push(match from_scoped_value::<Value>(scope, value) {
+                                Ok(json) => json.to_string(),
+                                Err(_) => format!("<{type_name}>"),
+                            });
+                        }
+                    }
+                    Err(err) => {
+                        script_error = Some(diagnostics::config_script_error(
+                            path, &sources, scope, &err,
+                        ));
+                    }
+                }
+                Ok(())
+            });
+        let synchronized = CallbackRegistry::synchronize(&callbacks, &mut runtime)
+            .map_err(|err| diagnostics::config_retained_error(Some(self.path.clone()), &err));
+        let unloaded = runtime
+            .unload(&loaded)
+            .map_err(|err| diagnostics::config_retained_error(Some(self.path.clone()), &err));
+        run.map_err(|err| diagnostics::config_retained_error(Some(self.path.clone()), &err))?;
+        synchronized?;
+        unloaded?;
+        match script_error {
+            Some(error) => Err(error),
+            None => Ok(output),
+        }
+    }
+}
@@ -236,7 +236,7 @@ impl SelectorConfig {
                     .step_with_context(&mut context, &options, |scope| {
                         let provider = provider.0.resolve(scope)?;
                         let ctx_value =
-                            super::host_userdata::mode_context_userdata(scope, ctx.clone())?;
+                            super::host_userdata::mode_context_userdata(scope, ctx.clone(), None)?;
                         let result = scope.call_protected(provider, ctx_value)?;
                         match result {
                             Ok(value) => items = Some(parse_selector_items(scope, value)?),
@@ -263,6 +263,7 @@ impl SelectorConfig {
                     col: None,
                     message: "selector provider returned no items".to_string(),
                     excerpt: None,
+                    diagnostics: Vec::new(),
                 })
             }
         }
@@ -0,0 +1,484 @@
+//! Luau spec files run by `hotki test`.
+//!
+//! A spec file runs in its own VM next to the config under test. Each `hotki.test` case gets a
+//! `TestContext` holding a fresh mode stack over the shared config, so cases can focus a fake
+//! window, render the HUD, and press chords. Effects are returned to the spec instead of being
+//! executed; navigation effects move the case's mode stack the way the engine would.
+
+use std::{
+    collections::HashMap,
+    fmt, fs, mem,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use mac_keycode::Chord;
+use ruau::{
+    bytecode::CompileOptions,
+    session::LoadTarget,
+    surface::Surface,
+    vm::{
+        HostType, HostTypeBuilder, MultiValue, RuntimeError, Scope, ScopedValue, ScriptError,
+        Table, Userdata, serde::to_scoped_value,
+    },
+};
+
+use super::{
+    BindingKind, Effect, LoadedConfig, ModeCtx, NavRequest, RenderedState,
+    callback::{CallbackContext, CallbackRegistry},
+    diagnostics,
+    host_args::HostArgs,
+    host_hotki::build_hotki_module,
+    host_parse::{TestWindowSpec, parse_chord, parse_optional},
+    host_runtime::{SharedSpecHarness, SpecHarness, chunk_name},
+    loader::build_runtime,
+    types::HudRow,
+    util::lock_unpoisoned,
+};
+use crate::{
+    Action, Error,
+    runtime::{ConfigRuntime, ModeStack},
+};
+
+/// File name suffix marking a Luau spec file.
+const SPEC_SUFFIX: &str = ".spec.luau";
+
+/// Outcome of one `hotki.test` case.
+#[derive(Debug, Clone)]
+pub struct SpecCase {
+    /// Name passed to `hotki.test`.
+    pub name: String,
+    /// Rendered failure, or `None` when the case passed.
+    pub failure: Option<String>,
+}
+
+/// Find every `*.spec.luau` file under the directory holding `config`, in path order.
+///
+/// Hidden directories are skipped.
+pub fn discover_specs(config: &Path) -> Result<Vec<PathBuf>, Error> {
+    let root = config.parent().unwrap_or_else(|| Path::new("."));
+    let mut specs = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|err| Error::Read {
+            path: Some(dir.clone()),
+            message: err.to_string(),
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|err| Error::Read {
+                    path: Some(dir.clone()),
+                    message: err.to_string(),
+                })?
+                .path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if path.is_dir() {
+                if !name.starts_with('.') {
+                    pending.push(path);
+                }
+            } else if name.ends_with(SPEC_SUFFIX) {
+                specs.push(path);
+            }
+        }
+    }
+    specs.sort();
+    Ok(specs)
+}
+
+/// Load the config at `config` and run the spec file at `spec` against it.
+///
+/// Every case shares one loaded config. A failing case is recorded and later cases still run;
+/// an error outside any case fails the whole file.
+pub fn run_spec_file(config: &Path, spec: &Path) -> Result<Vec<SpecCase>, Error> {
+    let runtime = ConfigRuntime::load(config)?;
+    let source = fs::read_to_string(spec).map_err(|err| Error::Read {
+        path: Some(spec.to_path_buf()),
+        message: err.to_string(),
+    })?;
+    let sources = Arc::new(Mutex::new(HashMap::from([(
+        spec.to_path_buf(),
+        Arc::from(source.clone().into_boxed_str()),
+    )])));
+    let harness = Arc::new(Mutex::new(SpecHarness {
+        config: Some(Arc::new(Mutex::new(runtime))),
+        path: Some(spec.to_path_buf()),
+        sources: Arc::clone(&sources),
+        cases: Vec::new(),
+    }));
+
+    let module = build_hotki_module(
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+        Arc::clone(&harness),
+    )
+    .map_err(|err| diagnostics::config_validation(Some(spec.to_path_buf()), err))?;
+    let surface = Surface::builder()
+        .enable_runtime_compilation()
+        .module(module)
+        .build()
+        .map_err(|err| diagnostics::config_validation(Some(spec.to_path_buf()), err))?;
+    let chunk = surface
+        .runtime_capabilities()
+        .compile_source(source.as_bytes(), &CompileOptions::new())
+        .map_err(|err| diagnostics::config_compile_error(&source, &err, Some(spec)))?;
+    let mut runtime = build_runtime(surface, Some(spec))?;
+    let loaded = runtime
+        .load_compiled(
+            &chunk,
+            &LoadTarget::named(chunk_name(Some(spec)).into_bytes()),
+        )
+        .map_err(|err| diagnostics::config_retained_error(Some(spec.to_path_buf()), &err))?;
+
+    let callbacks = LoadedConfig::callback_registry();
+    let mut context = CallbackContext::new(Arc::clone(&callbacks));
+    let options = LoadedConfig::entry_options();
+    let mut script_error = None;
+    let run = runtime.step_root_with_context(&loaded, &mut context, &options, |scope, entry| {
+        let result: Result<MultiValue<'_>, ScriptError<'_>> = scope.call_protected(entry, ())?;
+        if let Err(err) = result {
+            script_error = Some(diagnostics::config_script_error(
+                Some(spec),
+                &sources,
+                scope,
+                &err,
+            ));
+        }
+        Ok(())
+    });
+    let synchronized = CallbackRegistry::synchronize(&callbacks, &mut runtime)
+        .map_err(|err| diagnostics::config_retained_error(Some(spec.to_path_buf()), &err));
+    let unloaded = runtime
+        .unload(&loaded)
+        .map_err(|err| diagnostics::config_retained_error(Some(spec.to_path_buf()), &err));
+    run.map_err(|err| diagnostics::config_retained_error(Some(spec.to_path_buf()), &err))?;
+    synchronized?;
+    unloaded?;
+    if let Some(error) = script_error {
+        return Err(error);
+    }
+    Ok(mem::take(&mut lock_unpoisoned(&harness).cases))
+}
+
+/// One case's view of the config under test.
+pub(super) struct TestSession {
+    /// Config shared by every case in the spec file.
+    config: Arc<Mutex<ConfigRuntime>>,
+    /// Mode stack moved by presses and navigation effects.
+    stack: ModeStack,
+    /// Fake focused window, if any.
+    window: Option<hotki_protocol::FocusSnapshot>,
+}
+
+impl TestSession {
+    /// Start a session at the root mode with no focused window.
+    pub(super) fn new(config: Arc<Mutex<ConfigRuntime>>) -> Self {
+        let mut stack = ModeStack::default();
+        lock_unpoisoned(&config).ensure_stack(&mut stack);
+        Self {
+            config,
+            stack,
+            window: None,
+        }
+    }
+
+    /// Context passed to renderers and actions; the HUD counts as visible inside a submenu.
+    pub(super) fn ctx(&self) -> ModeCtx {
+        ModeCtx {
+            window: self.window.clone(),
+            hud: self.stack.depth() > 0,
+            depth: self.stack.depth() as i64,
+            recent_windows: Vec::new(),
+            clipboard_history: Vec::new(),
+        }
+    }
+
+    /// Render the current stack.
+    fn render(&mut self) -> Result<RenderedState, Error> {
+        let ctx = self.ctx();
+        lock_unpoisoned(&self.config)
+            .render(&mut self.stack, &ctx)
+            .map(|render| render.state)
+    }
+
+    /// Press `chord` in the current mode, returning `None` when nothing is bound to it.
+    ///
+    /// A submenu binding enters the submenu and emits no effects. A handler's effects are
+    /// returned; its navigation is applied to the stack, and without navigation, a selector, or
+    /// a stay request the stack returns to the root as the engine's auto-exit would.
+    fn press(&mut self, chord: &Chord) -> Result<Option<Vec<Effect>>, Error> {
+        let rendered = self.render()?;
+        let Some(binding) = ConfigRuntime::resolve_binding(&rendered, chord).cloned() else {
+            return Ok(None);
+        };
+        let stays = binding.stays_in_mode();
+        match binding.kind {
+            BindingKind::Mode(mode) => {
+                self.stack.push(
+                    binding.desc,
+                    mode,
+                    binding.mode_id.map(|id| (chord.clone(), id)),
+                    binding.mode_capture,
+                );
+                Ok(Some(Vec::new()))
+            }
+            BindingKind::Handler(handler) => {
+                let ctx = self.ctx();
+                let result = lock_unpoisoned(&self.config).execute_handler(&handler, &ctx)?;
+                let mut navigated = false;
+                for effect in &result.effects {
+                    match effect {
+                        Effect::Nav(nav) => {
+                            navigated = true;
+                            self.navigate(nav);
+                        }
+                        Effect::Select(_) => navigated = true,
+                        _ => {}
+                    }
+                }
+                if !navigated && !result.stay && !stays {
+                    self.stack.reset_to_root();
+                }
+                Ok(Some(result.effects))
+            }
+        }
+    }
+
+    /// Apply one navigation request to the stack.
+    fn navigate(&mut self, nav: &NavRequest) {
+        match nav {
+            NavRequest::Push { mode, title } => {
+                let title = title
+                    .clone()
+                    .or_else(|| mode.default_title().map(str::to_string))
+                    .unwrap_or_else(|| "mode".to_string());
+                self.stack.push(title, mode.clone(), None, false);
+            }
+            NavRequest::Pop => {
+                self.stack.pop();
+            }
+            NavRequest::Exit | NavRequest::ShowRoot => {
+                self.stack.reset_to_root();
+            }
+            NavRequest::HideHud => {}
+        }
+    }
+}
+
+/// Luau userdata wrapper for one case's session.
+#[derive(Clone)]
+struct TestContextUserData(Arc<Mutex<TestSession>>);
+
+impl fmt::Debug for TestContextUserData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestContextUserData")
+            .finish_non_exhaustive()
+    }
+}
+
+impl TestContextUserData {
+    /// Lock the session for one method call.
+    fn session(&self) -> MutexGuard<'_, TestSession> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+/// Wrap a shared session as a `TestContext` value.
+pub(super) fn test_context_userdata<'s>(
+    scope: &Scope<'s>,
+    session: Arc<Mutex<TestSession>>,
+) -> Result<Userdata<'s>, RuntimeError> {
+    scope.create_userdata(TestContextUserData(session))
+}
+
+/// Build the host userdata type definition for spec case contexts.
+pub(super) fn test_context_type() -> HostType {
+    HostTypeBuilder::<TestContextUserData>::new("TestContext")
+        .getter("depth", |_, this| Ok(this.session().stack.depth() as i64))
+        .method_raw("focus", test_context_focus)
+        .method_raw("rows", test_context_rows)
+        .method_raw("row", test_context_row)
+        .method_raw("press", test_context_press)
+        .declaration("declare class TestContext\nend\n")
+        .build()
+}
+
+/// Host implementation of `hotki.test`.
+pub(super) fn hotki_test<'s>(
+    harness: &SharedSpecHarness,
+    scope: &Scope<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let name = args.string(scope, "hotki.test name")?;
+    let body = args.function("hotki.test body")?;
+    args.finish("hotki.test")?;
+    let (config, path, sources) = {
+        let harness = lock_unpoisoned(harness);
+        let config = harness.config.clone().ok_or_else(|| {
+            RuntimeError::runtime("hotki.test is only available in spec files run by `hotki test`")
+        })?;
+        (config, harness.path.clone(), Arc::clone(&harness.sources))
+    };
+    let context = test_context_userdata(scope, Arc::new(Mutex::new(TestSession::new(config))))?;
+    let result: Result<(), ScriptError<'_>> = scope.call_protected(body, context)?;
+    let failure = result.err().map(|err| {
+        diagnostics::config_script_error(path.as_deref(), &sources, scope, &err).pretty()
+    });
+    lock_unpoisoned(harness)
+        .cases
+        .push(SpecCase { name, failure });
+    Ok(MultiValue::new())
+}
+
+/// Implement `t:focus`.
+fn test_context_focus<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let window = args.optional();
+    args.finish("t:focus")?;
+    let window = parse_optional::<TestWindowSpec>(scope, window)?;
+    receiver
+        .borrow::<TestContextUserData>(scope)?
+        .session()
+        .window = window.map(TestWindowSpec::into_snapshot);
+    Ok(MultiValue::new())
+}
+
+/// Implement `t:rows`.
+fn test_context_rows<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    HostArgs::new(args).finish("t:rows")?;
+    let rows = render_rows(scope, &receiver)?;
+    let table = scope.create_table()?;
+    for (idx, row) in rows.iter().enumerate() {
+        table.set(scope, (idx + 1) as f64, row_table(scope, row)?)?;
+    }
+    Ok(MultiValue::from_values(vec![ScopedValue::Table(table)]))
+}
+
+/// Implement `t:row`.
+fn test_context_row<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let chord = parse_chord(&args.string(scope, "t:row key")?)?;
+    args.finish("t:row")?;
+    let row = render_rows(scope, &receiver)?
+        .into_iter()
+        .find(|row| row.chord == chord);
+    let value = match row {
+        Some(row) => ScopedValue::Table(row_table(scope, &row)?),
+        None => ScopedValue::Nil,
+    };
+    Ok(MultiValue::from_values(vec![value]))
+}
+
+/// Implement `t:press`.
+fn test_context_press<'s>(
+    scope: &Scope<'s>,
+    receiver: Userdata<'s>,
+    args: MultiValue<'s>,
+) -> Result<MultiValue<'s>, RuntimeError> {
+    let mut args = HostArgs::new(args);
+    let key = args.string(scope, "t:press key")?;
+    args.finish("t:press")?;
+    let chord = parse_chord(&key)?;
+    let effects = {
+        let this = receiver.borrow::<TestContextUserData>(scope)?;
+        let mut session = this.session();
+        let effects = session.press(&chord).map_err(RuntimeError::external)?;
+        effects.ok_or_else(|| {
+            RuntimeError::runtime(format!(
+                "t:press: no binding for {key} at depth {}",
+                session.stack.depth()
+            ))
+        })?
+    };
+    let table = scope.create_table()?;
+    for (idx, effect) in effects.iter().enumerate() {
+        table.set(scope, (idx + 1) as f64, effect_table(scope, effect)?)?;
+    }
+    Ok(MultiValue::from_values(vec![ScopedValue::Table(table)]))
+}
+
+/// Render the receiver's stack and return its HUD rows.
+fn render_rows<'s>(
+    scope: &Scope<'s>,
+    receiver: &Userdata<'s>,
+) -> Result<Vec<HudRow>, RuntimeError> {
+    let this = receiver.borrow::<TestContextUserData>(scope)?;
+    let rendered = this.session().render().map_err(RuntimeError::external)?;
+    Ok(rendered.hud_rows)
+}
+
+/// Convert one HUD row into a `TestRow` table.
+fn row_table<'s>(scope: &Scope<'s>, row: &HudRow) -> Result<Table<'s>, RuntimeError> {
+    let table = scope.create_table()?;
+    table.set(scope, "key", row.chord.to_string())?;
+    table.set(scope, "desc", row.desc.clone())?;
+    table.set(scope, "mode", row.is_mode)?;
+    table.set(scope, "stay", row.stay)?;
+    Ok(table)
+}
+
+/// Convert one effect into a `TestEffect` table.
+fn effect_table<'s>(scope: &Scope<'s>, effect: &Effect) -> Result<Table<'s>, RuntimeError> {
+    let table = scope.create_table()?;
+    match effect {
+        Effect::Exec(Action::Shell(spec)) => {
+            table.set(scope, "kind", "shell")?;
+            table.set(scope, "spec", spec.command().to_string())?;
+        }
+        Effect::Exec(action) => {
+            let (kind, spec) = match serde_json::to_value(action).map_err(RuntimeError::external)? {
+                serde_json::Value::Object(fields) => fields
+                    .into_iter()
+                    .next()
+                    .map(|(kind, spec)| (kind, Some(spec)))
+                    .ok_or_else(|| RuntimeError::runtime("action serialized without a kind"))?,
+                serde_json::Value::String(kind) => (kind, None),
+                other => (other.to_string(), None),
+            };
+            table.set(scope, "kind", kind)?;
+            if let Some(spec) = spec {
+                table.set(scope, "spec", to_scoped_value(scope, &spec)?)?;
+            }
+        }
+        Effect::Notify { kind, title, body } => {
+            table.set(scope, "kind", "notify")?;
+            table.set(scope, "notify", to_scoped_value(scope, kind)?)?;
+            table.set(scope, "title", title.clone())?;
+            table.set(scope, "body", body.clone())?;
+        }
+        Effect::Nav(NavRequest::Push { mode, title }) => {
+            table.set(scope, "kind", "push")?;
+            let title = title
+                .clone()
+                .or_else(|| mode.default_title().map(str::to_string));
+            table.set(scope, "title", title)?;
+        }
+        Effect::Nav(NavRequest::Pop) => table.set(scope, "kind", "pop")?,
+        Effect::Nav(NavRequest::Exit) => table.set(scope, "kind", "exit")?,
+        Effect::Nav(NavRequest::ShowRoot) => table.set(scope, "kind", "show_root")?,
+        Effect::Nav(NavRequest::HideHud) => table.set(scope, "kind", "hide_hud")?,
+        Effect::Select(selector) => {
+            table.set(scope, "kind", "select")?;
+            table.set(scope, "title", selector.title.clone())?;
+        }
+        Effect::UntilKeyUp { .. } => table.set(scope, "kind", "until_keyup")?,
+        Effect::WithClipboard(_) => table.set(scope, "kind", "with_clipboard")?,
+    }
+    Ok(table)
+}
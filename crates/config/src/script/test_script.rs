@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use std::{
+        collections::BTreeMap,
         fs,
         path::{Path, PathBuf},
         sync::atomic::{AtomicU64, Ordering},
@@ -10,12 +11,17 @@ mod tests {
     use mac_keycode::Chord;
 
     use crate::{
-        Action, Error, Style, load_dynamic_config,
+        Action, Error, FocusTarget, LintKind, MissedRuns, ReplSession, ServiceRestart, ServiceSpec,
+        Severity, Style, discover_specs, explain_chord, lint_config, load_dynamic_config,
+        run_spec_file,
         script::{
             ActionRepeatPermission, Binding, BindingKind, Effect, LoadedConfig, ModeCtx, ModeFrame,
             NavRequest, RenderedState, RepeatSpec, SelectorItems,
             config::{SCRIPT_GAS_LIMIT, SCRIPT_MEMORY_LIMIT},
-            handler::{execute_handler, execute_handler_with_permission, execute_selector_handler},
+            handler::{
+                execute_clipboard_handler, execute_handler, execute_handler_with_permission,
+                execute_selector_handler,
+            },
             load_dynamic_config_from_string, render_stack,
         },
     };
@@ -44,6 +50,8 @@ mod tests {
             }),
             hud,
             depth,
+            recent_windows: Vec::new(),
+            clipboard_history: Vec::new(),
         }
     }
 
@@ -930,6 +938,202 @@ end
         assert!(error.pretty().contains("app_name must not be empty"));
     }
 
+    #[test]
+    fn bundle_and_window_relays_capture_their_targets() {
+        let source = r#"
+local a = hotki.actions
+local music = a.relay_to_bundle("com.example.music")
+
+return function(menu, ctx)
+    menu:bind("b", "bundle relay", music("space"))
+    menu:bind("w", "window relay", function(c)
+        c:relay_to_window("cmd+s")
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load config");
+        let style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &style).expect("render config");
+
+        for (chord, expected) in [
+            ("b", crate::RelaySpec::bundle("com.example.music", "space")),
+            ("w", crate::RelaySpec::pid(42, "cmd+s")),
+        ] {
+            let BindingKind::Handler(handler) = find_binding(&out.rendered, chord).kind.clone()
+            else {
+                panic!("expected relay handler");
+            };
+            let result = execute_handler(&mut cfg, &handler, &ctx).expect("run relay");
+            assert!(
+                matches!(&result.effects[0], Effect::Exec(Action::Relay(spec)) if spec == &expected),
+                "unexpected effects: {:?}",
+                result.effects
+            );
+        }
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "w").kind.clone() else {
+            panic!("expected relay handler");
+        };
+        let no_window = ModeCtx {
+            window: None,
+            ..ctx
+        };
+        let error = execute_handler(&mut cfg, &handler, &no_window).expect_err("no window");
+        assert!(error.pretty().contains("requires ctx.window"));
+    }
+
+    #[test]
+    fn process_relays_opt_into_modifier_events() {
+        let source = r#"
+local a = hotki.actions
+local electron = a.relay_to_app("Slack", { modifier_events = true })
+
+return function(menu, ctx)
+    menu:bind("a", "app relay", electron("cmd+k"))
+    menu:bind("b", "bundle relay", function(c)
+        c:relay_to_bundle("com.example.chat", "cmd+k", { modifier_events = true })
+    end)
+    menu:bind("w", "window relay", function(c)
+        c:relay_to_window("cmd+k", { modifier_events = false })
+    end)
+    menu:bind("x", "bad option", function(c)
+        c:relay_to_window("cmd+k", { modifiers = true })
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load config");
+        let style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &style).expect("render config");
+
+        for (chord, expected) in [
+            (
+                "a",
+                crate::RelaySpec::application("Slack", "cmd+k").with_modifier_events(true),
+            ),
+            (
+                "b",
+                crate::RelaySpec::bundle("com.example.chat", "cmd+k").with_modifier_events(true),
+            ),
+            ("w", crate::RelaySpec::pid(42, "cmd+k")),
+        ] {
+            let BindingKind::Handler(handler) = find_binding(&out.rendered, chord).kind.clone()
+            else {
+                panic!("expected relay handler");
+            };
+            let result = execute_handler(&mut cfg, &handler, &ctx).expect("run relay");
+            assert!(
+                matches!(&result.effects[0], Effect::Exec(Action::Relay(spec)) if spec == &expected),
+                "unexpected effects: {:?}",
+                result.effects
+            );
+        }
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "x").kind.clone() else {
+            panic!("expected relay handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("unknown option");
+        assert!(error.pretty().contains("modifiers"));
+    }
+
+    #[test]
+    fn relay_sequences_keep_chord_order_and_reject_empty_lists() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("k", "save all", a.relay_sequence({ "cmd+k", "cmd+s" }, { delay_ms = 30 }))
+    menu:bind("e", "empty", a.relay_sequence({}))
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load config");
+        let style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &style).expect("render config");
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "k").kind.clone() else {
+            panic!("expected relay_sequence handler");
+        };
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("run relay_sequence");
+        let expected = crate::RelaySequenceSpec {
+            chords: vec!["cmd+k".to_string(), "cmd+s".to_string()],
+            delay_ms: Some(30),
+        };
+        assert!(
+            matches!(&result.effects[0], Effect::Exec(Action::RelaySequence(spec)) if spec == &expected),
+            "unexpected effects: {:?}",
+            result.effects
+        );
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "e").kind.clone() else {
+            panic!("expected relay_sequence handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("empty sequence");
+        assert!(error.pretty().contains("chords must not be empty"));
+    }
+
+    #[test]
+    fn type_text_actions_capture_text_pacing_and_target() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("s", "signature", a.type_text("Regards,\nAlex"))
+    menu:bind("d", "slow", function(c)
+        c:type_text_to_app("Notes", "héllo", { delay_ms = 15 })
+    end)
+    menu:bind("x", "bad", function(c)
+        c:type_text("oops", { interval_ms = 1 })
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load config");
+        let style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &style).expect("render config");
+
+        for (chord, expected) in [
+            (
+                "s",
+                crate::TypeTextSpec {
+                    text: "Regards,\nAlex".to_string(),
+                    delay_ms: None,
+                    target: crate::RelayTarget::Focused,
+                },
+            ),
+            (
+                "d",
+                crate::TypeTextSpec {
+                    text: "héllo".to_string(),
+                    delay_ms: Some(15),
+                    target: crate::RelayTarget::ApplicationName("Notes".to_string()),
+                },
+            ),
+        ] {
+            let BindingKind::Handler(handler) = find_binding(&out.rendered, chord).kind.clone()
+            else {
+                panic!("expected type_text handler");
+            };
+            let result = execute_handler(&mut cfg, &handler, &ctx).expect("run type_text");
+            assert!(
+                matches!(&result.effects[0], Effect::Exec(Action::TypeText(spec)) if spec == &expected),
+                "unexpected effects: {:?}",
+                result.effects
+            );
+        }
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "x").kind.clone() else {
+            panic!("expected type_text handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("unknown option");
+        assert!(error.pretty().contains("interval_ms"));
+    }
+
     #[test]
     fn renderer_helpers_are_ordered_contextual_and_immutable() {
         let source = r#"
@@ -970,6 +1174,84 @@ return r.combine(
         assert!(other.warnings.is_empty());
     }
 
+    #[test]
+    fn app_mode_layers_bindings_over_root_while_app_is_focused() {
+        let source = r#"
+return function(menu, ctx)
+    menu:bind("a", "root a", function(c) end)
+    menu:bind("b", "root b", function(c) end)
+    menu:app_mode("Figma", "Figma", function(layer, inner)
+        layer:bind("a", "layer a", function(c) end)
+        layer:submenu("l", "Layers", function(child, _)
+            child:bind("x", "inside", function(c) end)
+        end)
+    end, { badge = true })
+    menu:app_mode("Figma", "Shadowed", function(layer, inner)
+        layer:bind("z", "shadowed", function(c) end)
+    end)
+    menu:app_mode("Sketch", "Sketch", function(layer, inner)
+        layer:bind("s", "sketch", function(c) end)
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load app modes");
+        let style = cfg.base_style();
+        let mut stack = vec![root_frame(&cfg)];
+
+        let figma = render_stack(&mut cfg, &mut stack, &base_ctx("Figma", false, 0), &style)
+            .expect("render Figma");
+        let descs: Vec<&str> = figma
+            .rendered
+            .bindings
+            .iter()
+            .map(|(_, binding)| binding.desc.as_str())
+            .collect();
+        assert_eq!(descs, ["layer a", "Layers", "root b"]);
+        assert_eq!(figma.rendered.badge.as_deref(), Some("Figma"));
+        assert!(figma.warnings.is_empty());
+
+        push_mode(&mut stack, find_binding(&figma.rendered, "l"));
+        let child = render_stack(&mut cfg, &mut stack, &base_ctx("Figma", true, 1), &style)
+            .expect("render layer submenu");
+        assert_eq!(stack.len(), 2, "layer submenu must not be orphaned");
+        assert_eq!(find_binding(&child.rendered, "x").desc, "inside");
+
+        let sketch = render_stack(&mut cfg, &mut stack, &base_ctx("Sketch", true, 1), &style)
+            .expect("render Sketch");
+        assert_eq!(stack.len(), 1, "leaving the app pops its layer submenu");
+        assert_eq!(find_binding(&sketch.rendered, "s").desc, "sketch");
+        assert_eq!(sketch.rendered.badge, None);
+
+        let other = render_stack(&mut cfg, &mut stack, &base_ctx("Safari", false, 0), &style)
+            .expect("render Safari");
+        assert_eq!(other.rendered.bindings.len(), 2);
+        assert_eq!(find_binding(&other.rendered, "a").desc, "root a");
+    }
+
+    #[test]
+    fn app_mode_is_rejected_outside_the_root_renderer() {
+        let source = r#"
+return function(menu, ctx)
+    menu:submenu("m", "Menu", function(child, _)
+        child:app_mode("Figma", "Figma", function(layer, _) end)
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load config");
+        let style = cfg.base_style();
+        let mut stack = vec![root_frame(&cfg)];
+        let root = render_stack(&mut cfg, &mut stack, &base_ctx("Finder", true, 0), &style)
+            .expect("render root");
+        push_mode(&mut stack, find_binding(&root.rendered, "m"));
+        let error = render_stack(&mut cfg, &mut stack, &base_ctx("Finder", true, 1), &style)
+            .expect_err("nested app_mode rejected");
+        assert!(
+            error
+                .pretty()
+                .contains("menu:app_mode is only available in the root menu")
+        );
+    }
+
     #[test]
     fn window_context_exposes_one_snapshot_and_targets_exec_by_id() {
         let source = r#"
@@ -1034,6 +1316,8 @@ end
             window: None,
             hud: false,
             depth: 0,
+            recent_windows: Vec::new(),
+            clipboard_history: Vec::new(),
         };
         let mut stack = vec![root_frame(&cfg)];
         let out = render_stack(&mut cfg, &mut stack, &ctx, &style).expect("render nil config");
@@ -1588,6 +1872,264 @@ end
         assert_handler_execs(&mut cfg, &child.rendered, "1", &ctx, &Action::SetVolume(50));
     }
 
+    #[test]
+    fn media_actions_parse_key_names() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("p", "play", a.media("play_pause"))
+    menu:bind("b", "brighter", function(c)
+        c:media("brightness_up")
+    end)
+    menu:bind("x", "bad", a.media("PlayPause"))
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let root = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render root");
+        assert_handler_execs(
+            &mut cfg,
+            &root.rendered,
+            "p",
+            &ctx,
+            &Action::Media(crate::MediaKey::PlayPause),
+        );
+        assert_handler_execs(
+            &mut cfg,
+            &root.rendered,
+            "b",
+            &ctx,
+            &Action::Media(crate::MediaKey::BrightnessUp),
+        );
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "x").kind.clone() else {
+            panic!("expected media handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("unknown media key");
+        assert!(error.pretty().contains("PlayPause"));
+    }
+
+    #[test]
+    fn app_actions_capture_target_and_command() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("a", "activate", a.activate_app("com.apple.Music"))
+    menu:bind("t", "toggle", function(c)
+        c:toggle_app("Notes")
+    end)
+    menu:bind("q", "quit", a.quit_app("Slack"))
+    menu:bind("x", "bad", a.hide_app(""))
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let root = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render root");
+        for (key, target, command) in [
+            ("a", "com.apple.Music", crate::AppCommand::Activate),
+            ("t", "Notes", crate::AppCommand::Toggle),
+            ("q", "Slack", crate::AppCommand::Quit),
+        ] {
+            assert_handler_execs(
+                &mut cfg,
+                &root.rendered,
+                key,
+                &ctx,
+                &Action::App(crate::AppSpec {
+                    target: target.to_string(),
+                    command,
+                }),
+            );
+        }
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "x").kind.clone() else {
+            panic!("expected app handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("empty target");
+        assert!(error.pretty().contains("hide_app target must not be empty"));
+    }
+
+    #[test]
+    fn clipboard_callbacks_receive_text_and_queue_clipboard_writes() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("w", "wrap", a.with_clipboard(function(c, text)
+        if text == nil then
+            c:notify("warn", "Clipboard", "empty")
+        else
+            c:set_clipboard("`" .. text .. "`")
+        end
+    end))
+    menu:bind("p", "pipe", a.exec({ program = "pbcopy", stdin = "clipboard" }))
+    menu:bind("x", "bad", a.exec({ program = "pbcopy", stdin = "pasteboard" }))
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let root = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render root");
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "w").kind.clone() else {
+            panic!("expected clipboard handler");
+        };
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("queue clipboard callback");
+        let [Effect::WithClipboard(callback)] = result.effects.as_slice() else {
+            panic!("unexpected effects: {:?}", result.effects);
+        };
+        let wrapped = execute_clipboard_handler(&mut cfg, callback, &ctx, Some("x".to_string()))
+            .expect("run clipboard callback");
+        assert!(
+            matches!(
+                wrapped.effects.as_slice(),
+                [Effect::Exec(Action::SetClipboard(text))] if text == "`x`"
+            ),
+            "unexpected effects: {:?}",
+            wrapped.effects
+        );
+        let empty = execute_clipboard_handler(&mut cfg, callback, &ctx, None)
+            .expect("run clipboard callback without text");
+        assert!(matches!(
+            empty.effects.as_slice(),
+            [Effect::Notify { body, .. }] if body == "empty"
+        ));
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "p").kind.clone() else {
+            panic!("expected exec handler");
+        };
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("execute exec handler");
+        assert!(matches!(
+            result.effects.as_slice(),
+            [Effect::Exec(Action::Exec(spec))] if spec.stdin == Some(crate::ExecStdin::Clipboard)
+        ));
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "x").kind.clone() else {
+            panic!("expected exec handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("unknown stdin source");
+        assert!(error.pretty().contains("pasteboard"));
+    }
+
+    #[test]
+    fn exec_specs_carry_environment_stdin_and_timeout() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("e", "env", a.exec({
+        program = "/usr/bin/env",
+        env = { LANG = "C" },
+        clear_env = true,
+        stdin = { text = "clipboard" },
+        timeout_ms = 250,
+    }))
+    menu:bind("x", "bad", a.exec({ program = "/usr/bin/env", env = { ["A=B"] = "1" } }))
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let root = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render root");
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "e").kind.clone() else {
+            panic!("expected exec handler");
+        };
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("execute exec handler");
+        let [Effect::Exec(Action::Exec(spec))] = result.effects.as_slice() else {
+            panic!("unexpected effects: {:?}", result.effects);
+        };
+        assert_eq!(spec.env.get("LANG").map(String::as_str), Some("C"));
+        assert!(spec.clear_env);
+        assert_eq!(
+            spec.stdin,
+            Some(crate::ExecStdin::Text("clipboard".to_string()))
+        );
+        assert_eq!(spec.timeout_ms, Some(250));
+
+        let BindingKind::Handler(handler) = find_binding(&root.rendered, "x").kind.clone() else {
+            panic!("expected exec handler");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("invalid env name");
+        assert!(
+            error.pretty().contains("ctx:exec env name"),
+            "{}",
+            error.pretty()
+        );
+        assert_eq!(error.diagnostics()[0].code, "invalid_exec_spec");
+    }
+
+    #[test]
+    fn process_actions_carry_concurrency_policies() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("s", "shell", a.shell("./upload.sh", { concurrency = "ignore" }))
+    menu:bind("e", "exec", a.exec({ program = "/usr/local/bin/yabai", concurrency = "queue" }))
+    menu:bind("p", "plain", a.shell("true"))
+    menu:bind("x", "bad", a.shell("true", { concurrency = "serial" }))
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let root = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render root");
+
+        let mut policy = |key: &str| {
+            let BindingKind::Handler(handler) = find_binding(&root.rendered, key).kind.clone()
+            else {
+                panic!("expected handler for {key}");
+            };
+            let result = execute_handler(&mut cfg, &handler, &ctx);
+            result.map(|result| match result.effects.as_slice() {
+                [Effect::Exec(Action::Shell(spec))] => spec.concurrency(),
+                [Effect::Exec(Action::Exec(spec))] => spec.concurrency,
+                effects => panic!("unexpected effects: {effects:?}"),
+            })
+        };
+        assert_eq!(
+            policy("s").expect("shell"),
+            crate::ProcessConcurrency::Ignore
+        );
+        assert_eq!(policy("e").expect("exec"), crate::ProcessConcurrency::Queue);
+        assert_eq!(
+            policy("p").expect("plain"),
+            crate::ProcessConcurrency::Parallel
+        );
+        let error = policy("x").expect_err("unknown policy");
+        assert!(error.pretty().contains("serial"), "{}", error.pretty());
+    }
+
+    #[test]
+    fn cancel_processes_helper_and_method_queue_the_action() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("k", "helper", a.cancel_processes())
+    menu:bind("shift+k", "direct", function(actx) actx:cancel_processes() end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("TestApp", true, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render");
+        for key in ["k", "shift+k"] {
+            assert_handler_execs(&mut cfg, &out.rendered, key, &ctx, &Action::CancelProcesses);
+        }
+    }
+
     #[test]
     fn render_uses_resolved_base_style_without_row_overrides() {
         let source = r##"
@@ -1797,6 +2339,612 @@ end
         assert_heap_plateaus(baseline_heap, retained_heap);
     }
 
+    #[test]
+    fn recent_windows_feed_selectors_and_focus_actions() {
+        let source = r#"
+local a = hotki.actions
+
+return function(menu, ctx)
+    menu:bind("p", "Previous app", a.focus_previous_app())
+    menu:bind("w", "Windows", function(actx)
+        actx:select({
+            items = hotki.recent_windows,
+            on_select = function(select_ctx, item, query)
+                select_ctx:focus_window(item.data)
+            end,
+        })
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let base_style = cfg.base_style();
+        let mut ctx = base_ctx("Editor", false, 0);
+        let current = ctx.window.clone().expect("base window");
+        ctx.recent_windows = vec![
+            current,
+            hotki_protocol::FocusSnapshot {
+                id: 9,
+                app: "Browser".to_string(),
+                title: "Docs".to_string(),
+                pid: 51,
+                display_id: None,
+            },
+            hotki_protocol::FocusSnapshot {
+                id: 3,
+                app: "Terminal".to_string(),
+                title: String::new(),
+                pid: 52,
+                display_id: None,
+            },
+        ];
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render");
+        assert_handler_execs(
+            &mut cfg,
+            &out.rendered,
+            "p",
+            &ctx,
+            &Action::Focus(FocusTarget::PreviousApp),
+        );
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "w").kind.clone() else {
+            panic!("expected handler binding");
+        };
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("execute handler");
+        let Effect::Select(selector) = result.effects[0].clone() else {
+            panic!("expected selector effect: {:?}", result.effects);
+        };
+        let items = selector
+            .resolve_items(&mut cfg, &ctx)
+            .expect("resolve items");
+        let labels: Vec<_> = items
+            .iter()
+            .map(|item| (item.label.as_str(), item.sublabel.as_deref()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![("Docs", Some("Browser")), ("Terminal", Some("Terminal"))]
+        );
+
+        let result = execute_selector_handler(&mut cfg, &selector.on_select, &ctx, &items[0], "")
+            .expect("select window");
+        assert!(
+            matches!(
+                &result.effects[0],
+                Effect::Exec(Action::Focus(FocusTarget::Window { pid: 51, id: 9 }))
+            ),
+            "unexpected effects: {:?}",
+            result.effects
+        );
+    }
+
+    #[test]
+    fn clipboard_history_is_enabled_at_load_and_pastes_selected_entries() {
+        let source = r#"
+local a = hotki.actions
+
+hotki.enable_clipboard_history({ capacity = 10, exclude_apps = { "^1Password" } })
+
+return function(menu, ctx)
+    menu:bind("v", "History", a.clipboard_history())
+    menu:bind("x", "Late", function(actx)
+        hotki.enable_clipboard_history()
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        assert_eq!(
+            cfg.clipboard_history,
+            Some(crate::ClipboardHistoryConfig {
+                capacity: 10,
+                exclude_apps: vec!["^1Password".to_string()],
+                persist: false,
+            })
+        );
+        let base_style = cfg.base_style();
+        let mut ctx = base_ctx("Editor", false, 0);
+        ctx.clipboard_history = vec!["  \nfirst line\nsecond line".to_string(), "x".repeat(100)];
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render");
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "v").kind.clone() else {
+            panic!("expected handler binding");
+        };
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("execute handler");
+        let Effect::Select(selector) = result.effects[0].clone() else {
+            panic!("expected selector effect: {:?}", result.effects);
+        };
+        let items = selector
+            .resolve_items(&mut cfg, &ctx)
+            .expect("resolve items");
+        assert_eq!(items[0].label, "first line");
+        assert_eq!(items[0].sublabel.as_deref(), Some("3 lines"));
+        assert_eq!(items[1].label.chars().count(), 80);
+        assert!(items[1].label.ends_with('…'));
+        assert_eq!(items[1].sublabel, None);
+
+        let result = execute_selector_handler(&mut cfg, &selector.on_select, &ctx, &items[0], "")
+            .expect("select entry");
+        assert!(
+            matches!(
+                result.effects.as_slice(),
+                [
+                    Effect::Exec(Action::SetClipboard(text)),
+                    Effect::Exec(Action::Relay(_)),
+                ] if text == "  \nfirst line\nsecond line"
+            ),
+            "unexpected effects: {:?}",
+            result.effects
+        );
+
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "x").kind.clone() else {
+            panic!("expected handler binding");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("late enable");
+        assert!(
+            error
+                .pretty()
+                .contains("must be called while the config loads")
+        );
+    }
+
+    #[test]
+    fn services_are_declared_at_load_and_validated() {
+        let source = r#"
+hotki.services({ name = "sync", program = "/usr/local/bin/clipsync", args = { "--watch" } })
+hotki.services({ name = "poller", program = "poll", restart = "always", backoff_ms = 250 })
+
+return function(menu, ctx)
+    menu:bind("x", "Late", function(actx)
+        hotki.services({ name = "late", program = "true" })
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        assert_eq!(
+            cfg.services,
+            vec![
+                ServiceSpec {
+                    name: "sync".to_string(),
+                    program: "/usr/local/bin/clipsync".to_string(),
+                    args: vec!["--watch".to_string()],
+                    cwd: None,
+                    env: BTreeMap::new(),
+                    restart: ServiceRestart::OnFailure,
+                    backoff_ms: 1000,
+                },
+                ServiceSpec {
+                    name: "poller".to_string(),
+                    program: "poll".to_string(),
+                    args: Vec::new(),
+                    cwd: None,
+                    env: BTreeMap::new(),
+                    restart: ServiceRestart::Always,
+                    backoff_ms: 250,
+                },
+            ]
+        );
+
+        let base_style = cfg.base_style();
+        let ctx = base_ctx("Editor", false, 0);
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render");
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "x").kind.clone() else {
+            panic!("expected handler binding");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("late declaration");
+        assert!(
+            error
+                .pretty()
+                .contains("must be called while the config loads")
+        );
+
+        for (source, expected) in [
+            (
+                r#"
+hotki.services({ name = "a", program = "x" })
+hotki.services({ name = "a", program = "y" })
+return function(menu, ctx) end
+"#,
+                "already declared",
+            ),
+            (
+                r#"
+hotki.services({ name = "a", program = "x", backoff_ms = 0 })
+return function(menu, ctx) end
+"#,
+                "backoff_ms must be at least 1",
+            ),
+            (
+                r#"
+hotki.services({ name = "a", program = "x", restart = "sometimes" })
+return function(menu, ctx) end
+"#,
+                "sometimes",
+            ),
+        ] {
+            let err = match load_dynamic_config_from_string(source, None) {
+                Ok(_) => panic!("invalid service should fail"),
+                Err(err) => err,
+            };
+            let pretty = err.pretty();
+            assert!(pretty.contains(expected), "{pretty}");
+        }
+    }
+
+    #[test]
+    fn schedules_are_declared_at_load_and_run_as_handlers() {
+        let source = r#"
+local a = hotki.actions
+
+hotki.schedule("0 9 * * 1-5", a.notify("info", "Stand-up", "Time for stand-up"))
+hotki.schedule({ at = "17:30", missed = "coalesce" }, function(ctx)
+    ctx:shell("~/bin/end-of-day")
+end)
+
+return function(menu, ctx)
+    menu:bind("x", "Late", function(actx)
+        hotki.schedule("* * * * *", a.pop)
+    end)
+end
+"#;
+        let mut cfg = load_dynamic_config_from_string(source, None).expect("load cfg");
+        let schedules = cfg
+            .schedules
+            .iter()
+            .map(|scheduled| (scheduled.schedule.to_string(), scheduled.missed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            schedules,
+            vec![
+                ("0 9 * * 1-5".to_string(), MissedRuns::Skip),
+                ("at 17:30".to_string(), MissedRuns::Coalesce),
+            ]
+        );
+
+        let ctx = base_ctx("Editor", false, 0);
+        let handler = cfg.schedules[0].handler.clone();
+        let result = execute_handler(&mut cfg, &handler, &ctx).expect("execute schedule");
+        assert!(
+            matches!(
+                result.effects.as_slice(),
+                [Effect::Notify { title, .. }] if title == "Stand-up"
+            ),
+            "unexpected effects: {:?}",
+            result.effects
+        );
+
+        let base_style = cfg.base_style();
+        let mut stack = vec![root_frame(&cfg)];
+        let out = render_stack(&mut cfg, &mut stack, &ctx, &base_style).expect("render");
+        let BindingKind::Handler(handler) = find_binding(&out.rendered, "x").kind.clone() else {
+            panic!("expected handler binding");
+        };
+        let error = execute_handler(&mut cfg, &handler, &ctx).expect_err("late declaration");
+        assert!(
+            error
+                .pretty()
+                .contains("must be called while the config loads")
+        );
+
+        for (source, expected) in [
+            (
+                r#"hotki.schedule("0 9 * *", hotki.actions.pop)"#,
+                "must have 5 fields",
+            ),
+            (
+                r#"hotki.schedule({ cron = "0 9 * * *", at = "09:00" }, hotki.actions.pop)"#,
+                "exactly one of cron or at",
+            ),
+            (
+                r#"hotki.schedule({ at = "9am" }, hotki.actions.pop)"#,
+                "24-hour HH:MM",
+            ),
+        ] {
+            let source = format!("{source}\nreturn function(menu, ctx) end\n");
+            let err = match load_dynamic_config_from_string(&source, None) {
+                Ok(_) => panic!("invalid schedule should fail"),
+                Err(err) => err,
+            };
+            let pretty = err.pretty();
+            assert!(pretty.contains(expected), "{pretty}");
+        }
+    }
+
+    #[test]
+    fn spec_files_drive_the_config_with_fake_windows() {
+        let root = test_dir("spec-files");
+        let config = root.join("config.luau");
+        fs::write(
+            &config,
+            r#"
+local a = hotki.actions
+return function(menu, ctx)
+    local window = ctx.window
+    if window ~= nil and window.app == "Safari" then
+        menu:bind("r", "reload", a.relay("cmd+r"))
+    end
+    menu:submenu("w", "windows", function(child)
+        child:bind("h", "hello", a.shell("echo hi"))
+        child:bind("n", "note", function(actx)
+            actx:notify("info", "Note", "body")
+            actx:stay()
+        end)
+    end)
+end
+"#,
+        )
+        .expect("write config");
+        fs::create_dir_all(root.join("specs")).expect("create spec dir");
+        fs::create_dir_all(root.join(".hidden")).expect("create hidden dir");
+        fs::write(root.join(".hidden/skip.spec.luau"), "error('hidden')").expect("write hidden");
+        let spec = root.join("specs/menu.spec.luau");
+        fs::write(
+            &spec,
+            r#"
+hotki.test("safari adds reload", function(t)
+    assert(t:row("r") == nil)
+    t:focus({ app = "Safari", title = "Docs" })
+    assert(t:row("r").desc == "reload")
+    local effects = t:press("r")
+    assert(effects[1].kind == "relay")
+end)
+
+hotki.test("submenu effects", function(t)
+    assert(t:press("w")[1] == nil)
+    assert(t.depth == 1)
+    assert(#t:rows() == 2)
+    local note = t:press("n")
+    assert(note[1].kind == "notify" and note[1].title == "Note")
+    assert(t.depth == 1)
+    local effects = t:press("h")
+    assert(effects[1].kind == "shell" and effects[1].spec == "echo hi")
+    assert(t.depth == 0)
+end)
+
+hotki.test("unbound chord fails", function(t)
+    t:press("z")
+end)
+"#,
+        )
+        .expect("write spec");
+
+        assert_eq!(
+            discover_specs(&config).expect("discover"),
+            vec![spec.clone()]
+        );
+        let cases = run_spec_file(&config, &spec).expect("run spec");
+        let names = cases
+            .iter()
+            .map(|case| case.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "safari adds reload",
+                "submenu effects",
+                "unbound chord fails"
+            ]
+        );
+        assert!(cases[0].failure.is_none(), "{:?}", cases[0].failure);
+        assert!(cases[1].failure.is_none(), "{:?}", cases[1].failure);
+        let failure = cases[2].failure.as_deref().expect("unbound chord failure");
+        assert!(failure.contains("no binding for z"), "{failure}");
+
+        let err = load_dynamic_config_from_string(
+            "hotki.test('x', function() end)\nreturn function(menu, ctx) end\n",
+            None,
+        )
+        .err()
+        .expect("hotki.test outside specs should fail");
+        assert!(err.pretty().contains("only available in spec files"));
+    }
+
+    #[test]
+    fn explain_reports_matches_inherited_globals_and_render_conditions() {
+        let root = test_dir("explain");
+        let config = root.join("config.luau");
+        fs::write(
+            &config,
+            r#"
+local a = hotki.actions
+local r = hotki.renderers
+return function(menu, ctx)
+    menu:bind("escape", "back", a.pop, { global = true, hidden = true })
+    menu:bind("q", "quit", a.pop, { global = true })
+    r.when_app("Safari", function(m)
+        m:bind("r", "reload", a.relay("cmd+r"))
+    end)(menu, ctx)
+    menu:submenu("w", "windows", function(child)
+        child:bind("q", "quarter", a.shell("echo q"))
+    end, { capture = true })
+    menu:submenu("m", "move / resize", function(child)
+        child:bind("c", "center", a.pop)
+    end)
+end
+"#,
+        )
+        .expect("write config");
+        let root_r = explain_chord(&config, None, &[], "r").expect("explain");
+        assert!(root_r.matched.is_none());
+        assert!(!root_r.capture);
+        let conditions = root_r
+            .conditions
+            .iter()
+            .map(|condition| (condition.mode.as_str(), condition.condition.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(conditions, [("root", "no focused window")]);
+
+        let window = Some(hotki_protocol::FocusSnapshot {
+            id: 1,
+            app: "Safari".to_string(),
+            title: String::new(),
+            pid: 0,
+            display_id: None,
+        });
+        let reload = explain_chord(&config, window, &[], "r").expect("explain");
+        let matched = reload.matched.expect("reload binding");
+        assert_eq!(matched.desc, "reload");
+        assert!(matched.source.is_some());
+        assert_eq!(reload.conditions.len(), 1);
+        assert_eq!(reload.conditions[0].condition, "window.app is \"Safari\"");
+
+        let slash = ["move / resize".to_string()];
+        let center = explain_chord(&config, None, &slash, "c").expect("explain");
+        assert_eq!(center.matched.expect("child binding").desc, "center");
+
+        let path = ["windows".to_string()];
+        let quarter = explain_chord(&config, None, &path, "q").expect("explain");
+        assert_eq!(quarter.mode, ["root", "windows"]);
+        assert!(quarter.capture);
+        let matched = quarter.matched.expect("child binding");
+        assert_eq!(
+            (matched.desc.as_str(), matched.mode.as_str()),
+            ("quarter", "windows")
+        );
+        let inherited = quarter
+            .inherited
+            .iter()
+            .map(|binding| (binding.desc.as_str(), binding.shadowed))
+            .collect::<Vec<_>>();
+        assert_eq!(inherited, [("back", false), ("quit", true)]);
+
+        let escape = explain_chord(&config, None, &path, "escape").expect("explain");
+        let matched = escape.matched.expect("inherited global");
+        assert_eq!(
+            (matched.desc.as_str(), matched.mode.as_str()),
+            ("back", "root")
+        );
+
+        let err = explain_chord(&config, None, &["missing".to_string()], "q")
+            .expect_err("unknown submenu");
+        assert!(err.pretty().contains("no submenu titled 'missing'"));
+        let err = explain_chord(&config, None, &[], "cmd+nope").expect_err("invalid chord");
+        assert!(err.pretty().contains("invalid chord 'cmd+nope'"));
+    }
+
+    #[test]
+    fn repl_evaluates_against_config_and_reports_effects() {
+        let root = test_dir("repl");
+        let config = root.join("config.luau");
+        fs::write(
+            &config,
+            r#"
+local a = hotki.actions
+return function(menu, ctx)
+    menu:submenu("w", "windows", function(child)
+        child:bind("h", "hello", a.shell("echo hi"))
+    end)
+end
+"#,
+        )
+        .expect("write config");
+        fs::write(
+            root.join("helpers.luau"),
+            "return { double = function(n: number): number return n * 2 end }\n",
+        )
+        .expect("write helpers");
+
+        let mut repl = ReplSession::new(&config).expect("start repl");
+        assert!(repl.eval("").expect("empty input").is_empty());
+        assert_eq!(
+            repl.eval("require(\"./helpers\").double(21)")
+                .expect("call module export"),
+            ["42"]
+        );
+        assert_eq!(repl.eval("ctx.depth").expect("read ctx"), ["0"]);
+        assert_eq!(repl.eval("#t:press(\"w\")").expect("enter submenu"), ["0"]);
+        assert_eq!(repl.eval("t.depth").expect("stack persists"), ["1"]);
+        let effects = repl.eval("t:press(\"h\")[1].kind").expect("queued effect");
+        assert_eq!(effects, ["\"shell\""]);
+        assert!(
+            repl.eval("local x = 1")
+                .expect("statement input")
+                .is_empty()
+        );
+
+        let err = repl.eval("ctx.depth + \"x\"").expect_err("type error");
+        assert!(err.pretty().contains("repl.luau"), "{}", err.pretty());
+        let err = repl
+            .eval("t:press(\"h\")[2].kind")
+            .expect_err("runtime error");
+        assert!(
+            err.pretty().contains("attempt to index nil"),
+            "{}",
+            err.pretty()
+        );
+    }
+
+    #[test]
+    fn lint_reports_best_practice_findings_with_allow_comments() {
+        let root = test_dir("lint");
+        let config = root.join("config.luau");
+        fs::write(
+            &config,
+            r#"
+local a = hotki.actions
+return function(menu, ctx)
+    menu:bind("o", "open finder", a.shell("open -a Finder"))
+    menu:bind("p", "count", a.shell("ls | wc -l"))
+    menu:bind("t", "tool", a.exec({ program = "rg" }))
+    -- hotki-lint: allow(exec_relative_program)
+    menu:bind("shift+t", "allowed tool", a.exec({ program = "rg" }))
+    menu:bind("l", "list", a.exec({ program = "ls" }))
+    menu:bind("r", "reload", function(c)
+        local window = c.window
+        if window ~= nil then
+            c:relay_to_app(window.app, "cmd+r")
+        end
+    end)
+    menu:bind("h", "help", a.exit)
+    menu:submenu("h", "help menu", function(child) child:bind("x", "x", a.pop) end)
+    menu:bind("q", "quit", a.exit)
+    menu:bind("escape", "quit", a.exit)
+    menu:submenu("e", "empty", function(_child) end)
+    menu:submenu("1", "one", function(one)
+        one:submenu("2", "two", function(two)
+            two:submenu("3", "three", function(three)
+                three:submenu("4", "four", function(four)
+                    four:submenu("5", "five", function(five)
+                        five:bind("x", "deep", a.pop)
+                    end)
+                end)
+            end)
+        end)
+    end)
+end
+"#,
+        )
+        .expect("write config");
+
+        let findings = lint_config(&config).expect("lint config");
+        let kinds = findings
+            .iter()
+            .map(|finding| {
+                let line = finding.location.as_ref().and_then(|pos| pos.line);
+                (finding.kind, line)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                (LintKind::ShellCouldBeExec, Some(4)),
+                (LintKind::ExecRelativeProgram, Some(6)),
+                (LintKind::RelayToWindowApp, Some(10)),
+                (LintKind::UnreachableSubmenu, Some(17)),
+                (LintKind::DuplicateDescription, Some(19)),
+                (LintKind::EmptyMenu, Some(20)),
+                (LintKind::DeepNesting, Some(25)),
+            ]
+        );
+        let diagnostic = findings[1].to_diagnostic();
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.code, "exec_relative_program");
+        assert_eq!(findings[3].to_diagnostic().severity, Severity::Error);
+        assert_eq!(findings[6].mode, ["root", "one", "two", "three", "four"]);
+        assert!(findings[3].to_string().contains("\"help\" takes 'h' first"));
+    }
+
     fn assert_handler_execs(
         cfg: &mut LoadedConfig,
         rendered: &RenderedState,
@@ -10,12 +10,13 @@ use std::{
 pub use hotki_protocol::HudRow;
 use mac_keycode::Chord;
 use ruau::vm::{Function, RuntimeError, Scope, SourceLocation};
+use serde::Serialize;
 
 use super::{SelectorConfig, callback::CallbackRef, util::lock_unpoisoned};
-use crate::{Action, NotifyKind, Style};
+use crate::{Action, MissedRuns, NotifyKind, Schedule, Style};
 
 /// Source location attached to a binding for diagnostics.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct SourcePos {
     /// Source file for the binding, when known.
     pub path: Option<PathBuf>,
@@ -91,6 +92,19 @@ impl ModeRef {
     }
 }
 
+/// Automatic mode layer declared by the root renderer for one application.
+#[derive(Debug, Clone)]
+pub struct AppMode {
+    /// Exact application name that activates the layer.
+    pub(crate) app: String,
+    /// Title shown by the HUD badge while the layer is active.
+    pub(crate) title: String,
+    /// Mode renderer layered over the root while the application is focused.
+    pub(crate) mode: ModeRef,
+    /// Whether the UI should show a badge while the layer is active.
+    pub(crate) badge: bool,
+}
+
 /// Opaque wrapper around a Luau handler closure.
 #[derive(Clone)]
 pub struct HandlerRef {
@@ -116,6 +130,17 @@ impl HandlerRef {
     }
 }
 
+/// Action declared by `hotki.schedule` and run by the engine on a wall-clock schedule.
+#[derive(Debug, Clone)]
+pub struct ScheduledAction {
+    /// When the action runs.
+    pub schedule: Schedule,
+    /// What happens to runs missed while the machine slept.
+    pub missed: MissedRuns,
+    /// Luau action invoked with a fresh action context.
+    pub handler: HandlerRef,
+}
+
 impl SourcePos {
     /// Build a source position from ruau's caller-location metadata.
     pub(crate) fn from_location(location: SourceLocation) -> Self {
@@ -137,6 +162,10 @@ pub struct ModeCtx {
     pub hud: bool,
     /// Current stack depth (root = 0).
     pub depth: i64,
+    /// Engine focus history, most recently focused window first.
+    pub recent_windows: Vec<hotki_protocol::FocusSnapshot>,
+    /// Engine clipboard history, most recently copied text first.
+    pub clipboard_history: Vec<String>,
 }
 
 /// Effect emitted by handlers.
@@ -164,6 +193,8 @@ pub enum Effect {
         /// Repeat timing overrides.
         repeat: Option<RepeatSpec>,
     },
+    /// Read the clipboard and run a stashed callback with its plain-text contents.
+    WithClipboard(HandlerRef),
 }
 
 /// Navigation request emitted by handlers or primitive actions.
@@ -208,6 +239,8 @@ struct ActionCtxShared {
     active: bool,
     /// Whether `ctx:until_keyup` was already requested during this activation.
     until_keyup: bool,
+    /// Why `ctx:exec` rejected a spec during this activation, if it did.
+    exec_rejection: Option<String>,
 }
 
 /// Whether a handler context may create a held-key repeat loop.
@@ -296,6 +329,17 @@ impl ActionCtx {
         Ok(())
     }
 
+    /// Record that `ctx:exec` rejected a spec, so the handler failure can be told apart from
+    /// other errors.
+    pub(crate) fn reject_exec(&self, reason: String) {
+        lock_unpoisoned(&self.shared).exec_rejection = Some(reason);
+    }
+
+    /// Why `ctx:exec` rejected a spec during this activation, if it did.
+    pub(crate) fn exec_rejection(&self) -> Option<String> {
+        lock_unpoisoned(&self.shared).exec_rejection.clone()
+    }
+
     /// Finish this context, invalidate it, and drain the queued effects.
     pub(crate) fn finish(&self) -> (Vec<Effect>, bool) {
         let mut shared = lock_unpoisoned(&self.shared);
@@ -401,4 +445,6 @@ pub struct RenderedState {
     pub style: Style,
     /// True when capture-all mode is active in the current frame.
     pub capture: bool,
+    /// Badge title for the active application mode, when it requested one.
+    pub badge: Option<String>,
 }
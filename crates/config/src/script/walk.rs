@@ -0,0 +1,276 @@
+//! Depth-first traversal of every submenu reachable from the root mode.
+//!
+//! Each mode is rendered for one synthetic context before duplicate chords are dropped, with the
+//! focused application's `app_mode` layered over the root, so a [`ModeVisitor`] sees every
+//! declaration site alongside the bindings dispatch would keep. Submenus are entered in
+//! declaration order; one already open further up the path, or nested deeper than
+//! [`MAX_DEPTH`], is reported to the visitor instead of being entered again.
+
+use std::collections::HashSet;
+
+use hotki_protocol::FocusSnapshot;
+
+use super::{
+    Binding, BindingKind, LoadedConfig, ModeCtx, ModeFrame, render::render_mode_output,
+    types::ModeId,
+};
+use crate::Error;
+
+/// Submenu nesting beyond which the walk stops descending.
+pub(crate) const MAX_DEPTH: usize = 32;
+
+/// One rendered mode handed to a [`ModeVisitor`].
+pub(crate) struct ModeVisit<'a> {
+    /// Mode titles from the root to this mode.
+    pub(crate) trail: &'a [String],
+    /// Context the mode was rendered for.
+    pub(crate) ctx: &'a ModeCtx,
+    /// Binding that opened the mode; `None` for the root.
+    pub(crate) entry: Option<&'a Binding>,
+    /// Whether the mode requested capture-all.
+    pub(crate) capture: bool,
+    /// Every binding the mode declared, in declaration order.
+    pub(crate) declared: &'a [Binding],
+    /// The first binding declared for each chord, which dispatch keeps.
+    pub(crate) own: &'a [&'a Binding],
+    /// Later declarations dropped for a chord, each paired with the binding that kept it.
+    pub(crate) dropped: &'a [(&'a Binding, &'a Binding)],
+    /// `global` bindings inherited from ancestors, nearest ancestor first, one per chord.
+    pub(crate) inherited: &'a [Binding],
+}
+
+impl ModeVisit<'_> {
+    /// Submenu nesting of the mode; the root is at depth 0.
+    pub(crate) fn depth(&self) -> usize {
+        self.trail.len() - 1
+    }
+
+    /// Bindings active in the mode in dispatch order: its own, then unshadowed inherited globals.
+    pub(crate) fn active(&self) -> impl Iterator<Item = &Binding> {
+        let own = self.own;
+        let inherited = self
+            .inherited
+            .iter()
+            .filter(move |global| !own.iter().any(|binding| binding.chord == global.chord));
+        own.iter().copied().chain(inherited)
+    }
+}
+
+/// Callbacks invoked by [`walk_modes`].
+pub(crate) trait ModeVisitor {
+    /// Inspect a rendered mode before its submenus are walked.
+    fn enter(&mut self, cfg: &mut LoadedConfig, mode: &ModeVisit<'_>);
+
+    /// Called once every submenu of the mode opened by `entry` has been walked.
+    fn leave(&mut self, _entry: Option<&Binding>) {}
+
+    /// Called for a submenu of the current mode that is not entered again.
+    fn recursive(&mut self, _binding: &Binding) {}
+
+    /// Decide what a failed render means; returning the error stops the walk.
+    fn failed(&mut self, frame: &ModeFrame, err: Error) -> Result<(), Error>;
+}
+
+/// Walk the root and every reachable submenu of `cfg` for a synthetic focused `window`.
+pub(crate) fn walk_modes(
+    cfg: &mut LoadedConfig,
+    window: Option<FocusSnapshot>,
+    visitor: &mut impl ModeVisitor,
+) -> Result<(), Error> {
+    let root = root_frame(cfg);
+    let mut walk = Walk {
+        cfg,
+        window,
+        path: Vec::new(),
+        trail: Vec::new(),
+        visitor,
+    };
+    walk.mode(&root, None, &[])
+}
+
+/// Applications declared with `app_mode` by the root, in declaration order without repeats.
+pub(crate) fn app_mode_apps(cfg: &mut LoadedConfig) -> Vec<String> {
+    let root = root_frame(cfg);
+    let mut apps: Vec<String> = Vec::new();
+    if let Ok(output) = render_mode_output(cfg, &root, &context(None, 0), true) {
+        for app_mode in output.app_modes {
+            if !apps.contains(&app_mode.app) {
+                apps.push(app_mode.app);
+            }
+        }
+    }
+    apps
+}
+
+/// Synthetic focused window for `app`.
+pub(crate) fn synthetic_window(app: String) -> FocusSnapshot {
+    FocusSnapshot {
+        id: 0,
+        app,
+        title: String::new(),
+        pid: 0,
+        display_id: None,
+    }
+}
+
+/// Render context for a synthetic window at `depth`; the HUD counts as visible inside a submenu.
+pub(crate) fn context(window: Option<FocusSnapshot>, depth: usize) -> ModeCtx {
+    ModeCtx {
+        window,
+        hud: depth > 0,
+        depth: depth as i64,
+        recent_windows: Vec::new(),
+        clipboard_history: Vec::new(),
+    }
+}
+
+/// Depth-first walk state.
+struct Walk<'a, V> {
+    /// Config being walked.
+    cfg: &'a mut LoadedConfig,
+    /// Synthetic focused window shared by every mode.
+    window: Option<FocusSnapshot>,
+    /// Identities of the submenus open above the current mode.
+    path: Vec<ModeId>,
+    /// Titles of the modes from the root to the current mode.
+    trail: Vec<String>,
+    /// Callbacks receiving each mode.
+    visitor: &'a mut V,
+}
+
+impl<V: ModeVisitor> Walk<'_, V> {
+    /// Render one mode, hand it to the visitor, and recurse into its submenus.
+    fn mode(
+        &mut self,
+        frame: &ModeFrame,
+        entry: Option<&Binding>,
+        inherited: &[Binding],
+    ) -> Result<(), Error> {
+        let depth = self.trail.len();
+        let ctx = context(self.window.clone(), depth);
+        let (declared, capture) = match render_layered(self.cfg, frame, &ctx, depth == 0) {
+            Ok(output) => output,
+            Err(err) => return self.visitor.failed(frame, err),
+        };
+        let mut own: Vec<&Binding> = Vec::new();
+        let mut dropped = Vec::new();
+        for binding in &declared {
+            if let Some(first) = own.iter().find(|first| first.chord == binding.chord) {
+                dropped.push((binding, *first));
+                continue;
+            }
+            own.push(binding);
+        }
+
+        self.trail.push(frame.title.clone());
+        let visit = ModeVisit {
+            trail: &self.trail,
+            ctx: &ctx,
+            entry,
+            capture,
+            declared: &declared,
+            own: &own,
+            dropped: &dropped,
+            inherited,
+        };
+        self.visitor.enter(self.cfg, &visit);
+        let result = self.submenus(&own, &child_inherited(&own, inherited), depth);
+        self.visitor.leave(entry);
+        self.trail.pop();
+        result
+    }
+
+    /// Enter each submenu binding of a mode at `depth`.
+    fn submenus(
+        &mut self,
+        own: &[&Binding],
+        inherited: &[Binding],
+        depth: usize,
+    ) -> Result<(), Error> {
+        for &binding in own {
+            let BindingKind::Mode(mode) = &binding.kind else {
+                continue;
+            };
+            let id = binding.mode_id;
+            if id.is_some_and(|id| self.path.contains(&id)) || depth >= MAX_DEPTH {
+                self.visitor.recursive(binding);
+                continue;
+            }
+            let child = ModeFrame {
+                title: binding.desc.clone(),
+                closure: mode.clone(),
+                entered_via: id.map(|id| (binding.chord.clone(), id)),
+                rendered: Vec::new(),
+                capture: binding.mode_capture,
+            };
+            self.path.extend(id);
+            let result = self.mode(&child, Some(binding), inherited);
+            if id.is_some() {
+                self.path.pop();
+            }
+            result?;
+        }
+        Ok(())
+    }
+}
+
+/// Globals a child of a mode inherits: the mode's own, then its inherited ones, one per chord.
+fn child_inherited(own: &[&Binding], inherited: &[Binding]) -> Vec<Binding> {
+    let mut globals: Vec<Binding> = own
+        .iter()
+        .filter(|binding| binding.flags.global)
+        .map(|binding| (*binding).clone())
+        .collect();
+    for global in inherited {
+        if !globals.iter().any(|binding| binding.chord == global.chord) {
+            globals.push(global.clone());
+        }
+    }
+    globals
+}
+
+/// Render one frame before duplicate chords are dropped, layering the `app_mode` declared for
+/// the focused application over the root, and report whether it requested capture-all.
+fn render_layered(
+    cfg: &mut LoadedConfig,
+    frame: &ModeFrame,
+    ctx: &ModeCtx,
+    root: bool,
+) -> Result<(Vec<Binding>, bool), Error> {
+    let output = render_mode_output(cfg, frame, ctx, root)?;
+    let app = ctx.window.as_ref().map(|window| window.app.as_str());
+    let Some(app_mode) = output
+        .app_modes
+        .iter()
+        .find(|app_mode| Some(app_mode.app.as_str()) == app)
+    else {
+        return Ok((output.bindings, output.capture));
+    };
+    let layer = ModeFrame {
+        title: app_mode.title.clone(),
+        closure: app_mode.mode.clone(),
+        entered_via: None,
+        rendered: Vec::new(),
+        capture: false,
+    };
+    let mut bindings = render_mode_output(cfg, &layer, ctx, false)?.bindings;
+    let chords: HashSet<String> = bindings.iter().map(|b| b.chord.to_string()).collect();
+    bindings.extend(
+        output
+            .bindings
+            .into_iter()
+            .filter(|binding| !chords.contains(&binding.chord.to_string())),
+    );
+    Ok((bindings, output.capture))
+}
+
+/// Root frame for a loaded config.
+fn root_frame(cfg: &LoadedConfig) -> ModeFrame {
+    ModeFrame {
+        title: "root".to_string(),
+        closure: cfg.root(),
+        entered_via: None,
+        rendered: Vec::new(),
+        capture: false,
+    }
+}
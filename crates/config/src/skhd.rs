@@ -0,0 +1,690 @@
+//! Translation of skhd configuration files into Hotki Luau for `hotki import skhd`.
+//!
+//! Each skhd mode becomes a renderer pushed with `menu:submenu`; bindings inside a mode keep the
+//! mode active, as they do in skhd, and `; default` becomes `a.exit`. Application blocks become
+//! `hotki.renderers` conditions. Constructs without a Hotki equivalent are kept in the output as
+//! comments and listed in [`SkhdImport::unsupported`].
+
+use std::fmt::Write;
+
+use mac_keycode::Chord;
+
+/// Name skhd gives the mode active when no other mode is.
+const DEFAULT_MODE: &str = "default";
+
+/// Longest binding description taken from a command before it is shortened.
+const MAX_DESC_CHARS: usize = 60;
+
+/// Result of translating one skhd configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkhdImport {
+    /// Generated `config.luau` source.
+    pub config: String,
+    /// Constructs that were not translated, as `line N: reason`, in source order.
+    pub unsupported: Vec<String>,
+}
+
+/// Translate skhdrc source into an equivalent Hotki config.
+pub fn import_skhd(source: &str) -> SkhdImport {
+    let mut import = Import::default();
+    for statement in statements(source) {
+        import.add(&statement);
+    }
+    import.finish()
+}
+
+/// One logical skhd statement after joining continuation lines and application blocks.
+struct Statement {
+    /// 1-based line the statement starts on.
+    line: usize,
+    /// Statement text; application block entries are separated by newlines.
+    text: String,
+}
+
+/// One skhd mode and the bindings declared in it.
+struct Mode {
+    /// skhd mode name.
+    name: String,
+    /// Whether the mode captures unbound keys (`@`).
+    capture: bool,
+    /// Whether a `::` declaration was seen.
+    declared: bool,
+    /// Bindings and notes in source order.
+    entries: Vec<Entry>,
+}
+
+/// One translated item inside a mode renderer.
+enum Entry {
+    /// A chord running a shell command.
+    Command {
+        /// Canonical Hotki chord.
+        chord: String,
+        /// Shell command.
+        command: String,
+    },
+    /// A chord switching to another mode.
+    Switch {
+        /// Canonical Hotki chord.
+        chord: String,
+        /// Target mode name.
+        target: String,
+    },
+    /// A chord whose command depends on the focused application.
+    Apps {
+        /// Canonical Hotki chord.
+        chord: String,
+        /// Application names and their commands.
+        arms: Vec<(String, String)>,
+        /// Command for every other application (`*`).
+        fallback: Option<String>,
+        /// Applications where the chord is left unbound (`~`).
+        excluded: Vec<String>,
+    },
+    /// A comment recording something that was not translated.
+    Note(String),
+}
+
+/// What a hotkey statement does.
+enum HotkeyAction {
+    /// `: command`.
+    Command(String),
+    /// `; mode`.
+    Switch(String),
+    /// `[ "app" : command ... ]`.
+    Apps(String),
+}
+
+/// Import state accumulated across statements.
+#[derive(Default)]
+struct Import {
+    /// Modes in order of first mention; the default mode is created on demand.
+    modes: Vec<Mode>,
+    /// Notes for constructs that belong to no mode.
+    notes: Vec<String>,
+    /// Every untranslated construct, for [`SkhdImport::unsupported`].
+    unsupported: Vec<String>,
+}
+
+impl Import {
+    /// Translate one statement.
+    fn add(&mut self, statement: &Statement) {
+        let text = statement.text.as_str();
+        if let Some(decl) = text.strip_prefix("::") {
+            self.declare_mode(statement.line, decl);
+        } else if text.starts_with('.') {
+            let directive = text.split_whitespace().next().unwrap_or(text);
+            let note = self.unsupported(
+                statement.line,
+                &format!("directive `{directive}` is not supported"),
+            );
+            self.notes.push(note);
+        } else {
+            self.add_hotkey(statement);
+        }
+    }
+
+    /// Record a `:: name [@] [: command]` declaration.
+    fn declare_mode(&mut self, line: usize, decl: &str) {
+        let (head, command) = match decl.split_once(':') {
+            Some((head, command)) => (head.trim(), Some(command.trim())),
+            None => (decl.trim(), None),
+        };
+        let capture = head.ends_with('@');
+        let name = head.trim_end_matches('@').trim();
+        if name.is_empty() {
+            let note = self.unsupported(line, "mode declaration without a name");
+            self.notes.push(note);
+            return;
+        }
+        let index = self.mode(name);
+        self.modes[index].declared = true;
+        self.modes[index].capture |= capture;
+        if let Some(command) = command.filter(|command| !command.is_empty()) {
+            let note = self.unsupported(
+                line,
+                &format!("command run on entering mode `{name}` is not supported: {command}"),
+            );
+            self.modes[index].entries.push(Entry::Note(note));
+        }
+    }
+
+    /// Translate a `[modes <] keys [->] (: command | ; mode | [ ... ])` statement.
+    fn add_hotkey(&mut self, statement: &Statement) {
+        let line = statement.line;
+        let text = statement.text.as_str();
+        let Some(split) = text.find([':', ';', '[']) else {
+            let note = self.unsupported(line, &format!("hotkey without a command: {text}"));
+            self.notes.push(note);
+            return;
+        };
+        let (head, tail) = text.split_at(split);
+        let (modes, keys) = match head.split_once('<') {
+            Some((modes, keys)) => (
+                modes
+                    .split(',')
+                    .map(str::trim)
+                    .map(str::to_string)
+                    .collect(),
+                keys,
+            ),
+            None => (vec![DEFAULT_MODE.to_string()], head),
+        };
+        let keys = keys.trim();
+        let (keys, passthrough) = match keys.strip_suffix("->") {
+            Some(keys) => (keys.trim(), true),
+            None => (keys, false),
+        };
+        let action = match tail.split_at(1) {
+            (":", command) => HotkeyAction::Command(command.trim().to_string()),
+            (";", mode) => HotkeyAction::Switch(mode.trim().to_string()),
+            (_, block) => HotkeyAction::Apps(block.trim_end().trim_end_matches(']').to_string()),
+        };
+        for mode in modes {
+            let index = self.mode(&mode);
+            let chord = match translate_chord(keys) {
+                Ok(chord) => chord,
+                Err(reason) => {
+                    let note = self.unsupported(line, &format!("{reason}: {}", first_line(text)));
+                    self.modes[index].entries.push(Entry::Note(note));
+                    continue;
+                }
+            };
+            if passthrough {
+                let note = self.unsupported(
+                    line,
+                    &format!("passthrough (`->`) is not supported; `{chord}` is consumed"),
+                );
+                self.modes[index].entries.push(Entry::Note(note));
+            }
+            let entry = self.entry(line, chord, &action);
+            self.modes[index].entries.push(entry);
+        }
+    }
+
+    /// Build the entry for one translated chord.
+    fn entry(&mut self, line: usize, chord: String, action: &HotkeyAction) -> Entry {
+        match action {
+            HotkeyAction::Command(command) => Entry::Command {
+                chord,
+                command: command.clone(),
+            },
+            HotkeyAction::Switch(target) => {
+                self.mode(target);
+                Entry::Switch {
+                    chord,
+                    target: target.clone(),
+                }
+            }
+            HotkeyAction::Apps(block) => {
+                let (mut arms, mut fallback, mut excluded) = (Vec::new(), None, Vec::new());
+                let mut notes = Vec::new();
+                for arm in block.lines().map(str::trim).filter(|arm| !arm.is_empty()) {
+                    match parse_arm(arm) {
+                        Some((None, Some(command))) => fallback = Some(command),
+                        Some((Some(app), Some(command))) => arms.push((app, command)),
+                        Some((Some(app), None)) => excluded.push(app),
+                        _ => notes.push(format!("unrecognized application entry: {arm}")),
+                    }
+                }
+                for note in notes {
+                    let note = self.unsupported(line, &note);
+                    self.notes.push(note);
+                }
+                Entry::Apps {
+                    chord,
+                    arms,
+                    fallback,
+                    excluded,
+                }
+            }
+        }
+    }
+
+    /// Index of the mode called `name`, creating it if needed.
+    fn mode(&mut self, name: &str) -> usize {
+        if let Some(index) = self.modes.iter().position(|mode| mode.name == name) {
+            return index;
+        }
+        self.modes.push(Mode {
+            name: name.to_string(),
+            capture: false,
+            declared: name == DEFAULT_MODE,
+            entries: Vec::new(),
+        });
+        self.modes.len() - 1
+    }
+
+    /// Record an untranslated construct and return its comment text.
+    fn unsupported(&mut self, line: usize, reason: &str) -> String {
+        let note = format!("line {line}: {reason}");
+        self.unsupported.push(note.clone());
+        note
+    }
+
+    /// Render the accumulated modes as a config.
+    fn finish(mut self) -> SkhdImport {
+        self.mode(DEFAULT_MODE);
+        let undeclared = self
+            .modes
+            .iter()
+            .filter(|mode| !mode.declared)
+            .map(|mode| mode.name.clone())
+            .collect::<Vec<_>>();
+        for name in undeclared {
+            let note = format!("mode `{name}` is used but never declared with `::`");
+            self.unsupported.push(note.clone());
+            self.notes.push(note);
+        }
+
+        let mut out = String::from("-- Generated by `hotki import skhd`.\n");
+        for note in &self.notes {
+            let _ignored = writeln!(out, "-- {}", comment(note));
+        }
+        out.push_str("\nlocal a = hotki.actions\n");
+        if self.uses_apps() {
+            out.push_str("local r = hotki.renderers\n");
+        }
+
+        let modes = self
+            .modes
+            .iter()
+            .filter(|mode| mode.name != DEFAULT_MODE)
+            .collect::<Vec<_>>();
+        if !modes.is_empty() {
+            out.push('\n');
+        }
+        for mode in &modes {
+            let _ignored = writeln!(out, "local {}: ModeRenderer", mode_local(&mode.name));
+        }
+        for mode in &modes {
+            let _ignored = write!(
+                out,
+                "\n{} = function(menu: MenuBuilder, ",
+                mode_local(&mode.name)
+            );
+            self.write_renderer(&mut out, mode);
+        }
+        out.push_str("\nreturn function(menu: MenuBuilder, ");
+        if let Some(root) = self.modes.iter().find(|mode| mode.name == DEFAULT_MODE) {
+            self.write_renderer(&mut out, root);
+        }
+        SkhdImport {
+            config: out,
+            unsupported: self.unsupported,
+        }
+    }
+
+    /// Whether any entry needs `hotki.renderers`.
+    fn uses_apps(&self) -> bool {
+        self.modes.iter().any(|mode| {
+            mode.entries
+                .iter()
+                .any(|entry| matches!(entry, Entry::Apps { arms, .. } if !arms.is_empty()))
+        })
+    }
+
+    /// Write the context parameter and body of one mode renderer.
+    fn write_renderer(&self, out: &mut String, mode: &Mode) {
+        let uses_ctx = mode
+            .entries
+            .iter()
+            .any(|entry| matches!(entry, Entry::Apps { .. }));
+        let ctx = if uses_ctx { "ctx" } else { "_ctx" };
+        let _ignored = writeln!(out, "{ctx}: ModeContext)");
+        let root = mode.name == DEFAULT_MODE;
+        let builder = if root { "menu" } else { "stay" };
+        if !root
+            && mode
+                .entries
+                .iter()
+                .any(|entry| !matches!(entry, Entry::Note(_)))
+        {
+            out.push_str("    local stay = menu:with({ stay = true })\n");
+        }
+        for entry in &mode.entries {
+            match entry {
+                Entry::Command { chord, command } => {
+                    let _ignored = writeln!(out, "    {}", bind(builder, chord, command));
+                }
+                Entry::Switch { chord, target } => self.write_switch(out, chord, target),
+                Entry::Apps {
+                    chord,
+                    arms,
+                    fallback,
+                    excluded,
+                } => write_apps(out, builder, chord, arms, fallback.as_deref(), excluded),
+                Entry::Note(note) => {
+                    let _ignored = writeln!(out, "    -- {}", comment(note));
+                }
+            }
+        }
+        out.push_str("end\n");
+    }
+
+    /// Write a mode switch: `default` exits, any other mode is pushed as a submenu.
+    fn write_switch(&self, out: &mut String, chord: &str, target: &str) {
+        if target == DEFAULT_MODE {
+            let _ignored = writeln!(
+                out,
+                "    menu:bind({}, {}, a.exit)",
+                luau_string(chord),
+                luau_string(target)
+            );
+            return;
+        }
+        let capture = self
+            .modes
+            .iter()
+            .any(|mode| mode.name == target && mode.capture);
+        let opts = if capture { ", { capture = true }" } else { "" };
+        let _ignored = writeln!(
+            out,
+            "    menu:submenu({}, {}, {}{opts})",
+            luau_string(chord),
+            luau_string(target),
+            mode_local(target)
+        );
+    }
+}
+
+/// Write a chord whose command depends on the focused application.
+///
+/// Application renderers run first because the first binding for a chord wins.
+fn write_apps(
+    out: &mut String,
+    builder: &str,
+    chord: &str,
+    arms: &[(String, String)],
+    fallback: Option<&str>,
+    excluded: &[String],
+) {
+    for (app, command) in arms {
+        let _ignored = writeln!(out, "    {}function(m)", when_app(app));
+        let _ignored = writeln!(out, "        {}", bind("m", chord, command));
+        let _ignored = writeln!(out, "    end)({builder}, ctx)");
+    }
+    let Some(command) = fallback else {
+        return;
+    };
+    if excluded.is_empty() {
+        let _ignored = writeln!(out, "    {}", bind(builder, chord, command));
+        return;
+    }
+    let matches = excluded
+        .iter()
+        .map(|app| app_condition(app))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    out.push_str("    do\n        local window = ctx.window\n");
+    let _ignored = writeln!(out, "        if window == nil or not ({matches}) then");
+    let _ignored = writeln!(out, "            {}", bind(builder, chord, command));
+    out.push_str("        end\n    end\n");
+}
+
+/// A `bind` call running `command` in a shell.
+fn bind(builder: &str, chord: &str, command: &str) -> String {
+    format!(
+        "{builder}:bind({}, {}, a.shell({}))",
+        luau_string(chord),
+        luau_string(&description(command)),
+        luau_string(command)
+    )
+}
+
+/// Renderer condition for an skhd application name.
+///
+/// skhd matches process names case-insensitively. Names written with capitals are taken as exact
+/// application names; lowercase names match any capitalization.
+fn when_app(app: &str) -> String {
+    if app.chars().any(char::is_uppercase) {
+        format!("r.when_app({}, ", luau_string(app))
+    } else {
+        format!("r.when_app_matches({}, ", luau_string(&app_pattern(app)))
+    }
+}
+
+/// Luau condition on a local `window` matching an skhd application name.
+fn app_condition(app: &str) -> String {
+    if app.chars().any(char::is_uppercase) {
+        format!("window.app == {}", luau_string(app))
+    } else {
+        format!("window:app_matches({})", luau_string(&app_pattern(app)))
+    }
+}
+
+/// Case-insensitive anchored pattern for an application name.
+fn app_pattern(app: &str) -> String {
+    format!("(?i)^{}$", regex::escape(app))
+}
+
+/// Parse one application block entry: `"app" : command`, `"app" ~`, or `* : command`.
+fn parse_arm(arm: &str) -> Option<(Option<String>, Option<String>)> {
+    let (app, rest) = if let Some(rest) = arm.strip_prefix('*') {
+        (None, rest)
+    } else {
+        let rest = arm.strip_prefix('"')?;
+        let (app, rest) = rest.split_once('"')?;
+        (Some(app.to_string()), rest)
+    };
+    let rest = rest.trim();
+    if let Some(command) = rest.strip_prefix(':') {
+        Some((app, Some(command.trim().to_string())))
+    } else if rest == "~" && app.is_some() {
+        Some((app, None))
+    } else {
+        None
+    }
+}
+
+/// Translate an skhd key combination such as `cmd + shift - h` into a canonical Hotki chord.
+fn translate_chord(keys: &str) -> Result<String, String> {
+    let (modifiers, key) = match keys.split_once('-') {
+        Some((modifiers, key)) => (modifiers, key.trim()),
+        None => ("", keys.trim()),
+    };
+    let mut parts = Vec::new();
+    for modifier in modifiers
+        .split('+')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+    {
+        let lowered = modifier.to_ascii_lowercase();
+        let specs: &[&str] = match lowered.as_str() {
+            "cmd" | "lcmd" => &["cmd"],
+            "rcmd" => &["rightcommand"],
+            "alt" | "lalt" => &["opt"],
+            "ralt" => &["rightoption"],
+            "ctrl" | "lctrl" => &["ctrl"],
+            "rctrl" => &["rightcontrol"],
+            "shift" | "lshift" => &["shift"],
+            "rshift" => &["rightshift"],
+            "fn" => &["fn"],
+            "hyper" => &["cmd", "opt", "ctrl", "shift"],
+            "meh" => &["opt", "ctrl", "shift"],
+            _ => return Err(format!("unknown modifier `{modifier}`")),
+        };
+        parts.extend_from_slice(specs);
+    }
+    let key = key.to_ascii_lowercase();
+    if key.starts_with("0x") {
+        return Err(format!("hex keycode `{key}` is not supported"));
+    }
+    parts.push(match key.as_str() {
+        "delete" => "forwarddelete",
+        "insert" => "help",
+        "sound_up" => "volumeup",
+        "sound_down" => "volumedown",
+        other => other,
+    });
+    Chord::parse(&parts.join("+"))
+        .map(|chord| chord.to_string())
+        .ok_or_else(|| format!("key `{key}` has no Hotki equivalent"))
+}
+
+/// Split skhd source into statements, dropping comments and blank lines.
+fn statements(source: &str) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    let mut pending: Option<Statement> = None;
+    let mut in_block = false;
+    for (index, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let statement = pending.get_or_insert_with(|| Statement {
+            line: index + 1,
+            text: String::new(),
+        });
+        if in_block {
+            statement.text.push('\n');
+        }
+        if let Some(continued) = trimmed.strip_suffix('\\') {
+            statement.text.push_str(continued.trim_end());
+            statement.text.push(' ');
+            continue;
+        }
+        statement.text.push_str(trimmed);
+        if in_block {
+            in_block = !trimmed.ends_with(']');
+        } else {
+            in_block = trimmed.ends_with('[');
+        }
+        if !in_block && let Some(statement) = pending.take() {
+            statements.push(statement);
+        }
+    }
+    statements.extend(pending);
+    statements
+}
+
+/// Binding description for a command: its first line, shortened for the HUD.
+fn description(command: &str) -> String {
+    let line = first_line(command);
+    if line.chars().count() <= MAX_DESC_CHARS {
+        return line.to_string();
+    }
+    let mut short = line.chars().take(MAX_DESC_CHARS - 1).collect::<String>();
+    short.push('…');
+    short
+}
+
+/// First line of a possibly multi-line statement.
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
+
+/// Text safe to place after `--` on one line.
+fn comment(text: &str) -> String {
+    text.replace('\n', " ")
+}
+
+/// Luau local holding the renderer for an skhd mode.
+fn mode_local(name: &str) -> String {
+    let ident = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format!("mode_{ident}")
+}
+
+/// Double-quoted Luau string literal.
+fn luau_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ignored = write!(out, "\\u{{{:x}}}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::load_dynamic_config_from_string;
+
+    #[test]
+    fn translates_modes_applications_and_reports_unsupported() {
+        let source = r#"
+# window management
+.load "other.skhdrc"
+:: default : yabai -m config active_window_border_color 0xff775759
+:: window @ : yabai -m config active_window_border_color 0xff24ccaa
+
+alt - return : open -na "Kitty"
+hyper - r : yabai -m space --rotate 90
+default < cmd + alt - w ; window
+window < escape ; default
+window < h : yabai -m window --focus west
+window < shift - h : yabai -m window --warp west \
+    && echo moved
+cmd - 0x2B : echo comma
+cmd - x -> : echo cut
+cmd - n [
+    "kitty" : kitty --single-instance
+    "Safari" : open -a Safari
+    "terminal" ~
+    * : open -a Finder
+]
+"#;
+        let import = import_skhd(source);
+        let config = &import.config;
+        assert!(config.contains(r#"menu:bind("opt+return", "open -na \"Kitty\"""#));
+        assert!(config.contains(r#"menu:bind("cmd+opt+ctrl+shift+r""#));
+        assert!(
+            config.contains(
+                r#"menu:submenu("cmd+opt+w", "window", mode_window, { capture = true })"#
+            )
+        );
+        assert!(config.contains(r#"menu:bind("escape", "default", a.exit)"#));
+        assert!(config.contains(r#"stay:bind("h", "yabai -m window --focus west""#));
+        assert!(config.contains(r#"a.shell("yabai -m window --warp west && echo moved")"#));
+        assert!(config.contains(r#"r.when_app_matches("(?i)^kitty$", function(m)"#));
+        assert!(config.contains(r#"r.when_app("Safari", function(m)"#));
+        assert!(config.contains(r#"not (window:app_matches("(?i)^terminal$"))"#));
+        assert!(config.contains(r#"menu:bind("cmd+x", "echo cut""#));
+
+        let unsupported = import.unsupported.join("\n");
+        assert!(
+            unsupported.contains("line 3: directive `.load`"),
+            "{unsupported}"
+        );
+        assert!(
+            unsupported.contains("entering mode `default`"),
+            "{unsupported}"
+        );
+        assert!(
+            unsupported.contains("entering mode `window`"),
+            "{unsupported}"
+        );
+        assert!(unsupported.contains("hex keycode `0x2b`"), "{unsupported}");
+        assert!(unsupported.contains("passthrough"), "{unsupported}");
+        assert_eq!(import.unsupported.len(), 5, "{unsupported}");
+        assert!(config.contains("-- line 14: hex keycode"));
+
+        load_dynamic_config_from_string(config, None).expect("generated config loads");
+    }
+
+    #[test]
+    fn chords_translate_through_mac_keycode() {
+        assert_eq!(
+            translate_chord("lalt + rshift - f1").as_deref(),
+            Ok("opt+rightshift+f1")
+        );
+        assert_eq!(
+            translate_chord("meh - delete").as_deref(),
+            Ok("opt+ctrl+shift+forwarddelete")
+        );
+        assert_eq!(translate_chord("alt - space").as_deref(), Ok("opt+ "));
+        assert!(translate_chord("super - a").is_err());
+        assert!(translate_chord("cmd - brightness_up").is_err());
+    }
+}
@@ -234,6 +234,7 @@ pub fn eval_style_source(source: &str, path: &Path) -> Result<raw::RawStyle, Err
             col: None,
             message: format!("invalid style table: {message}"),
             excerpt: None,
+            diagnostics: Vec::new(),
         });
     }
 
@@ -276,6 +277,7 @@ fn style_script_error<'s>(
         col,
         message,
         excerpt,
+        diagnostics: Vec::new(),
     }
 }
 
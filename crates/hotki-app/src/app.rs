@@ -10,7 +10,7 @@ use eframe::{App, CreationContext};
 use egui::Context;
 use eguidev::DevMcp;
 use hotki_protocol::{
-    DisplaysSnapshot, HudState, InputHealth, MsgToUI, NotifyConfig, Style, Toggle,
+    DisplaysSnapshot, HudState, InputHealth, MsgToUI, NotifyConfig, Style, Toggle, rpc::ProcessInfo,
 };
 use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy};
 use objc2_foundation::MainThreadMarker;
@@ -55,6 +55,8 @@ pub enum UiCommand {
     SetRuntimeHealthOverride(Option<Box<RuntimeHealth>>),
     /// Update the server binding identifiers visible to devtools.
     SetServerBindings(Vec<String>),
+    /// Replace the running-process list shown in the main window.
+    SetProcesses(Vec<ProcessInfo>),
     /// Update the full input-health snapshot used by diagnostics.
     SetInputHealth(InputHealth),
     /// Override permission status for deterministic devtools fixtures.
@@ -133,6 +135,8 @@ pub struct HotkiApp {
     pub(crate) tx_ctrl: tokio_mpsc::UnboundedSender<ControlMsg>,
     /// Sorted server binding identifiers, when the runtime has reported them.
     pub(crate) server_bindings: Vec<String>,
+    /// Processes started by bindings that are still running, oldest first.
+    pub(crate) processes: Vec<ProcessInfo>,
     /// Latest full physical-input health snapshot from the server heartbeat.
     pub(crate) input_health: InputHealth,
     /// App-local requests waiting for a specific UI state to be painted.
@@ -221,10 +225,12 @@ impl App for HotkiApp {
         self.selector.render(ctx, &self.devmcp);
         let notifications_animating = self.notifications.render(ctx, &self.devmcp);
         self.fixture_runtime.set_app_idle(!notifications_animating);
-        if let Some(command) =
-            self.main_window
-                .render(ctx, self.notifications.backlog(), &self.devmcp)
-        {
+        if let Some(command) = self.main_window.render(
+            ctx,
+            self.notifications.backlog(),
+            &self.processes,
+            &self.devmcp,
+        ) {
             self.handle_main_window_command(command);
         }
         self.logs_window.render(ctx, &self.devmcp);
@@ -313,6 +319,7 @@ impl HotkiApp {
             about_panel_opened: false,
             tx_ctrl: bootstrap.tx_ctrl,
             server_bindings: Vec::new(),
+            processes: Vec::new(),
             input_health: InputHealth::default(),
             harness_requests: bootstrap.harness_requests,
             pending_presentations: Vec::new(),
@@ -417,6 +424,9 @@ impl HotkiApp {
             UiCommand::SetServerBindings(bindings) => {
                 self.server_bindings = bindings;
             }
+            UiCommand::SetProcesses(processes) => {
+                self.processes = processes;
+            }
             UiCommand::SetInputHealth(input) => {
                 self.input_health = input;
             }
@@ -519,6 +529,7 @@ impl HotkiApp {
                 presentation.hud.rows.clone(),
                 presentation.hud.visible,
                 presentation.hud.breadcrumbs.clone(),
+                presentation.hud.badge.clone(),
             );
             self.hud
                 .set_display_metrics(DisplayMetrics::from_snapshot(&presentation.displays));
@@ -535,8 +546,12 @@ impl HotkiApp {
             return;
         };
         self.hud.set_style(hud.style.hud.clone());
-        self.hud
-            .set_state(hud.rows.clone(), hud.visible, hud.breadcrumbs.clone());
+        self.hud.set_state(
+            hud.rows.clone(),
+            hud.visible,
+            hud.breadcrumbs.clone(),
+            hud.badge.clone(),
+        );
     }
 
     /// Propagate one runtime-health snapshot to every normal UI surface.
@@ -566,6 +581,11 @@ impl HotkiApp {
                 }
             }
             MainWindowCommand::ShowLogs => self.logs_window.show(),
+            MainWindowCommand::CancelProcess(run) => {
+                if let Err(error) = self.tx_ctrl.send(ControlMsg::CancelProcess(run)) {
+                    tracing::warn!(?error, "failed to send main-window process cancel");
+                }
+            }
         }
     }
 }
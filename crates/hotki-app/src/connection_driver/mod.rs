@@ -3,7 +3,11 @@ use std::{collections::VecDeque, fmt::Write as _, path::PathBuf, pin::Pin};
 /// UI event forwarding and repaint coordination.
 mod ui_sink;
 
-use hotki_protocol::{NotifyKind, ipc::heartbeat, rpc::InjectKind};
+use hotki_protocol::{
+    NotifyKind,
+    ipc::heartbeat,
+    rpc::{InjectKind, ProcessInfo},
+};
 use hotki_server::{Client, Result as ServerResult};
 use tokio::{
     sync::{mpsc, oneshot},
@@ -43,6 +47,8 @@ pub struct ConnectionDriver {
     pending_controls: VecDeque<ServerControl>,
     /// Whether this app session already warned for the current active observation run.
     secure_input_warning_sent: bool,
+    /// Running processes last published to the UI.
+    processes: Vec<ProcessInfo>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -105,6 +111,8 @@ enum ServerControl {
         /// Whether failed injection should be surfaced to the user.
         report_errors: bool,
     },
+    /// Kill one running process by run identifier.
+    CancelProcess(u64),
 }
 
 /// Routing class for a UI/runtime control message.
@@ -131,6 +139,7 @@ impl ControlRoute {
                 ident,
                 report_errors,
             }),
+            ControlMsg::CancelProcess(run) => Self::Server(ServerControl::CancelProcess(run)),
             ControlMsg::OpenPermissionsHelp => Self::Local(LocalControl::OpenPermissionsHelp),
             ControlMsg::Notice { kind, title, text } => {
                 Self::Local(LocalControl::Notice { kind, title, text })
@@ -167,6 +176,7 @@ impl ConnectionDriver {
             permission_override: None,
             pending_controls: VecDeque::new(),
             secure_input_warning_sent: false,
+            processes: Vec::new(),
         };
         driver.ui.set_runtime_health(driver.health.clone());
         driver
@@ -181,6 +191,7 @@ impl ConnectionDriver {
     fn mark_disconnected(&mut self, message: impl Into<String>) {
         self.health.disconnect(message);
         self.ui.set_server_bindings(Vec::new());
+        self.set_processes(Vec::new());
         self.publish_health();
     }
 
@@ -282,6 +293,12 @@ impl ConnectionDriver {
                         .await;
                 }
             }
+            ServerControl::CancelProcess(run) => {
+                if let Err(err) = conn.cancel_process(run).await {
+                    warn!("failed to cancel process run {run}: {err}");
+                }
+                self.refresh_processes(conn).await;
+            }
         }
     }
 
@@ -658,6 +675,34 @@ impl ConnectionDriver {
         }
     }
 
+    /// Refresh the running-process list, publishing it only when it changed.
+    async fn refresh_processes(&mut self, conn: &mut hotki_server::Connection) {
+        match conn.list_processes().await {
+            Ok(processes) => self.set_processes(processes),
+            Err(err) => debug!("failed to refresh running processes: {err}"),
+        }
+    }
+
+    /// Publish a running-process list that differs from the last one sent.
+    fn set_processes(&mut self, processes: Vec<ProcessInfo>) {
+        if processes != self.processes {
+            self.processes = processes.clone();
+            self.ui.set_processes(processes);
+        }
+    }
+
+    /// Refresh supervised service status, republishing health only when it changed.
+    async fn refresh_services(&mut self, conn: &mut hotki_server::Connection) {
+        match conn.get_services().await {
+            Ok(services) if services != self.health.services => {
+                self.health.services = services;
+                self.publish_health();
+            }
+            Ok(_) => {}
+            Err(err) => debug!("failed to refresh services: {err}"),
+        }
+    }
+
     /// Main UI event loop once connected: handles control, server events, and heartbeat.
     pub(crate) async fn drive_events(&mut self, client: &mut Client) {
         let conn = match client.connection() {
@@ -708,7 +753,15 @@ impl ConnectionDriver {
             Some(msg) = self.rx_ctrl.recv() => {
                 !self.route_control_msg(Some(conn), msg).await.should_stop()
             }
-            resp = conn.recv_event() => self.handle_recv_event_result(resp, hb_timer).await,
+            resp = conn.recv_event() => {
+                let heartbeat = matches!(resp, Ok(hotki_protocol::MsgToUI::Heartbeat(_)));
+                let connected = self.handle_recv_event_result(resp, hb_timer).await;
+                if connected && heartbeat {
+                    self.refresh_processes(conn).await;
+                    self.refresh_services(conn).await;
+                }
+                connected
+            }
             _ = dump_timer.as_mut(), if dumpworld => {
                 self.dump_world_snapshot(conn).await;
                 dump_timer.as_mut().reset(TokioInstant::now() + dump_interval);
@@ -870,6 +923,10 @@ mod tests {
                 report_errors: true,
             })
         );
+        assert_eq!(
+            ControlRoute::from_msg(ControlMsg::CancelProcess(7)),
+            ControlRoute::Server(ServerControl::CancelProcess(7))
+        );
         assert_eq!(
             ControlRoute::from_msg(ControlMsg::Shutdown),
             ControlRoute::Shutdown
@@ -1,5 +1,5 @@
 use egui::Context;
-use hotki_protocol::{InputHealth, MsgToUI, NotifyKind};
+use hotki_protocol::{InputHealth, MsgToUI, NotifyKind, rpc::ProcessInfo};
 
 use crate::{
     app::{UiCommand, UiEvent},
@@ -55,6 +55,11 @@ impl UiSink {
         self.send_command(UiCommand::SetServerBindings(bindings));
     }
 
+    /// Replace the running-process list shown in the main window.
+    pub(super) fn set_processes(&self, processes: Vec<ProcessInfo>) {
+        self.send_command(UiCommand::SetProcesses(processes));
+    }
+
     /// Replace the full diagnostic input-health snapshot.
     pub(super) fn set_input_health(&self, input: InputHealth) {
         self.send_command(UiCommand::SetInputHealth(input));
@@ -906,6 +906,7 @@ impl FixtureBridge {
                     breadcrumbs: vec![title.to_string()],
                     style,
                     capture: false,
+                    badge: None,
                 }),
                 displays: DisplaysSnapshot::default(),
             },
@@ -936,6 +937,7 @@ impl FixtureBridge {
                     breadcrumbs: vec!["Tall HUD".to_string()],
                     style: Style::default(),
                     capture: false,
+                    badge: None,
                 }),
                 displays: tall_hud_display_snapshot(),
             },
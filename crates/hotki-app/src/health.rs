@@ -2,7 +2,10 @@
 
 use std::path::{Path, PathBuf};
 
-use hotki_protocol::{InputHealth, SecureInputOwner, SecureInputState, TapLifecycle, TapMode};
+use hotki_protocol::{
+    InputHealth, SecureInputOwner, SecureInputState, TapLifecycle, TapMode,
+    rpc::{ServiceState, ServiceStatus},
+};
 use permissions::PermissionsStatus;
 
 /// Transition-stable input state used by normal UI presentation.
@@ -254,6 +257,8 @@ pub struct RuntimeHealth {
     pub(crate) permissions: PermissionsStatus,
     /// Transition-stable physical-input health.
     pub(crate) input: InputProjection,
+    /// Latest status of the services declared by the active config.
+    pub(crate) services: Vec<ServiceStatus>,
 }
 
 impl RuntimeHealth {
@@ -272,6 +277,7 @@ impl RuntimeHealth {
         self.state = RuntimeState::Disconnected {
             message: Some(message.into()),
         };
+        self.services.clear();
     }
 
     /// Begin the current initial connection or a post-failure reconnect.
@@ -434,7 +440,7 @@ impl RuntimeHealth {
                 }),
                 Some(PrimaryAction::ReloadConfig),
             ),
-            RuntimePhase::Ready => (None, Some(PrimaryAction::ReloadConfig)),
+            RuntimePhase::Ready => (self.service_notice(), Some(PrimaryAction::ReloadConfig)),
             RuntimePhase::Connecting => (
                 Some(RuntimeNotice {
                     title: "Starting Hotki…",
@@ -492,6 +498,29 @@ impl RuntimeHealth {
         }
     }
 
+    /// Attention notice naming services that failed or are waiting to restart.
+    fn service_notice(&self) -> Option<RuntimeNotice> {
+        let failing = self
+            .services
+            .iter()
+            .filter(|service| matches!(service.state, ServiceState::Failed | ServiceState::Backoff))
+            .map(|service| service.name.as_str())
+            .collect::<Vec<_>>();
+        if failing.is_empty() {
+            return None;
+        }
+        Some(RuntimeNotice {
+            title: if failing.len() == 1 {
+                "A background service is failing"
+            } else {
+                "Background services are failing"
+            },
+            detail: Some(format!("Check {} under Services.", failing.join(", "))),
+            tone: NoticeTone::Attention,
+            progress: false,
+        })
+    }
+
     /// Return retry intent only while the failed operation is user-retryable.
     fn retry_action(&self) -> Option<PrimaryAction> {
         matches!(self.retry(), RetryState::Available).then_some(PrimaryAction::TryAgain)
@@ -535,6 +564,7 @@ fn missing_required_permissions(status: PermissionsStatus) -> Option<String> {
 mod tests {
     use std::path::{Path, PathBuf};
 
+    use hotki_protocol::rpc::{ServiceState, ServiceStatus};
     use permissions::{PermissionState, PermissionsStatus};
 
     use super::{
@@ -887,6 +917,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn failing_services_raise_an_attention_notice_while_ready() {
+        let service = |name: &str, state: ServiceState| ServiceStatus {
+            name: name.to_string(),
+            state,
+            pid: None,
+            restarts: 0,
+            detail: None,
+        };
+        let mut health = health_for_phase(RuntimePhase::Ready);
+        health.services = vec![
+            service("sync", ServiceState::Running),
+            service("poller", ServiceState::Exited),
+        ];
+        assert!(health.presentation().notice.is_none());
+
+        health
+            .services
+            .push(service("watcher", ServiceState::Backoff));
+        let notice = health.presentation().notice.expect("service notice");
+        assert_eq!(notice.title, "A background service is failing");
+        assert_eq!(
+            notice.detail.as_deref(),
+            Some("Check watcher under Services.")
+        );
+        assert_eq!(notice.tone, NoticeTone::Attention);
+
+        health
+            .services
+            .push(service("indexer", ServiceState::Failed));
+        let notice = health.presentation().notice.expect("service notice");
+        assert_eq!(notice.title, "Background services are failing");
+        assert_eq!(
+            notice.detail.as_deref(),
+            Some("Check watcher, indexer under Services.")
+        );
+
+        health.disconnect("connection lost");
+        assert!(health.services.is_empty());
+    }
+
     #[test]
     fn invalid_config_explains_active_predecessor() {
         let mut health = health_for_phase(RuntimePhase::InvalidConfig);
@@ -47,6 +47,8 @@ pub struct Hud {
     rows: Vec<HudRow>,
     /// Breadcrumb titles for the current mode stack.
     breadcrumbs: Vec<String>,
+    /// Active application-mode badge shown while the HUD is otherwise hidden.
+    badge: Option<String>,
     /// Transient physical press state keyed by canonical chord.
     presses: HudPressState,
     /// Whether NSWindow style has been applied for the current HUD session.
@@ -204,6 +206,7 @@ impl Hud {
             viewport: OverlayWindow::new("hotki_hud"),
             rows: Vec::new(),
             breadcrumbs: Vec::new(),
+            badge: None,
             presses: HudPressState::default(),
             window_configured: false,
         }
@@ -401,18 +404,41 @@ impl Hud {
         ui.style_mut().visuals.override_text_color = previous_color;
     }
 
-    /// Update the current HUD state: rows, visibility, and breadcrumbs.
-    pub fn set_state(&mut self, rows: Vec<HudRow>, visible: bool, breadcrumbs: Vec<String>) {
+    /// Update the current HUD state: rows, visibility, breadcrumbs, and app-mode badge.
+    pub fn set_state(
+        &mut self,
+        rows: Vec<HudRow>,
+        visible: bool,
+        breadcrumbs: Vec<String>,
+        badge: Option<String>,
+    ) {
         self.rows = rows;
         self.breadcrumbs = breadcrumbs;
         self.presses.reconcile(&self.rows, visible, self.cfg.mode);
-        if visible && !self.visible {
+        if visible != self.visible || badge != self.badge {
             self.viewport.reset_geometry();
             self.window_configured = false;
-        } else if !visible {
-            self.window_configured = false;
         }
         self.visible = visible;
+        self.badge = badge;
+    }
+
+    /// Whether only the application-mode badge should be shown.
+    fn badge_only(&self) -> bool {
+        !self.visible && self.badge.is_some() && !matches!(self.cfg.mode, Mode::Hide)
+    }
+
+    /// Title rendered by compact presentations: the badge or the active breadcrumb.
+    fn compact_title(&self) -> Option<&String> {
+        if self.badge_only() {
+            return self.badge.as_ref();
+        }
+        self.breadcrumbs.last().filter(|s| !s.trim().is_empty())
+    }
+
+    /// Whether the current state renders as a single compact title.
+    fn is_compact(&self) -> bool {
+        self.badge_only() || matches!(self.cfg.mode, Mode::Mini)
     }
 
     /// Hide the HUD window immediately.
@@ -420,6 +446,7 @@ impl Hud {
         self.visible = false;
         self.rows.clear();
         self.breadcrumbs.clear();
+        self.badge = None;
         self.presses.clear();
         self.window_configured = false;
         self.viewport.hide(ctx);
@@ -535,9 +562,9 @@ impl Hud {
 
     /// Desired HUD window size including padding and minimums.
     fn desired_size(&self, ctx: &Context) -> Vec2 {
-        if matches!(self.cfg.mode, Mode::Mini) {
-            // Compact size based only on the active breadcrumb title.
-            if let Some(title) = self.breadcrumbs.last().filter(|s| !s.trim().is_empty()) {
+        if self.is_compact() {
+            // Compact size based only on the badge or active breadcrumb title.
+            if let Some(title) = self.compact_title() {
                 let (w, h) = ctx.fonts_mut(|f| {
                     let g = f.layout_no_wrap(title.clone(), self.title_font_id(), Color32::WHITE);
                     (g.size().x, g.size().y)
@@ -570,15 +597,11 @@ impl Hud {
             12.0,
             self.cfg.offset,
         );
-        let mini_title = matches!(self.cfg.mode, Mode::Mini)
-            .then(|| {
-                self.breadcrumbs
-                    .last()
-                    .filter(|s| !s.trim().is_empty())
-                    .cloned()
-            })
+        let mini_title = self
+            .is_compact()
+            .then(|| self.compact_title().cloned())
             .flatten();
-        let content_size = if matches!(self.cfg.mode, Mode::Hud) {
+        let content_size = if !self.is_compact() && matches!(self.cfg.mode, Mode::Hud) {
             self.measure_content_size(ctx)
         } else {
             Vec2::ZERO
@@ -592,7 +615,7 @@ impl Hud {
 
     /// Render and update the HUD viewport.
     pub fn render(&mut self, ctx: &Context, devmcp: &DevMcp) {
-        if !self.visible {
+        if !self.visible && !self.badge_only() {
             self.viewport.hide(ctx);
             return;
         }
@@ -665,7 +688,7 @@ impl Hud {
         let (fr, fg, fb) = self.cfg.title_fg;
         style.visuals.override_text_color = Some(Color32::from_rgba_unmultiplied(fr, fg, fb, 255));
 
-        if matches!(self.cfg.mode, Mode::Mini) {
+        if self.is_compact() {
             self.render_mini_hud(ui, hud_ctx, model.mini_title.as_deref());
         } else {
             self.render_full_hud(ui, model, now);
@@ -817,7 +840,12 @@ mod tests {
             }),
             displays: Vec::new(),
         }));
-        hud.set_state(test_rows(row_count), true, vec!["Tall HUD".to_string()]);
+        hud.set_state(
+            test_rows(row_count),
+            true,
+            vec!["Tall HUD".to_string()],
+            None,
+        );
         hud
     }
 
@@ -921,7 +949,7 @@ mod tests {
         let mut hud = Hud::new(&HudStyle::default());
 
         hud.set_key_state(&chord, true, start);
-        hud.set_state(vec![test_row("a", true, false)], true, Vec::new());
+        hud.set_state(vec![test_row("a", true, false)], true, Vec::new(), None);
         assert!(hud.presses.is_active(&chord, start, minimum));
 
         let mut reloaded = HudStyle::default();
@@ -929,15 +957,15 @@ mod tests {
         hud.set_style(reloaded);
         assert!(hud.presses.is_active(&chord, start, minimum));
 
-        hud.set_state(vec![test_row("a", false, false)], true, Vec::new());
+        hud.set_state(vec![test_row("a", false, false)], true, Vec::new(), None);
         assert!(!hud.presses.is_active(&chord, start, minimum));
 
         hud.set_key_state(&chord, true, start);
-        hud.set_state(vec![test_row("b", true, false)], true, Vec::new());
+        hud.set_state(vec![test_row("b", true, false)], true, Vec::new(), None);
         assert!(!hud.presses.is_active(&chord, start, minimum));
 
         hud.set_key_state(&chord, true, start);
-        hud.set_state(vec![test_row("a", true, false)], false, Vec::new());
+        hud.set_state(vec![test_row("a", true, false)], false, Vec::new(), None);
         assert!(!hud.presses.is_active(&chord, start, minimum));
 
         hud.set_key_state(&chord, true, start);
@@ -953,6 +981,35 @@ mod tests {
         assert!(!hud.presses.is_active(&chord, start, minimum));
     }
 
+    #[test]
+    fn hidden_hud_with_app_mode_badge_renders_compact_title() {
+        let ctx = test_context();
+        let display_size = vec2(1600.0, 1200.0);
+        let mut hud = test_hud(4, display_size);
+
+        hud.set_state(test_rows(4), false, Vec::new(), Some("Figma".to_string()));
+        let model = view_model_for(&ctx, &hud, display_size);
+        assert_eq!(model.mini_title.as_deref(), Some("Figma"));
+        assert_eq!(model.content_size, Vec2::ZERO);
+
+        hud.set_style(HudStyle {
+            mode: Mode::Hide,
+            ..HudStyle::default()
+        });
+        assert!(!hud.badge_only());
+
+        hud.set_style(HudStyle::default());
+        hud.set_state(
+            test_rows(4),
+            true,
+            vec!["Tall HUD".to_string()],
+            Some("Figma".to_string()),
+        );
+        let model = view_model_for(&ctx, &hud, display_size);
+        assert_eq!(model.mini_title, None);
+        assert!(model.content_size.y > 0.0);
+    }
+
     #[test]
     fn full_hud_render_stays_inside_measured_height_before_screen_cap() {
         let ctx = test_context();
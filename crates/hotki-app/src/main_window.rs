@@ -1,5 +1,7 @@
-//! Compact main window with runtime notice, recent activity, and commands.
+//! Compact main window with runtime notice, services, running processes, recent activity, and
+//! commands.
 
+use chrono::{DateTime, Local};
 use egui::{
     CentralPanel, Color32, Context, Label, Layout, Panel, Pos2, RichText, ScrollArea, Sense, Vec2,
     ViewportBuilder, ViewportCommand, vec2,
@@ -7,7 +9,10 @@ use egui::{
 use eguidev::{
     DevMcp, DevUiExt, WidgetMeta, WidgetRole, WidgetValue, container, track_response_full,
 };
-use hotki_protocol::{NotifyKind, NotifyTheme};
+use hotki_protocol::{
+    NotifyKind, NotifyTheme,
+    rpc::{ProcessInfo, ServiceState, ServiceStatus},
+};
 
 use crate::{
     devtools,
@@ -30,6 +35,10 @@ const CONTENT_PAD: f32 = 14.0;
 const CONTENT_GAP: f32 = 8.0;
 /// Width reserved for a fixed-format `HH:MM` activity timestamp.
 const TIMESTAMP_WIDTH: f32 = 34.0;
+/// Maximum height of the running-process list before it scrolls.
+const PROCESSES_MAX_HEIGHT: f32 = 140.0;
+/// Trailing output lines shown under each running process.
+const PROCESS_OUTPUT_LINES: usize = 3;
 
 /// One command emitted by the main-window renderer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +47,8 @@ pub enum MainWindowCommand {
     Primary(PrimaryAction),
     /// Open or raise the dedicated logs window.
     ShowLogs,
+    /// Kill one running process by run identifier.
+    CancelProcess(u64),
 }
 
 /// Session geometry with global outer position and egui inner size kept separate.
@@ -90,6 +101,8 @@ pub struct MainWindow {
     theme: NotifyTheme,
     /// Complete presentation derived from the latest runtime snapshot.
     presentation: RuntimePresentation,
+    /// Services declared by the active config, from the latest runtime snapshot.
+    services: Vec<ServiceStatus>,
 }
 
 impl MainWindow {
@@ -104,6 +117,7 @@ impl MainWindow {
             last_saved: None,
             theme,
             presentation: RuntimeHealth::default().presentation(),
+            services: Vec::new(),
         }
     }
 
@@ -144,6 +158,7 @@ impl MainWindow {
     /// Replace the presentation model from one complete runtime snapshot.
     pub(crate) fn set_runtime_health(&mut self, health: &RuntimeHealth) {
         self.presentation = health.presentation();
+        self.services.clone_from(&health.services);
         if health.is_shutting_down() {
             self.hide();
         }
@@ -154,6 +169,7 @@ impl MainWindow {
         &mut self,
         ctx: &Context,
         backlog: &[BacklogEntry],
+        processes: &[ProcessInfo],
         devmcp: &DevMcp,
     ) -> Option<MainWindowCommand> {
         if !self.visible {
@@ -190,7 +206,7 @@ impl MainWindow {
                     self.want_focus = false;
                 }
                 CentralPanel::default().show(vp_ui, |ui| {
-                    command = self.render_contents(ui, backlog);
+                    command = self.render_contents(ui, backlog, processes);
                 });
                 self.save_geometry(&window_ctx);
             });
@@ -242,11 +258,13 @@ impl MainWindow {
         }
     }
 
-    /// Render the fixed footer and notice around the scrolling activity list.
+    /// Render the fixed footer, notice, services, and running processes around the scrolling
+    /// activity list.
     fn render_contents(
         &self,
         ui: &mut egui::Ui,
         backlog: &[BacklogEntry],
+        processes: &[ProcessInfo],
     ) -> Option<MainWindowCommand> {
         let mut command = None;
         Panel::bottom("main.footer.panel")
@@ -261,6 +279,20 @@ impl MainWindow {
                 .resizable(false)
                 .show(ui, |ui| self.render_notice(ui, notice));
         }
+        if !self.services.is_empty() {
+            Panel::top("main.services.panel")
+                .resizable(false)
+                .show(ui, |ui| self.render_services(ui));
+        }
+        if !processes.is_empty() {
+            Panel::top("main.processes.panel")
+                .resizable(false)
+                .show(ui, |ui| {
+                    if let Some(cancel) = self.render_processes(ui, processes) {
+                        command = Some(cancel);
+                    }
+                });
+        }
         CentralPanel::default().show(ui, |ui| self.render_activity(ui, backlog));
         command
     }
@@ -320,6 +352,116 @@ impl MainWindow {
         );
     }
 
+    /// Render supervised services in declaration order with their state.
+    fn render_services(&self, ui: &mut egui::Ui) {
+        ui.add_space(CONTENT_PAD);
+        container(ui, "main.services", |ui| {
+            ui.dev_label("main.services.title", RichText::new("Services").strong());
+            for (index, service) in self.services.iter().enumerate() {
+                ui.add_space(CONTENT_GAP);
+                container(ui, format!("main.services.{index}.row"), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.dev_label(
+                            format!("main.services.{index}.name"),
+                            RichText::new(&service.name).strong(),
+                        );
+                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                            let label = RichText::new(service_state_label(service.state));
+                            let label = match service.state {
+                                ServiceState::Failed => label.color(ui.visuals().error_fg_color),
+                                ServiceState::Backoff => label.color(ui.visuals().warn_fg_color),
+                                _ => label.weak(),
+                            };
+                            ui.dev_label(format!("main.services.{index}.state"), label);
+                        });
+                    });
+                    let detail = service_detail(service);
+                    if !detail.is_empty() {
+                        ui.dev_label(
+                            format!("main.services.{index}.detail"),
+                            RichText::new(detail).weak(),
+                        );
+                    }
+                });
+            }
+        });
+        ui.add_space(CONTENT_PAD);
+        ui.dev_separator("main.services.separator");
+    }
+
+    /// Render running processes oldest first, each with a cancel button.
+    fn render_processes(
+        &self,
+        ui: &mut egui::Ui,
+        processes: &[ProcessInfo],
+    ) -> Option<MainWindowCommand> {
+        let mut command = None;
+        ui.add_space(CONTENT_PAD);
+        container(ui, "main.processes", |ui| {
+            ui.dev_label("main.processes.title", RichText::new("Running").strong());
+            ScrollArea::vertical()
+                .id_salt("main.processes.scroll")
+                .max_height(PROCESSES_MAX_HEIGHT)
+                .show(ui, |ui| {
+                    for (index, process) in processes.iter().enumerate() {
+                        if index > 0 {
+                            ui.dev_separator(format!("main.processes.{index}.separator"));
+                        }
+                        if self.render_process_row(ui, index, process) {
+                            command = Some(MainWindowCommand::CancelProcess(process.run));
+                        }
+                    }
+                });
+        });
+        ui.add_space(CONTENT_PAD);
+        ui.dev_separator("main.processes.separator");
+        command
+    }
+
+    /// Render one running process and return whether its cancel button was clicked.
+    fn render_process_row(&self, ui: &mut egui::Ui, index: usize, process: &ProcessInfo) -> bool {
+        let mut cancel = false;
+        container(ui, format!("main.processes.{index}.row"), |ui| {
+            ui.add_space(CONTENT_GAP);
+            ui.horizontal(|ui| {
+                ui.dev_label(
+                    format!("main.processes.{index}.command"),
+                    RichText::new(&process.command).strong(),
+                );
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    cancel = ui
+                        .dev_button(format!("main.processes.{index}.cancel"), "Cancel")
+                        .clicked();
+                });
+            });
+            ui.dev_label(
+                format!("main.processes.{index}.detail"),
+                RichText::new(process_detail(process)).weak(),
+            );
+            let tail = output_tail(&process.output);
+            if !tail.is_empty() {
+                let response = ui.add(
+                    Label::new(RichText::new(&tail).monospace())
+                        .wrap()
+                        .selectable(true),
+                );
+                track_response_full(
+                    format!("main.processes.{index}.output"),
+                    &response,
+                    WidgetMeta {
+                        role: WidgetRole::Selectable,
+                        label: Some(tail.clone()),
+                        value: Some(WidgetValue::Text(tail)),
+                        visible: true,
+                        ..Default::default()
+                    },
+                );
+            }
+            ui.add_space(CONTENT_GAP);
+        });
+        cancel
+    }
+
     /// Render recent activity newest first inside the only scrolling region.
     fn render_activity(&self, ui: &mut egui::Ui, backlog: &[BacklogEntry]) {
         ui.add_space(CONTENT_PAD);
@@ -438,6 +580,57 @@ fn severity_color(theme: &NotifyTheme, kind: NotifyKind) -> Color32 {
     Color32::from_rgb(red, green, blue)
 }
 
+/// Binding, process identifier, and local start time for one running process.
+fn process_detail(process: &ProcessInfo) -> String {
+    let started = i64::try_from(process.started_ms)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .map(|time| time.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    format!(
+        "{} · pid {} · started {started}",
+        process.binding, process.pid
+    )
+}
+
+/// Short lowercase label for a service lifecycle state.
+fn service_state_label(state: ServiceState) -> &'static str {
+    match state {
+        ServiceState::Starting => "starting",
+        ServiceState::Running => "running",
+        ServiceState::Backoff => "restarting",
+        ServiceState::Exited => "exited",
+        ServiceState::Failed => "failed",
+        ServiceState::Stopped => "stopped",
+    }
+}
+
+/// Pid, restart count, and last exit for one service, omitting parts that do not apply.
+fn service_detail(service: &ServiceStatus) -> String {
+    let mut parts = Vec::new();
+    if let Some(pid) = service.pid {
+        parts.push(format!("pid {pid}"));
+    }
+    match service.restarts {
+        0 => {}
+        1 => parts.push("1 restart".to_string()),
+        restarts => parts.push(format!("{restarts} restarts")),
+    }
+    if let Some(detail) = &service.detail {
+        parts.push(detail.clone());
+    }
+    parts.join(" · ")
+}
+
+/// Last few non-blank lines of a process's output so far.
+fn output_tail(output: &str) -> String {
+    let lines = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(PROCESS_OUTPUT_LINES)..].join("\n")
+}
+
 /// Stable human-readable severity name exposed to UI automation.
 fn kind_label(kind: NotifyKind) -> &'static str {
     match kind {
@@ -451,9 +644,14 @@ fn kind_label(kind: NotifyKind) -> &'static str {
 #[cfg(test)]
 mod tests {
     use egui::{pos2, vec2};
-    use hotki_protocol::{DisplayFrame, DisplaysSnapshot, NotifyTheme};
-
-    use super::{DEFAULT_SIZE, MIN_SIZE, MainWindow, MainWindowGeometry};
+    use hotki_protocol::{
+        DisplayFrame, DisplaysSnapshot, NotifyTheme,
+        rpc::{ServiceState, ServiceStatus},
+    };
+
+    use super::{
+        DEFAULT_SIZE, MIN_SIZE, MainWindow, MainWindowGeometry, output_tail, service_detail,
+    };
     use crate::display::{DisplayMetrics, WindowGeometry};
 
     #[test]
@@ -462,6 +660,38 @@ mod tests {
         assert_eq!(MIN_SIZE, vec2(440.0, 280.0));
     }
 
+    #[test]
+    fn process_output_tail_keeps_last_non_blank_lines() {
+        assert_eq!(output_tail(""), "");
+        assert_eq!(output_tail("one\n\n"), "one");
+        assert_eq!(
+            output_tail("one\ntwo\n \nthree\nfour\n"),
+            "two\nthree\nfour"
+        );
+    }
+
+    #[test]
+    fn service_detail_joins_only_present_parts() {
+        let mut service = ServiceStatus {
+            name: "sync".to_string(),
+            state: ServiceState::Running,
+            pid: Some(42),
+            restarts: 0,
+            detail: None,
+        };
+        assert_eq!(service_detail(&service), "pid 42");
+        service.restarts = 1;
+        assert_eq!(service_detail(&service), "pid 42 · 1 restart");
+        service.state = ServiceState::Backoff;
+        service.pid = None;
+        service.restarts = 3;
+        service.detail = Some("exited with status 1".to_string());
+        assert_eq!(
+            service_detail(&service),
+            "3 restarts · exited with status 1"
+        );
+    }
+
     #[test]
     fn saved_geometry_keeps_inner_size_separate_from_decorated_frame() {
         let mut window = MainWindow::new(NotifyTheme::default());
@@ -27,6 +27,8 @@ pub enum ControlMsg {
         /// Whether failed injection should be surfaced to the user.
         report_errors: bool,
     },
+    /// Kill one running process by run identifier.
+    CancelProcess(u64),
     /// Open the in-app permissions help view.
     OpenPermissionsHelp,
     /// Forward a user-facing notice into the app UI.
@@ -81,6 +81,8 @@ struct PendingSnapshots {
     runtime_health: Option<SequencedUiEvent>,
     /// Latest server-binding health state.
     binding_health: Option<SequencedUiEvent>,
+    /// Latest running-process list.
+    processes: Option<SequencedUiEvent>,
     /// Latest permission-health state.
     permission_health: Option<SequencedUiEvent>,
 }
@@ -97,6 +99,7 @@ impl PendingSnapshots {
             UiEvent::Message(MsgToUI::World(_)) => &mut self.world,
             UiEvent::Command(UiCommand::SetRuntimeHealth(_)) => &mut self.runtime_health,
             UiEvent::Command(UiCommand::SetServerBindings(_)) => &mut self.binding_health,
+            UiEvent::Command(UiCommand::SetProcesses(_)) => &mut self.processes,
             UiEvent::Command(UiCommand::SetPermissionStatusOverride(_)) => {
                 &mut self.permission_health
             }
@@ -114,6 +117,7 @@ impl PendingSnapshots {
             self.selector.as_ref(),
             self.runtime_health.as_ref(),
             self.binding_health.as_ref(),
+            self.processes.as_ref(),
             self.permission_health.as_ref(),
             self.heartbeat.as_ref(),
             self.world.as_ref(),
@@ -131,6 +135,7 @@ impl PendingSnapshots {
             self.selector.as_ref(),
             self.runtime_health.as_ref(),
             self.binding_health.as_ref(),
+            self.processes.as_ref(),
             self.permission_health.as_ref(),
             self.heartbeat.as_ref(),
             self.world.as_ref(),
@@ -147,6 +152,7 @@ impl PendingSnapshots {
             &mut self.selector,
             &mut self.runtime_health,
             &mut self.binding_health,
+            &mut self.processes,
             &mut self.permission_health,
             &mut self.heartbeat,
             &mut self.world,
@@ -335,6 +341,7 @@ mod tests {
                 breadcrumbs: vec!["Mode".to_string()],
                 style: Style::default(),
                 capture: false,
+                badge: None,
             }),
             displays: DisplaysSnapshot::default(),
         })
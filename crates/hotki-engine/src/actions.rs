@@ -13,11 +13,14 @@ use config::runtime as dyn_engine;
 use mac_keycode::Chord;
 
 use crate::{
-    DispatchResult, Engine, Result,
+    DispatchResult, Engine, Error, Result,
     repeater::{ProcessSpec, RepeatSpec},
     selector_controller::SelectorController,
 };
 
+/// Delay between relay sequence chords when the config does not set one.
+const DEFAULT_SEQUENCE_DELAY_MS: u64 = 20;
+
 /// How an effect queue is being applied.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum EffectRun {
@@ -98,6 +101,10 @@ impl Engine {
                             config::Action::Relay(spec) => {
                                 self.apply_relay(identifier, spec, run).await?
                             }
+                            config::Action::Focus(target) => EffectApplication {
+                                result: self.apply_focus(*target, ctx.window.as_ref())?,
+                                terminal: false,
+                            },
                             _ => EffectApplication {
                                 result: self.apply_action(identifier, &action, None).await?,
                                 terminal: false,
@@ -114,6 +121,10 @@ impl Engine {
                         result: self.apply_nav_request(nav, ctx.window.clone()).await,
                         terminal: false,
                     },
+                    dyn_engine::Effect::WithClipboard(callback) => {
+                        self.run_clipboard_callback(identifier, callback, ctx.clone(), run)
+                            .await?
+                    }
                     dyn_engine::Effect::Select(config) => EffectApplication {
                         result: if SelectorController::new(self)
                             .open(config, ctx.clone())
@@ -243,17 +254,36 @@ impl Engine {
 
         match action {
             config::Action::Shell(spec) => {
-                self.start_shell_action(
+                self.start_process_action(
                     identifier,
-                    spec.command().to_string(),
-                    spec.ok_notify(),
-                    spec.err_notify(),
+                    ProcessSpec::shell(
+                        spec.command().to_string(),
+                        spec.ok_notify(),
+                        spec.err_notify(),
+                    )
+                    .with_concurrency(spec.concurrency()),
                     repeat,
                 );
                 Ok(DispatchResult::AutoExit)
             }
             config::Action::Exec(spec) => {
-                let process = self.direct_process_spec(spec).await;
+                let mut process = self.direct_process_spec(spec).await;
+                match &spec.stdin {
+                    Some(config::ExecStdin::Text(text)) => {
+                        process = process.with_stdin(text.clone());
+                    }
+                    Some(config::ExecStdin::Clipboard) => {
+                        let text = match self.clipboard.read_text() {
+                            Ok(text) => text.unwrap_or_default(),
+                            Err(err) => {
+                                self.report_clipboard_read_failure(&err)?;
+                                return Ok(DispatchResult::AutoExit);
+                            }
+                        };
+                        process = process.with_stdin(text);
+                    }
+                    None => {}
+                }
                 self.start_process_action(identifier, process, repeat);
                 Ok(DispatchResult::AutoExit)
             }
@@ -261,6 +291,8 @@ impl Engine {
                 .apply_relay(identifier, spec, EffectRun::OneShot)
                 .await?
                 .result),
+            config::Action::RelaySequence(spec) => self.apply_relay_sequence(identifier, spec),
+            config::Action::TypeText(spec) => self.apply_type_text(spec).await,
             config::Action::ReloadConfig => {
                 if let Err(err) = self.reload_dynamic_config().await {
                     self.notifier.send_error("Config", err.to_string())?;
@@ -308,9 +340,226 @@ impl Engine {
                 self.start_warn_apple_script(identifier, mute_script(*arg), None);
                 Ok(DispatchResult::AutoExit)
             }
+            config::Action::Media(key) => {
+                if !self.relay.media(*key) {
+                    self.notifier.send_error(
+                        "Media",
+                        format!("Failed to post media key {}", key.to_spec()),
+                    )?;
+                }
+                Ok(DispatchResult::AutoExit)
+            }
+            config::Action::App(spec) => self.apply_app(identifier, spec),
+            config::Action::SetClipboard(text) => {
+                if let Err(err) = self.clipboard.write_text(text) {
+                    self.notifier
+                        .send_error("Clipboard", format!("Failed to write clipboard: {err}"))?;
+                }
+                Ok(DispatchResult::AutoExit)
+            }
+            config::Action::Focus(target) => {
+                let current = self.current_focus_snapshot();
+                self.apply_focus(*target, current.as_ref())
+            }
+            config::Action::CancelProcesses => {
+                let killed = self.repeater.cancel_all();
+                tracing::debug!(killed, "cancelled running processes");
+                Ok(DispatchResult::AutoExit)
+            }
         }
     }
 
+    /// Report a clipboard read failure; the action that needed the text is skipped.
+    fn report_clipboard_read_failure(&self, err: &Error) -> Result<()> {
+        self.notifier
+            .send_error("Clipboard", format!("Failed to read clipboard: {err}"))
+    }
+
+    /// Run a `ctx:with_clipboard` callback with the current clipboard text and apply its effects.
+    async fn run_clipboard_callback(
+        &self,
+        identifier: &str,
+        callback: dyn_engine::HandlerRef,
+        ctx: dyn_engine::ModeCtx,
+        run: EffectRun,
+    ) -> Result<EffectApplication> {
+        let text = match self.clipboard.read_text() {
+            Ok(text) => text,
+            Err(err) => {
+                self.report_clipboard_read_failure(&err)?;
+                return Ok(EffectApplication {
+                    result: DispatchResult::AutoExit,
+                    terminal: true,
+                });
+            }
+        };
+        let result = {
+            let mut cfg_guard = self.config.lock().await;
+            let Some(cfg) = cfg_guard.as_mut() else {
+                return Ok(EffectApplication {
+                    result: DispatchResult::AutoExit,
+                    terminal: true,
+                });
+            };
+            match cfg.execute_clipboard_handler(&callback, &ctx, text) {
+                Ok(result) => result,
+                Err(err) => {
+                    self.notifier.send_error("Handler", err.pretty())?;
+                    return Ok(EffectApplication {
+                        result: DispatchResult::AutoExit,
+                        terminal: true,
+                    });
+                }
+            }
+        };
+
+        let mut applied = self
+            .apply_effect_queue(identifier, result.effects, ctx, run)
+            .await?;
+        applied.result = applied.result.with_stay(result.stay);
+        Ok(applied)
+    }
+
+    /// Focus a window or application from the focus history, skipping windows that have closed
+    /// and processes that have exited.
+    fn apply_focus(
+        &self,
+        target: config::FocusTarget,
+        current: Option<&hotki_protocol::FocusSnapshot>,
+    ) -> Result<DispatchResult> {
+        if let config::FocusTarget::Window { pid, id } = target {
+            if !self.raise_history_window(pid, id) {
+                self.notifier.send_notification(
+                    config::NotifyKind::Warn,
+                    "Focus".to_string(),
+                    format!("Window {id} of the application with pid {pid} is not open"),
+                )?;
+            }
+            return Ok(DispatchResult::AutoExit);
+        }
+
+        loop {
+            let candidate = {
+                let history = self.history.lock();
+                match target {
+                    config::FocusTarget::PreviousApp => history.previous_app(current),
+                    _ => history.previous_window(current),
+                }
+            };
+            let Some(window) = candidate else {
+                self.notifier.send_notification(
+                    config::NotifyKind::Warn,
+                    "Focus".to_string(),
+                    "No previous window in focus history".to_string(),
+                )?;
+                return Ok(DispatchResult::AutoExit);
+            };
+            let focused = match target {
+                config::FocusTarget::PreviousApp => self.activate_history_pid(window.pid),
+                _ => self.raise_history_window(window.pid, window.id),
+            };
+            if focused {
+                return Ok(DispatchResult::AutoExit);
+            }
+        }
+    }
+
+    /// Raise one window in front of its application, dropping it from the focus history when it
+    /// has closed, or its whole application when the process has exited.
+    fn raise_history_window(&self, pid: i32, id: u32) -> bool {
+        let raised = self
+            .world
+            .control_application(pid, hotki_world::ApplicationCommand::RaiseWindow(id));
+        if !raised {
+            let running = matches!(
+                self.world
+                    .resolve_application(hotki_world::ApplicationQuery::Pid(pid)),
+                hotki_world::ApplicationResolution::Found(_)
+            );
+            let mut history = self.history.lock();
+            if running {
+                history.forget_window(pid, id);
+            } else {
+                history.forget_pid(pid);
+            }
+        }
+        raised
+    }
+
+    /// Activate one application, dropping it from the focus history when it is gone.
+    fn activate_history_pid(&self, pid: i32) -> bool {
+        let activated = self
+            .world
+            .control_application(pid, hotki_world::ApplicationCommand::Activate);
+        if !activated {
+            self.history.lock().forget_pid(pid);
+        }
+        activated
+    }
+
+    /// Apply one application lifecycle action, warning when the target does not resolve.
+    fn apply_app(&self, identifier: &str, spec: &config::AppSpec) -> Result<DispatchResult> {
+        let label = format!("Application \"{}\"", spec.target);
+        let resolution = self
+            .world
+            .resolve_application(hotki_world::ApplicationQuery::NameOrBundleId(&spec.target));
+        let pid = match resolution {
+            hotki_world::ApplicationResolution::Found(pid) => pid,
+            hotki_world::ApplicationResolution::NotRunning
+                if spec.command == config::AppCommand::Toggle =>
+            {
+                let flag = if spec.launches_by_bundle_id() {
+                    "-b"
+                } else {
+                    "-a"
+                };
+                self.start_process_action(
+                    identifier,
+                    ProcessSpec::new(
+                        "open",
+                        vec![flag.to_string(), spec.target.clone()],
+                        None,
+                        config::NotifyKind::Ignore,
+                        config::NotifyKind::Warn,
+                        "Application",
+                    ),
+                    None,
+                );
+                return Ok(DispatchResult::AutoExit);
+            }
+            unresolved => {
+                self.notifier.send_notification(
+                    config::NotifyKind::Warn,
+                    "Application".to_string(),
+                    unresolved_application_warning(&label, unresolved),
+                )?;
+                return Ok(DispatchResult::AutoExit);
+            }
+        };
+
+        let command = match spec.command {
+            config::AppCommand::Activate => hotki_world::ApplicationCommand::Activate,
+            config::AppCommand::Hide => hotki_world::ApplicationCommand::Hide,
+            config::AppCommand::Quit => hotki_world::ApplicationCommand::Quit,
+            config::AppCommand::Toggle => {
+                let frontmost = self.current_focus_snapshot().map(|focus| focus.pid);
+                if frontmost == Some(pid) {
+                    hotki_world::ApplicationCommand::Hide
+                } else {
+                    hotki_world::ApplicationCommand::Activate
+                }
+            }
+        };
+        if !self.world.control_application(pid, command) {
+            self.notifier.send_notification(
+                config::NotifyKind::Warn,
+                "Application".to_string(),
+                format!("{label} is not running"),
+            )?;
+        }
+        Ok(DispatchResult::AutoExit)
+    }
+
     /// Apply one relay effect, resolving a process target only at gesture start.
     async fn apply_relay(
         &self,
@@ -337,7 +586,15 @@ impl Engine {
                         terminal: true,
                     });
                 };
-                self.relay.begin(identifier.to_string(), chord, destination);
+                if spec.modifier_events {
+                    self.relay.begin_with_modifier_events(
+                        identifier.to_string(),
+                        chord,
+                        destination,
+                    );
+                } else {
+                    self.relay.begin(identifier.to_string(), chord, destination);
+                }
                 if matches!(run, EffectRun::OneShot) {
                     let _ = self.relay.end(identifier);
                 }
@@ -351,54 +608,98 @@ impl Engine {
         Ok(EffectApplication::EMPTY)
     }
 
-    /// Resolve a configured relay target without changing application state.
-    async fn resolve_relay_destination(
+    /// Start one focused relay sequence after validating every chord.
+    fn apply_relay_sequence(
         &self,
-        target: &config::RelayTarget,
-    ) -> Result<Option<relaykey::RelayDestination>> {
-        match target {
-            config::RelayTarget::Focused => Ok(Some(relaykey::RelayDestination::Hid)),
-            config::RelayTarget::ApplicationName(app_name) => {
-                match self.world.resolve_application(app_name) {
-                    hotki_world::ApplicationResolution::Found(pid) => {
-                        Ok(Some(relaykey::RelayDestination::Process(pid)))
-                    }
-                    hotki_world::ApplicationResolution::NotRunning => {
-                        self.notifier.send_notification(
-                            config::NotifyKind::Warn,
-                            "Relay".to_string(),
-                            format!("Application \"{app_name}\" is not running"),
-                        )?;
-                        Ok(None)
-                    }
-                    hotki_world::ApplicationResolution::Ambiguous(count) => {
-                        self.notifier.send_notification(
-                            config::NotifyKind::Warn,
-                            "Relay".to_string(),
-                            format!(
-                                "Application \"{app_name}\" is ambiguous: {count} running matches"
-                            ),
-                        )?;
-                        Ok(None)
+        identifier: &str,
+        spec: &config::RelaySequenceSpec,
+    ) -> Result<DispatchResult> {
+        let mut chords = Vec::with_capacity(spec.chords.len());
+        for chord in &spec.chords {
+            let Some(parsed) = Chord::parse(chord) else {
+                self.notifier
+                    .send_error("Relay", format!("Invalid relay chord string: {chord}"))?;
+                return Ok(DispatchResult::AutoExit);
+            };
+            chords.push(parsed);
+        }
+        let delay_ms = spec.delay_ms.unwrap_or(DEFAULT_SEQUENCE_DELAY_MS);
+        self.relay.sequence(
+            identifier.to_string(),
+            chords,
+            std::time::Duration::from_millis(delay_ms),
+            relaykey::RelayDestination::Hid,
+        );
+        Ok(DispatchResult::AutoExit)
+    }
+
+    /// Type one string at its resolved destination, pacing characters in the background.
+    async fn apply_type_text(&self, spec: &config::TypeTextSpec) -> Result<DispatchResult> {
+        let Some(destination) = self.resolve_relay_destination(&spec.target).await? else {
+            return Ok(DispatchResult::AutoExit);
+        };
+        let delay = match spec.delay_ms {
+            Some(delay_ms) if delay_ms > 0 => tokio::time::Duration::from_millis(delay_ms),
+            _ => {
+                if !self.relay.type_text(&spec.text, destination) {
+                    self.notifier
+                        .send_error("Type text", "Failed to post text events".to_string())?;
+                }
+                return Ok(DispatchResult::AutoExit);
+            }
+        };
+
+        let relay = self.relay.clone();
+        let text = spec.text.clone();
+        let cancel = self.background_cancellation_token();
+        let task = tokio::spawn(async move {
+            let mut buf = [0u8; 4];
+            for (idx, ch) in text.chars().enumerate() {
+                if idx > 0 {
+                    tokio::select! {
+                        () = cancel.cancelled() => return,
+                        () = tokio::time::sleep(delay) => {}
                     }
                 }
+                if !relay.type_text(ch.encode_utf8(&mut buf), destination) {
+                    return;
+                }
             }
-        }
+        });
+        self.register_background_task(task);
+        Ok(DispatchResult::AutoExit)
     }
 
-    fn start_shell_action(
+    /// Resolve a configured relay target without changing application state.
+    async fn resolve_relay_destination(
         &self,
-        identifier: &str,
-        command: String,
-        ok_notify: config::NotifyKind,
-        err_notify: config::NotifyKind,
-        repeat: Option<dyn_engine::RepeatSpec>,
-    ) {
-        self.start_process_action(
-            identifier,
-            ProcessSpec::shell(command, ok_notify, err_notify),
-            repeat,
-        );
+        target: &config::RelayTarget,
+    ) -> Result<Option<relaykey::RelayDestination>> {
+        let (query, label) = match target {
+            config::RelayTarget::Focused => return Ok(Some(relaykey::RelayDestination::Hid)),
+            config::RelayTarget::ApplicationName(app_name) => (
+                hotki_world::ApplicationQuery::Name(app_name),
+                format!("Application \"{app_name}\""),
+            ),
+            config::RelayTarget::BundleId(bundle_id) => (
+                hotki_world::ApplicationQuery::BundleId(bundle_id),
+                format!("Application with bundle id \"{bundle_id}\""),
+            ),
+            config::RelayTarget::Pid(pid) => (
+                hotki_world::ApplicationQuery::Pid(*pid),
+                format!("Application with pid {pid}"),
+            ),
+        };
+        let resolution = self.world.resolve_application(query);
+        if let hotki_world::ApplicationResolution::Found(pid) = resolution {
+            return Ok(Some(relaykey::RelayDestination::Process(pid)));
+        }
+        self.notifier.send_notification(
+            config::NotifyKind::Warn,
+            "Relay".to_string(),
+            unresolved_application_warning(&label, resolution),
+        )?;
+        Ok(None)
     }
 
     fn start_process_action(
@@ -420,6 +721,9 @@ impl Engine {
             spec.err_notify,
             "Process",
         )
+        .with_env(spec.env.clone(), spec.clear_env)
+        .with_timeout(spec.timeout_ms.map(std::time::Duration::from_millis))
+        .with_concurrency(spec.concurrency)
     }
 
     async fn resolve_exec_cwd(&self, cwd: Option<&str>) -> Option<PathBuf> {
@@ -512,6 +816,19 @@ fn repeat_spec(repeat: Option<dyn_engine::RepeatSpec>) -> Option<RepeatSpec> {
     })
 }
 
+/// Warning text for an application query that did not resolve to exactly one process.
+fn unresolved_application_warning(
+    label: &str,
+    resolution: hotki_world::ApplicationResolution,
+) -> String {
+    match resolution {
+        hotki_world::ApplicationResolution::Ambiguous(count) => {
+            format!("{label} is ambiguous: {count} running matches")
+        }
+        _ => format!("{label} is not running"),
+    }
+}
+
 fn apple_script_process(script: String) -> ProcessSpec {
     let args = script
         .split('\n')
@@ -527,7 +844,7 @@ fn apple_script_process(script: String) -> ProcessSpec {
     )
 }
 
-fn config_entry_directory(path: &Path) -> PathBuf {
+pub(crate) fn config_entry_directory(path: &Path) -> PathBuf {
     let path = if path.is_absolute() {
         path.to_path_buf()
     } else {
@@ -582,6 +899,7 @@ mod tests {
     fn application(name: &str, pid: i32) -> TestApplication {
         TestApplication {
             name: Some(name.to_string()),
+            bundle_id: None,
             pid,
             terminated: false,
         }
@@ -621,6 +939,11 @@ mod tests {
             cwd: Some("child".to_string()),
             ok_notify: NotifyKind::Info,
             err_notify: NotifyKind::Warn,
+            stdin: None,
+            env: Default::default(),
+            clear_env: false,
+            timeout_ms: None,
+            concurrency: config::ProcessConcurrency::Parallel,
         });
         engine
             .apply_action("pwd", &action, None)
@@ -710,6 +1033,217 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn bundle_and_pid_targets_resolve_or_warn_fail_closed() {
+        let world = Arc::new(TestWorld::new());
+        let mut helper = application("Helper", 81);
+        helper.bundle_id = Some("com.example.helper".to_string());
+        let mut twin = application("Helper", 82);
+        twin.bundle_id = Some("com.example.twin".to_string());
+        let mut twin_copy = application("Helper", 83);
+        twin_copy.bundle_id = Some("com.example.twin".to_string());
+        world.set_running_applications(vec![helper, twin, twin_copy]);
+        let (engine, mut rx) = relay_engine(world);
+
+        for (target, expected) in [
+            (
+                config::RelayTarget::BundleId("com.example.helper".to_string()),
+                Some(relaykey::RelayDestination::Process(81)),
+            ),
+            (
+                config::RelayTarget::Pid(82),
+                Some(relaykey::RelayDestination::Process(82)),
+            ),
+        ] {
+            assert_eq!(
+                engine
+                    .resolve_relay_destination(&target)
+                    .await
+                    .expect("resolve destination"),
+                expected
+            );
+        }
+        assert!(rx.try_recv().is_err(), "resolved targets must not warn");
+
+        for (target, expected) in [
+            (
+                config::RelayTarget::BundleId("com.example.missing".to_string()),
+                "Application with bundle id \"com.example.missing\" is not running",
+            ),
+            (
+                config::RelayTarget::BundleId("com.example.twin".to_string()),
+                "Application with bundle id \"com.example.twin\" is ambiguous: 2 running matches",
+            ),
+            (
+                config::RelayTarget::Pid(99),
+                "Application with pid 99 is not running",
+            ),
+        ] {
+            assert_eq!(
+                engine
+                    .resolve_relay_destination(&target)
+                    .await
+                    .expect("resolve destination"),
+                None
+            );
+            let message = rx.try_recv().expect("warning notification");
+            assert!(matches!(
+                message,
+                MsgToUI::Notify {
+                    kind: NotifyKind::Warn,
+                    title,
+                    text,
+                    ..
+                } if title == "Relay" && text == expected
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn app_actions_apply_lifecycle_commands_and_warn_fail_closed() {
+        use hotki_world::ApplicationCommand;
+
+        let world = Arc::new(TestWorld::new());
+        let mut music = application("Music", 91);
+        music.bundle_id = Some("com.apple.Music".to_string());
+        world.set_running_applications(vec![
+            music,
+            application("Notes", 92),
+            application("Twin", 93),
+            application("Twin", 94),
+        ]);
+        let (engine, mut rx) = relay_engine(world.clone());
+        *engine.focus_ctx.lock() = Some(hotki_protocol::FocusSnapshot {
+            id: 1,
+            app: "Notes".to_string(),
+            title: "Notes".to_string(),
+            pid: 92,
+            display_id: None,
+        });
+        let app = |target: &str, command| config::AppSpec {
+            target: target.to_string(),
+            command,
+        };
+
+        for spec in [
+            app("com.apple.Music", config::AppCommand::Activate),
+            app("Music", config::AppCommand::Hide),
+            app("Notes", config::AppCommand::Toggle),
+            app("Music", config::AppCommand::Toggle),
+            app("Music", config::AppCommand::Quit),
+        ] {
+            engine.apply_app("app", &spec).expect("apply app action");
+        }
+        assert!(rx.try_recv().is_err(), "resolved targets must not warn");
+        assert_eq!(
+            world.application_commands(),
+            vec![
+                (91, ApplicationCommand::Activate),
+                (91, ApplicationCommand::Hide),
+                (92, ApplicationCommand::Hide),
+                (91, ApplicationCommand::Activate),
+                (91, ApplicationCommand::Quit),
+            ]
+        );
+
+        for (spec, expected) in [
+            (
+                app("Music", config::AppCommand::Activate),
+                "Application \"Music\" is not running",
+            ),
+            (
+                app("Twin", config::AppCommand::Toggle),
+                "Application \"Twin\" is ambiguous: 2 running matches",
+            ),
+        ] {
+            engine.apply_app("app", &spec).expect("apply app action");
+            let message = rx.try_recv().expect("warning notification");
+            assert!(matches!(
+                message,
+                MsgToUI::Notify {
+                    kind: NotifyKind::Warn,
+                    title,
+                    text,
+                    ..
+                } if title == "Application" && text == expected
+            ));
+        }
+        assert_eq!(world.application_commands().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn focus_actions_raise_history_windows_and_prune_closed_ones() {
+        use hotki_world::{ApplicationCommand, WindowKey, WorldWindow};
+
+        let world = Arc::new(TestWorld::new());
+        world.set_running_applications(vec![application("Editor", 1), application("Browser", 2)]);
+        let open = |pid: i32, id: u32| WorldWindow {
+            app: format!("App {pid}"),
+            title: format!("Window {id}"),
+            pid,
+            id,
+            display_id: None,
+            focused: id == 10,
+        };
+        world.set_snapshot(
+            vec![open(1, 10), open(1, 12), open(2, 20)],
+            Some(WindowKey { pid: 1, id: 10 }),
+        );
+        let (engine, mut rx) = relay_engine(world.clone());
+        let focus = |pid: i32, id: u32| hotki_world::focus_snapshot(&open(pid, id));
+        {
+            let mut history = engine.history.lock();
+            for (pid, id) in [(2, 20), (1, 12), (1, 11), (1, 10)] {
+                history.record(&focus(pid, id));
+            }
+        }
+        let current = focus(1, 10);
+
+        engine
+            .apply_focus(config::FocusTarget::PreviousWindow, Some(&current))
+            .expect("focus previous window");
+        engine
+            .apply_focus(config::FocusTarget::PreviousApp, Some(&current))
+            .expect("focus previous app");
+        assert!(
+            rx.try_recv().is_err(),
+            "resolved focus targets must not warn"
+        );
+        assert_eq!(
+            world.application_commands(),
+            vec![
+                (1, ApplicationCommand::RaiseWindow(12)),
+                (2, ApplicationCommand::Activate),
+            ]
+        );
+        assert!(
+            engine
+                .history
+                .lock()
+                .windows()
+                .iter()
+                .all(|window| window.id != 11),
+            "a closed window should leave the history"
+        );
+
+        engine
+            .apply_focus(
+                config::FocusTarget::Window { pid: 3, id: 30 },
+                Some(&current),
+            )
+            .expect("focus missing window");
+        let message = rx.try_recv().expect("warning notification");
+        assert!(matches!(
+            message,
+            MsgToUI::Notify {
+                kind: NotifyKind::Warn,
+                title,
+                text,
+                ..
+            } if title == "Focus" && text == "Window 30 of the application with pid 3 is not open"
+        ));
+    }
+
     #[tokio::test]
     async fn focused_and_targeted_relays_share_invalid_chord_diagnostics() {
         for spec in [
@@ -0,0 +1,468 @@
+//! Clipboard history sampled from the pasteboard change count.
+//!
+//! History stays in memory unless the config opts into persistence, in which case entries are
+//! sealed with ChaCha20-Poly1305 under a key kept in the login Keychain.
+
+use std::{
+    collections::VecDeque,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use config::ClipboardHistoryConfig;
+use ring::{
+    aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+use security_framework::passwords::{get_generic_password, set_generic_password};
+use tokio::task::spawn_blocking;
+use tracing::warn;
+
+use crate::{Engine, Error, Result, deps::Clipboard};
+
+/// Interval between pasteboard change-count samples.
+pub(crate) const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Keychain service holding the history encryption key.
+const KEYCHAIN_SERVICE: &str = "hotki.clipboard-history";
+/// Keychain account holding the history encryption key.
+const KEYCHAIN_ACCOUNT: &str = "encryption-key";
+/// Security framework status for a missing Keychain item.
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+/// Length of the ChaCha20-Poly1305 key.
+const KEY_LEN: usize = 32;
+/// Permissions of the history file: readable and writable by the owner only.
+const FILE_MODE: u32 = 0o600;
+
+/// Text entries copied while clipboard history is enabled.
+#[derive(Default)]
+pub(crate) struct ClipboardHistory {
+    /// Active settings, or `None` while history is disabled.
+    config: Option<ClipboardHistoryConfig>,
+    /// Entries, most recent first, unique by text.
+    entries: VecDeque<String>,
+    /// Change count observed by the previous sample.
+    last_change: Option<i64>,
+    /// Encrypted file backing a persistent history.
+    store: Option<Arc<EncryptedStore>>,
+    /// Whether a sampler task is currently running.
+    sampler_running: bool,
+}
+
+impl ClipboardHistory {
+    /// Whether applying `config` needs an [`OpenedStore`] opened first.
+    pub(crate) fn needs_store(&self, config: Option<&ClipboardHistoryConfig>) -> bool {
+        config.is_some_and(|config| config.persist) && self.store.is_none()
+    }
+
+    /// Apply settings from a newly installed config.
+    ///
+    /// Disabling history drops every entry. Enabling persistence adopts `opened`, placing its
+    /// entries ahead of anything already recorded in memory; without one history stays in
+    /// memory. Disabling persistence leaves the file untouched.
+    pub(crate) fn configure(
+        &mut self,
+        config: Option<ClipboardHistoryConfig>,
+        opened: Option<OpenedStore>,
+    ) {
+        let Some(config) = config else {
+            *self = Self {
+                sampler_running: self.sampler_running,
+                ..Self::default()
+            };
+            return;
+        };
+        if !config.persist {
+            self.store = None;
+        } else if self.store.is_none()
+            && let Some(opened) = opened
+        {
+            self.entries.extend(opened.entries);
+            self.store = Some(opened.store);
+        }
+        self.config = Some(config);
+        self.truncate();
+    }
+
+    /// Whether the config currently enables history.
+    pub(crate) fn enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// Mark a sampler task as started, returning `false` when one is already running.
+    pub(crate) fn claim_sampler(&mut self) -> bool {
+        !std::mem::replace(&mut self.sampler_running, true)
+    }
+
+    /// Record that the sampler task has exited.
+    pub(crate) fn release_sampler(&mut self) {
+        self.sampler_running = false;
+    }
+
+    /// Snapshot of the entries, most recent first.
+    pub(crate) fn entries(&self) -> Vec<String> {
+        self.entries.iter().cloned().collect()
+    }
+
+    /// Record the clipboard text if the change count moved since the previous sample.
+    ///
+    /// The first sample only establishes a baseline, so text copied before history was enabled
+    /// is not recorded. Exclusion checks `focused_app`, the application focused when the change
+    /// is sampled; a copy followed by a switch to another application within
+    /// [`SAMPLE_INTERVAL`] is attributed to the application switched to. Returns whether the
+    /// entries changed; persisting them is left to the caller via [`Self::pending_save`].
+    pub(crate) fn sample(
+        &mut self,
+        clipboard: &dyn Clipboard,
+        focused_app: Option<&str>,
+    ) -> Result<bool> {
+        let Some(config) = &self.config else {
+            return Ok(false);
+        };
+        let change = clipboard.change_count();
+        let previous = self.last_change.replace(change);
+        if previous.is_none_or(|previous| previous == change) {
+            return Ok(false);
+        }
+        if focused_app.is_some_and(|app| config.excludes_app(app)) {
+            return Ok(false);
+        }
+        let Some(text) = clipboard.read_text()? else {
+            return Ok(false);
+        };
+        if text.trim().is_empty() {
+            return Ok(false);
+        }
+        self.entries.retain(|entry| *entry != text);
+        self.entries.push_front(text);
+        self.truncate();
+        Ok(true)
+    }
+
+    /// Store and entries to write after a change, or `None` when history is memory only.
+    pub(crate) fn pending_save(&self) -> Option<(Arc<EncryptedStore>, Vec<String>)> {
+        let store = self.store.as_ref()?;
+        Some((Arc::clone(store), self.entries()))
+    }
+
+    /// Drop entries beyond the configured capacity.
+    fn truncate(&mut self) {
+        if let Some(config) = &self.config {
+            self.entries.truncate(config.capacity);
+        }
+    }
+}
+
+impl Engine {
+    /// Apply the clipboard history settings of a committed config.
+    ///
+    /// The Keychain and the history file are read on a blocking thread before the history lock
+    /// is taken. A persistence failure is reported and history falls back to memory only. The
+    /// sampler task starts the first time history is enabled and exits once a config disables
+    /// it.
+    pub(crate) async fn configure_clipboard_history(&self, config: Option<ClipboardHistoryConfig>) {
+        let mut opened = None;
+        if self.clipboard_history.lock().needs_store(config.as_ref()) {
+            match spawn_blocking(OpenedStore::open_default).await {
+                Ok(Ok(store)) => opened = Some(store),
+                Ok(Err(err)) => self.report_persistence_failure(&err),
+                Err(err) => self.report_persistence_failure(&Error::Msg(err.to_string())),
+            }
+        }
+        let start = {
+            let mut history = self.clipboard_history.lock();
+            history.configure(config, opened);
+            history.enabled() && history.claim_sampler()
+        };
+        if start {
+            self.spawn_clipboard_sampler();
+        }
+    }
+
+    /// Tell the user that persistence failed and history is kept in memory only.
+    fn report_persistence_failure(&self, err: &Error) {
+        if let Err(notify_err) = self
+            .notifier
+            .send_error("Clipboard history", format!("Persistence disabled: {err}"))
+        {
+            warn!(
+                "Clipboard history error notification failed: {}",
+                notify_err
+            );
+        }
+    }
+
+    /// Poll the pasteboard change count until history is disabled or the engine shuts down.
+    fn spawn_clipboard_sampler(&self) {
+        let engine = self.clone_for_background();
+        let cancel = self.background_cancellation_token();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+            loop {
+                tokio::select! {
+                    () = cancel.cancelled() => return,
+                    _ = interval.tick() => {}
+                }
+                if !engine.sample_clipboard().await {
+                    return;
+                }
+            }
+        });
+        self.register_background_task(task);
+    }
+
+    /// Take one clipboard sample, returning `false` once history has been disabled.
+    ///
+    /// A changed persistent history is written on a blocking thread after the lock is released.
+    pub(crate) async fn sample_clipboard(&self) -> bool {
+        let app = self.current_focus_snapshot().map(|focus| focus.app);
+        let pending = {
+            let mut history = self.clipboard_history.lock();
+            if !history.enabled() {
+                history.release_sampler();
+                return false;
+            }
+            match history.sample(self.clipboard.as_ref(), app.as_deref()) {
+                Ok(true) => history.pending_save(),
+                Ok(false) => None,
+                Err(err) => {
+                    warn!("Clipboard history sample failed: {}", err);
+                    None
+                }
+            }
+        };
+        if let Some((store, entries)) = pending {
+            match spawn_blocking(move || store.save(&entries)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => warn!("Clipboard history save failed: {}", err),
+                Err(err) => warn!("Clipboard history save task failed: {}", err),
+            }
+        }
+        true
+    }
+
+    /// Snapshot clipboard history entries, most recent first.
+    pub(crate) fn clipboard_entries(&self) -> Vec<String> {
+        self.clipboard_history.lock().entries()
+    }
+}
+
+/// Persistent store opened off the history lock, with the entries it held.
+pub(crate) struct OpenedStore {
+    /// Store to adopt.
+    store: Arc<EncryptedStore>,
+    /// Entries loaded from the store, most recent first.
+    entries: Vec<String>,
+}
+
+impl OpenedStore {
+    /// Open the per-user store and load its entries; blocks on the Keychain and the file.
+    fn open_default() -> Result<Self> {
+        let store = EncryptedStore::open_default()?;
+        let entries = store.load()?;
+        Ok(Self {
+            store: Arc::new(store),
+            entries,
+        })
+    }
+}
+
+/// History file sealed with a ChaCha20-Poly1305 key.
+pub(crate) struct EncryptedStore {
+    /// File holding the nonce followed by the sealed JSON entry list.
+    path: PathBuf,
+    /// Sealing key.
+    key: LessSafeKey,
+}
+
+impl EncryptedStore {
+    /// Open the per-user history file with the Keychain-held key, creating the key if needed.
+    fn open_default() -> Result<Self> {
+        let home = env::var_os("HOME").ok_or_else(|| Error::Msg("HOME is not set".to_string()))?;
+        let path = PathBuf::from(home).join("Library/Application Support/hotki/clipboard-history");
+        Self::new(path, &keychain_key()?)
+    }
+
+    /// Build a store for `path` sealed with `key`.
+    fn new(path: PathBuf, key: &[u8]) -> Result<Self> {
+        let key = UnboundKey::new(&CHACHA20_POLY1305, key)
+            .map_err(|_| Error::Msg("invalid clipboard history key".to_string()))?;
+        Ok(Self {
+            path,
+            key: LessSafeKey::new(key),
+        })
+    }
+
+    /// Load stored entries; a missing file is an empty history.
+    fn load(&self) -> Result<Vec<String>> {
+        let sealed = match fs::read(&self.path) {
+            Ok(sealed) => sealed,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        if sealed.len() < NONCE_LEN {
+            return Err(corrupt(&self.path));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| corrupt(&self.path))?;
+        let mut buffer = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut buffer)
+            .map_err(|_| corrupt(&self.path))?;
+        serde_json::from_slice(plaintext).map_err(|_| corrupt(&self.path))
+    }
+
+    /// Replace the stored entries, keeping the file private to the owner.
+    fn save(&self, entries: &[String]) -> Result<()> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| Error::Msg("failed to generate a nonce".to_string()))?;
+        let mut buffer = serde_json::to_vec(entries)
+            .map_err(|err| Error::Msg(format!("failed to encode clipboard history: {err}")))?;
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut buffer,
+            )
+            .map_err(|_| Error::Msg("failed to seal clipboard history".to_string()))?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&buffer);
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let staging = self.path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(FILE_MODE)
+            .open(&staging)?;
+        file.set_permissions(fs::Permissions::from_mode(FILE_MODE))?;
+        file.write_all(&sealed)?;
+        drop(file);
+        fs::rename(&staging, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Error for a history file that cannot be decrypted or decoded.
+fn corrupt(path: &Path) -> Error {
+    Error::Msg(format!(
+        "clipboard history file {} is unreadable",
+        path.display()
+    ))
+}
+
+/// Fetch the history key from the login Keychain, generating and storing one on first use.
+fn keychain_key() -> Result<Vec<u8>> {
+    match get_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT) {
+        Ok(key) if key.len() == KEY_LEN => return Ok(key),
+        Ok(_) => {
+            return Err(Error::Msg(
+                "clipboard history key has the wrong length".to_string(),
+            ));
+        }
+        Err(err) if err.code() == ERR_SEC_ITEM_NOT_FOUND => {}
+        Err(err) => return Err(Error::Msg(format!("Keychain read failed: {err}"))),
+    }
+    let mut key = vec![0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| Error::Msg("failed to generate a clipboard history key".to_string()))?;
+    set_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, &key)
+        .map_err(|err| Error::Msg(format!("Keychain write failed: {err}")))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deps::MemoryClipboard;
+
+    fn enabled(config: ClipboardHistoryConfig) -> ClipboardHistory {
+        let mut history = ClipboardHistory::default();
+        history.configure(Some(config), None);
+        history
+    }
+
+    #[test]
+    fn sampler_records_new_copies_once_and_skips_excluded_apps() {
+        let clipboard = MemoryClipboard::with_text("before enable");
+        let mut history = enabled(ClipboardHistoryConfig {
+            capacity: 2,
+            exclude_apps: vec!["^1Password".to_string()],
+            persist: false,
+        });
+
+        assert!(
+            !history
+                .sample(&clipboard, Some("Editor"))
+                .expect("baseline")
+        );
+        assert!(
+            !history
+                .sample(&clipboard, Some("Editor"))
+                .expect("unchanged")
+        );
+
+        clipboard.set_text(Some("one"));
+        assert!(history.sample(&clipboard, Some("Editor")).expect("copy"));
+        assert!(
+            !history
+                .sample(&clipboard, Some("Editor"))
+                .expect("same count")
+        );
+
+        clipboard.set_text(Some("secret"));
+        assert!(
+            !history
+                .sample(&clipboard, Some("1Password 7"))
+                .expect("excluded")
+        );
+        clipboard.set_text(None);
+        assert!(!history.sample(&clipboard, Some("Editor")).expect("no text"));
+
+        clipboard.set_text(Some("two"));
+        history.sample(&clipboard, None).expect("copy");
+        clipboard.set_text(Some("one"));
+        history.sample(&clipboard, None).expect("copy again");
+        clipboard.set_text(Some("three"));
+        history.sample(&clipboard, None).expect("copy");
+        assert_eq!(history.entries(), vec!["three", "one"]);
+
+        history.configure(None, None);
+        assert!(!history.enabled());
+        assert!(history.entries().is_empty());
+    }
+
+    #[test]
+    fn encrypted_store_round_trips_and_rejects_other_keys() {
+        let dir = env::temp_dir().join(format!("hotki-clipboard-history-{}", std::process::id()));
+        let path = dir.join("history");
+        let store = EncryptedStore::new(path.clone(), &[7; KEY_LEN]).expect("store");
+        assert!(store.load().expect("missing file").is_empty());
+
+        let entries = vec!["secret text".to_string(), "two".to_string()];
+        store.save(&entries).expect("save");
+        let mode = fs::metadata(&path)
+            .expect("stat sealed file")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, FILE_MODE);
+        let sealed = fs::read(&path).expect("read sealed file");
+        assert!(!String::from_utf8_lossy(&sealed).contains("secret text"));
+        assert_eq!(store.load().expect("load"), vec!["secret text", "two"]);
+
+        let other = EncryptedStore::new(path, &[8; KEY_LEN]).expect("store");
+        assert!(other.load().is_err());
+        let _ignored = fs::remove_dir_all(&dir);
+    }
+}
@@ -2,7 +2,11 @@ use std::sync::Arc;
 #[cfg(test)]
 use std::sync::atomic::{AtomicU32, Ordering};
 
-use crate::Result;
+use chrono::{Local, NaiveDateTime, TimeDelta, TimeZone};
+use objc2_app_kit::{NSPasteboard, NSPasteboardTypeString};
+use objc2_foundation::NSString;
+
+use crate::{Error, Result};
 
 /// Capture-all guard returned by the hotkey API.
 pub(crate) enum CaptureGuard {
@@ -82,3 +86,157 @@ impl HotkeyApi for MockHotkeyApi {
         CaptureGuard::Fake
     }
 }
+
+/// Plain-text system clipboard used by clipboard actions and clipboard history.
+pub(crate) trait Clipboard: Send + Sync {
+    /// Read the clipboard as plain text, or `None` when it holds no text.
+    fn read_text(&self) -> Result<Option<String>>;
+    /// Replace the clipboard contents with plain text.
+    fn write_text(&self, text: &str) -> Result<()>;
+    /// Counter that changes whenever any application changes the clipboard contents.
+    fn change_count(&self) -> i64;
+}
+
+/// Clipboard backed by the AppKit general pasteboard.
+pub(crate) struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn read_text(&self) -> Result<Option<String>> {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        // SAFETY: `NSPasteboardTypeString` is an immutable AppKit constant initialized when
+        // AppKit loads, so reading it has no data race.
+        let text = pasteboard.stringForType(unsafe { NSPasteboardTypeString });
+        Ok(text.map(|text| text.to_string()))
+    }
+
+    fn write_text(&self, text: &str) -> Result<()> {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        // SAFETY: `NSPasteboardTypeString` is an immutable AppKit constant, as in `read_text`.
+        let string_type = unsafe { NSPasteboardTypeString };
+        if !pasteboard.setString_forType(&NSString::from_str(text), string_type) {
+            return Err(Error::Msg("pasteboard rejected the text".to_string()));
+        }
+        Ok(())
+    }
+
+    fn change_count(&self) -> i64 {
+        NSPasteboard::generalPasteboard().changeCount() as i64
+    }
+}
+
+/// In-memory clipboard for tests.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryClipboard {
+    /// Current text and change count.
+    state: parking_lot::Mutex<(Option<String>, i64)>,
+}
+
+#[cfg(test)]
+impl MemoryClipboard {
+    /// Create a clipboard holding `text`.
+    pub(crate) fn with_text(text: &str) -> Self {
+        let clipboard = Self::default();
+        clipboard.set_text(Some(text));
+        clipboard
+    }
+
+    /// Replace the contents as another application would, bumping the change count.
+    pub(crate) fn set_text(&self, text: Option<&str>) {
+        let mut state = self.state.lock();
+        state.0 = text.map(str::to_string);
+        state.1 += 1;
+    }
+
+    /// Current clipboard text.
+    pub(crate) fn text(&self) -> Option<String> {
+        self.state.lock().0.clone()
+    }
+}
+
+#[cfg(test)]
+impl Clipboard for MemoryClipboard {
+    fn read_text(&self) -> Result<Option<String>> {
+        Ok(self.text())
+    }
+
+    fn write_text(&self, text: &str) -> Result<()> {
+        self.set_text(Some(text));
+        Ok(())
+    }
+
+    fn change_count(&self) -> i64 {
+        self.state.lock().1
+    }
+}
+
+/// Local wall-clock time source for scheduled actions.
+pub(crate) trait WallClock: Send + Sync {
+    /// Current local date and time.
+    fn now(&self) -> NaiveDateTime;
+
+    /// First local minute at or after `local` that exists, skipping a daylight-saving gap.
+    fn first_valid(&self, local: NaiveDateTime) -> NaiveDateTime;
+
+    /// Current offset of local time from UTC, which drops when daylight saving ends.
+    fn utc_offset(&self) -> TimeDelta;
+}
+
+/// Wall clock backed by the system time and time zone.
+pub(crate) struct SystemWallClock;
+
+impl WallClock for SystemWallClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+
+    fn first_valid(&self, local: NaiveDateTime) -> NaiveDateTime {
+        // Gaps last at most a few hours, so a day bounds the search.
+        (0..24 * 60)
+            .map(|minute| local + TimeDelta::minutes(minute))
+            .find(|time| Local.from_local_datetime(time).earliest().is_some())
+            .unwrap_or(local)
+    }
+
+    fn utc_offset(&self) -> TimeDelta {
+        TimeDelta::seconds(i64::from(Local::now().offset().local_minus_utc()))
+    }
+}
+
+/// Wall clock that tests set explicitly.
+#[cfg(test)]
+pub(crate) struct ManualClock {
+    /// Current reported time.
+    now: parking_lot::Mutex<NaiveDateTime>,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    /// Create a clock reporting `now`.
+    pub(crate) fn at(now: NaiveDateTime) -> Self {
+        Self {
+            now: parking_lot::Mutex::new(now),
+        }
+    }
+
+    /// Move the clock to `now`, forwards or backwards.
+    pub(crate) fn set(&self, now: NaiveDateTime) {
+        *self.now.lock() = now;
+    }
+}
+
+#[cfg(test)]
+impl WallClock for ManualClock {
+    fn now(&self) -> NaiveDateTime {
+        *self.now.lock()
+    }
+
+    fn first_valid(&self, local: NaiveDateTime) -> NaiveDateTime {
+        local
+    }
+
+    fn utc_offset(&self) -> TimeDelta {
+        TimeDelta::zero()
+    }
+}
@@ -41,13 +41,14 @@ impl Engine {
         }
 
         let (binding, ctx, press_feedback) = {
-            let rt = self.runtime.lock().await;
+            let mut rt = self.runtime.lock().await;
             let Some(binding) =
                 dyn_engine::ConfigRuntime::resolve_binding(&rt.rendered, chord).cloned()
             else {
                 trace!("No binding for chord {}", chord);
                 return Ok(());
             };
+            rt.clipboard_history = self.clipboard_entries();
             let ctx = rt.mode_ctx(&focus);
             let press_feedback = allow_press_feedback
                 && rt.hud_visible
@@ -0,0 +1,120 @@
+//! Most-recently-used focus history maintained across config reloads.
+
+use std::collections::VecDeque;
+
+use hotki_protocol::FocusSnapshot;
+
+/// Maximum number of windows retained in the focus history.
+const HISTORY_CAPACITY: usize = 64;
+
+/// Focused windows ordered from most to least recently focused.
+#[derive(Debug, Default)]
+pub(crate) struct FocusHistory {
+    /// History entries, most recent first, unique by pid and window id.
+    windows: VecDeque<FocusSnapshot>,
+}
+
+impl FocusHistory {
+    /// Move `focus` to the front of the history, replacing any older entry for the same window.
+    pub(crate) fn record(&mut self, focus: &FocusSnapshot) {
+        self.windows.retain(|window| !same_window(window, focus));
+        self.windows.push_front(focus.clone());
+        self.windows.truncate(HISTORY_CAPACITY);
+    }
+
+    /// Drop every entry owned by `pid`.
+    pub(crate) fn forget_pid(&mut self, pid: i32) {
+        self.windows.retain(|window| window.pid != pid);
+    }
+
+    /// Drop the entry for window `id` of `pid`.
+    pub(crate) fn forget_window(&mut self, pid: i32, id: u32) {
+        self.windows
+            .retain(|window| window.pid != pid || window.id != id);
+    }
+
+    /// Snapshot of the history, most recent first.
+    pub(crate) fn windows(&self) -> Vec<FocusSnapshot> {
+        self.windows.iter().cloned().collect()
+    }
+
+    /// Most recent window other than `current`.
+    pub(crate) fn previous_window(&self, current: Option<&FocusSnapshot>) -> Option<FocusSnapshot> {
+        self.windows
+            .iter()
+            .find(|window| current.is_none_or(|current| !same_window(window, current)))
+            .cloned()
+    }
+
+    /// Most recent window owned by an application other than `current`'s.
+    pub(crate) fn previous_app(&self, current: Option<&FocusSnapshot>) -> Option<FocusSnapshot> {
+        self.windows
+            .iter()
+            .find(|window| current.is_none_or(|current| window.pid != current.pid))
+            .cloned()
+    }
+}
+
+/// Whether two snapshots identify the same window.
+fn same_window(a: &FocusSnapshot, b: &FocusSnapshot) -> bool {
+    a.pid == b.pid && a.id == b.id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(pid: i32, id: u32, app: &str) -> FocusSnapshot {
+        FocusSnapshot {
+            id,
+            app: app.to_string(),
+            title: format!("{app} {id}"),
+            pid,
+            display_id: None,
+        }
+    }
+
+    #[test]
+    fn record_moves_existing_windows_to_front_and_caps_length() {
+        let mut history = FocusHistory::default();
+        history.record(&window(1, 10, "Editor"));
+        history.record(&window(2, 20, "Browser"));
+        let mut renamed = window(1, 10, "Editor");
+        renamed.title = "Renamed".to_string();
+        history.record(&renamed);
+
+        let windows = history.windows();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].title, "Renamed");
+        assert_eq!(windows[1].pid, 2);
+
+        for id in 0..(HISTORY_CAPACITY as u32 + 8) {
+            history.record(&window(3, 100 + id, "Terminal"));
+        }
+        assert_eq!(history.windows().len(), HISTORY_CAPACITY);
+    }
+
+    #[test]
+    fn previous_lookups_skip_the_current_window_or_app() {
+        let mut history = FocusHistory::default();
+        history.record(&window(2, 20, "Browser"));
+        history.record(&window(1, 11, "Editor"));
+        history.record(&window(1, 10, "Editor"));
+        let current = window(1, 10, "Editor");
+
+        assert_eq!(
+            history.previous_window(Some(&current)).map(|w| w.id),
+            Some(11)
+        );
+        assert_eq!(history.previous_app(Some(&current)).map(|w| w.pid), Some(2));
+        assert_eq!(history.previous_app(None).map(|w| w.pid), Some(1));
+
+        history.forget_window(1, 11);
+        assert_eq!(
+            history.previous_window(Some(&current)).map(|w| w.id),
+            Some(20)
+        );
+        history.forget_pid(2);
+        assert_eq!(history.previous_app(Some(&current)), None);
+    }
+}
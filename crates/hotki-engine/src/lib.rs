@@ -21,10 +21,14 @@
 //!   bindings; other actions operate on the event-maintained focus cache.
 //! - A transient mode-stack session pins its opening window while live focus
 //!   continues updating for the next session.
+//! - Focus changes outside mode sessions and selectors feed an engine-owned
+//!   most-recently-used history, so Hotki's own HUD windows never enter it and
+//!   config reloads keep it.
 //! - Early startup policy: if the world snapshot is empty, focus-driven
 //!   actions are a no-op with a debug log.
-//! - Focused relays use the global HID path. Application-name relays resolve
-//!   once at gesture start and remain pinned through repeat and key-up.
+//! - Focused relays use the global HID path. Application relays (by name, bundle
+//!   id, or captured pid) resolve once at gesture start and remain pinned
+//!   through repeat and key-up.
 //!
 //! Concurrency and Lock Ordering
 //! - The engine uses a handful of locks. To avoid deadlocks and priority
@@ -35,6 +39,11 @@
 //! - `focus_ctx` uses `parking_lot::Mutex` for the event-maintained focus cache.
 //!   Never hold this guard across an `.await`. Clone/copy values out and drop
 //!   the guard before awaiting.
+//! - `history` uses `parking_lot::Mutex` for the focus history. It is a leaf lock:
+//!   it may be taken while holding `runtime`, but never acquire another lock or
+//!   await while holding it.
+//! - `clipboard_history` and `schedules` use `parking_lot::Mutex` and are leaf
+//!   locks with the same rules as `history`.
 //! - Service calls (`world`, `repeater`, `relay`, `notifier`) must
 //!   not be awaited while any of the async engine mutexes are held. Acquire,
 //!   compute, drop guards, then perform async work.
@@ -53,17 +62,21 @@ use std::{
 };
 
 mod actions;
+mod clipboard_history;
 mod deps;
 mod dispatch;
 mod error;
+mod history;
 mod key_binding;
 mod key_state;
 mod notification;
 mod refresh;
 mod repeater;
 mod runtime;
+mod schedule;
 mod selector;
 mod selector_controller;
+mod services;
 mod ticker;
 mod world_sync;
 
@@ -127,10 +140,15 @@ impl DispatchResult {
     }
 }
 
+use clipboard_history::ClipboardHistory;
 use config::runtime as dyn_engine;
 use deps::RealHotkeyApi;
 pub use error::{Error, Result};
-use hotki_protocol::{DisplaysSnapshot, MsgToUI};
+use history::FocusHistory;
+use hotki_protocol::{
+    DisplaysSnapshot, MsgToUI,
+    rpc::{ChordExplanation, ProcessInfo, ServiceStatus},
+};
 use hotki_world::WorldView;
 use key_binding::KeyBindingManager;
 use key_state::KeyStateTracker;
@@ -140,6 +158,8 @@ use parking_lot::Mutex;
 pub(crate) use repeater::OnRelayRepeat;
 pub use repeater::RepeatSpec;
 use repeater::Repeater;
+use schedule::Scheduler;
+use services::ServiceSupervisor;
 use ticker::Ticker;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -275,6 +295,10 @@ pub struct Engine {
     config_path: Arc<tokio::sync::RwLock<Option<PathBuf>>>,
     /// Cached focus snapshot from World events.
     focus_ctx: Arc<Mutex<Option<hotki_protocol::FocusSnapshot>>>,
+    /// Most-recently-used focus history, retained across config reloads.
+    history: Arc<Mutex<FocusHistory>>,
+    /// Clipboard history sampled while the config enables it, retained across reloads.
+    clipboard_history: Arc<Mutex<ClipboardHistory>>,
     /// If true, refresh world state before dispatch; else trust cached context.
     sync_on_dispatch: bool,
     /// Last displays snapshot sent to the UI.
@@ -289,10 +313,18 @@ pub struct Engine {
     repeater: Repeater,
     /// Repeater for Luau action closures created by `ctx:until_keyup`.
     action_repeater: Ticker,
+    /// Background services declared by the committed config.
+    services: ServiceSupervisor,
+    /// Wall-clock scheduled actions declared by the committed config.
+    schedules: Arc<Mutex<Scheduler>>,
     /// Serializes candidate preparation and committed refreshes.
     config_transaction: Arc<tokio::sync::Mutex<()>>,
     /// World view for focus and display tracking.
     world: Arc<dyn WorldView>,
+    /// Plain-text clipboard for clipboard actions.
+    clipboard: Arc<dyn deps::Clipboard>,
+    /// Local wall clock for scheduled actions.
+    clock: Arc<dyn deps::WallClock>,
 }
 
 impl Engine {
@@ -306,7 +338,14 @@ impl Engine {
     ) -> Self {
         let api = Arc::new(RealHotkeyApi::new(manager));
         let world = hotki_world::World::spawn_default_view(hotki_world::WorldCfg::default());
-        Self::build(api, event_tx, true, true, world)
+        Self::build(
+            api,
+            event_tx,
+            true,
+            true,
+            world,
+            Arc::new(deps::SystemClipboard),
+        )
     }
 
     /// Construct an engine around test-owned platform and world adapters.
@@ -317,7 +356,28 @@ impl Engine {
         relay_enabled: bool,
         world: Arc<dyn WorldView>,
     ) -> Self {
-        Self::build(api, event_tx, relay_enabled, false, world)
+        Self::build(
+            api,
+            event_tx,
+            relay_enabled,
+            false,
+            world,
+            Arc::new(deps::MemoryClipboard::default()),
+        )
+    }
+
+    /// Replace the clipboard adapter on a test engine.
+    #[cfg(test)]
+    pub(crate) fn with_clipboard(mut self, clipboard: Arc<dyn deps::Clipboard>) -> Self {
+        self.clipboard = clipboard;
+        self
+    }
+
+    /// Replace the wall clock on a test engine.
+    #[cfg(test)]
+    pub(crate) fn with_clock(mut self, clock: Arc<dyn deps::WallClock>) -> Self {
+        self.clock = clock;
+        self
     }
 
     fn build(
@@ -326,6 +386,7 @@ impl Engine {
         relay_enabled: bool,
         sync_on_dispatch: bool,
         world: Arc<dyn WorldView>,
+        clipboard: Arc<dyn deps::Clipboard>,
     ) -> Self {
         let binding_manager_arc = Arc::new(tokio::sync::Mutex::new(
             KeyBindingManager::new_with_api(api),
@@ -351,6 +412,8 @@ impl Engine {
             config: config_arc,
             config_path: Arc::new(tokio::sync::RwLock::new(None)),
             focus_ctx,
+            history: Arc::new(Mutex::new(FocusHistory::default())),
+            clipboard_history: Arc::new(Mutex::new(ClipboardHistory::default())),
             sync_on_dispatch,
             display_snapshot: Arc::new(tokio::sync::Mutex::new(DisplaysSnapshot::default())),
             relay,
@@ -358,8 +421,12 @@ impl Engine {
             selector_notify,
             repeater,
             action_repeater,
+            services: ServiceSupervisor::default(),
+            schedules: Arc::new(Mutex::new(Scheduler::default())),
             config_transaction: Arc::new(tokio::sync::Mutex::new(())),
             world,
+            clipboard,
+            clock: Arc::new(deps::SystemWallClock),
         };
         engine.spawn_world_focus_subscription();
         engine.spawn_selector_notify_task();
@@ -411,6 +478,38 @@ impl Engine {
     pub fn world_status(&self) -> hotki_world::WorldStatus {
         self.world.status()
     }
+
+    /// Processes started by bindings that are still running, oldest first.
+    pub fn running_processes(&self) -> Vec<ProcessInfo> {
+        self.repeater.processes()
+    }
+
+    /// Kill one running process without a notification, dropping runs queued behind it; false
+    /// when it is no longer running.
+    pub fn cancel_process(&self, run: u64) -> bool {
+        self.repeater.cancel_run(run)
+    }
+
+    /// Status of every service declared by the committed config, in declaration order.
+    pub fn service_statuses(&self) -> Vec<ServiceStatus> {
+        self.services.statuses()
+    }
+
+    /// Explain how `chord` would resolve against the live mode stack and focused window.
+    pub async fn explain_chord(&self, chord: &mac_keycode::Chord) -> Result<ChordExplanation> {
+        let _transaction = self.config_transaction.lock().await;
+        let focus = self.current_focus_snapshot();
+        let (stack, ctx, hud_visible) = {
+            let rt = self.runtime.lock().await;
+            (rt.stack.clone(), rt.mode_ctx(&focus), rt.hud_visible)
+        };
+        let mut cfg_guard = self.config.lock().await;
+        let Some(cfg) = cfg_guard.as_mut() else {
+            return Err(Error::Msg("no config loaded".to_string()));
+        };
+        cfg.explain(&stack, &ctx, hud_visible, chord)
+            .map_err(|err| Error::Msg(err.pretty()))
+    }
 }
 
 impl Drop for Engine {
@@ -419,6 +518,7 @@ impl Drop for Engine {
             self.lifecycle.shutdown();
             self.repeater.abort_all();
             self.action_repeater.abort_all();
+            self.services.abort_all();
             self.relay.release_all();
         }
     }
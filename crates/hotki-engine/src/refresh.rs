@@ -110,7 +110,13 @@ impl RenderRecovery {
         rt: &mut RuntimeState,
         cfg: &mut dyn_engine::ConfigRuntime,
     ) -> (Vec<dyn_engine::Effect>, Vec<String>) {
-        let mut ctx = mode_ctx(&rt.focus, rt.hud_visible, rt.depth());
+        let mut ctx = mode_ctx(
+            &rt.focus,
+            rt.hud_visible,
+            rt.depth(),
+            &rt.recent_windows,
+            &rt.clipboard_history,
+        );
         if let Some(warnings) = self.try_render(rt, cfg, &ctx) {
             return (warnings, self.errors);
         }
@@ -153,6 +159,7 @@ pub(crate) fn hud_state_for_ui_from_state(rt: &RuntimeState) -> hotki_protocol::
         breadcrumbs: rt.stack.breadcrumbs(),
         style: rt.rendered.style.clone(),
         capture: rt.hud_visible && rt.rendered.capture,
+        badge: rt.rendered.badge.clone(),
     }
 }
 
@@ -172,6 +179,8 @@ impl Engine {
             }
         };
         let mut runtime = RuntimeState::empty();
+        runtime.recent_windows = self.recent_windows();
+        runtime.clipboard_history = self.clipboard_entries();
         runtime.hud_visible = hud_visible;
         runtime.focus = focus.clone();
         if hud_visible {
@@ -208,6 +217,10 @@ impl Engine {
             hud,
         } = plan;
 
+        let clipboard_history = config.clipboard_history();
+        let services = config.services();
+        let schedules = config.schedules();
+        let service_dir = crate::actions::config_entry_directory(&path);
         let mut config_guard = self.config.lock().await;
         let mut runtime_guard = self.runtime.lock().await;
         let mut manager = self.binding_manager.lock().await;
@@ -232,6 +245,9 @@ impl Engine {
         drop(runtime_guard);
         drop(config_guard);
 
+        self.configure_clipboard_history(clipboard_history).await;
+        self.services.replace(services, &service_dir).await;
+        self.configure_schedules(schedules);
         if bindings_changed {
             tracing::debug!("bindings updated, clearing repeater + relay");
             self.repeater.stop_repeats_async().await;
@@ -255,6 +271,8 @@ impl Engine {
         let mut manager = self.binding_manager.lock().await;
         let mut display_guard = self.display_snapshot.lock().await;
         let permit = self.notifier.reserve_ui()?;
+        runtime_guard.recent_windows = self.recent_windows();
+        runtime_guard.clipboard_history = self.clipboard_entries();
         let checkpoint = runtime_guard.checkpoint();
         let rollback = checkpoint.clone();
         let rollback_focus = runtime_guard.focus.clone();
@@ -6,22 +6,25 @@
 
 use std::{
     cmp,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     env, io,
     os::unix::process::CommandExt,
     path::{Component, Path, PathBuf},
     process::Stdio,
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
     },
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use config::NotifyKind;
+use config::{NotifyKind, ProcessConcurrency};
+use hotki_protocol::rpc::ProcessInfo;
 use parking_lot::Mutex;
 use tokio::{
-    io::{AsyncRead, AsyncReadExt},
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
     process::Command,
+    sync::Notify,
     task::JoinHandle,
     time::Duration,
 };
@@ -56,6 +59,28 @@ fn process_failure(
     }
 }
 
+/// Report a process killed by its timeout as an error.
+///
+/// The notification is titled "<title> timed out" so it cannot be mistaken for the process's own
+/// failure output. Silent runs and `err_notify = "ignore"` stay quiet.
+fn process_timeout(
+    spec: &ProcessSpec,
+    notify: ProcessNotify,
+    output: String,
+) -> Option<ProcessNotification> {
+    let timeout = spec.timeout.unwrap_or_default();
+    let mut text = format!("Killed {} after {} ms", spec.program, timeout.as_millis());
+    if !output.is_empty() {
+        text.push('\n');
+        text.push_str(&output);
+    }
+    process_failure(spec, notify, text).map(|notification| ProcessNotification {
+        kind: NotifyKind::Error,
+        title: format!("{} timed out", spec.title),
+        ..notification
+    })
+}
+
 #[derive(Debug, Clone, Copy)]
 enum ProcessNotify {
     Configured {
@@ -74,7 +99,14 @@ struct CapturedStream {
     truncated: bool,
 }
 
-async fn read_bounded(mut reader: impl AsyncRead + Unpin) -> io::Result<CapturedStream> {
+/// Interleaved output of one run, readable while the process is still going.
+type LiveOutput = Arc<Mutex<Vec<u8>>>;
+
+async fn read_bounded(
+    mut reader: impl AsyncRead + Unpin,
+    live: LiveOutput,
+    changed: Arc<Notify>,
+) -> io::Result<CapturedStream> {
     let mut bytes = Vec::with_capacity(PROCESS_STREAM_LIMIT_BYTES);
     let mut truncated = false;
     let mut chunk = [0_u8; 4096];
@@ -86,6 +118,8 @@ async fn read_bounded(mut reader: impl AsyncRead + Unpin) -> io::Result<Captured
         let remaining = PROCESS_STREAM_LIMIT_BYTES.saturating_sub(bytes.len());
         let retained = remaining.min(read);
         bytes.extend_from_slice(&chunk[..retained]);
+        live.lock().extend_from_slice(&chunk[..retained]);
+        changed.notify_waiters();
         truncated |= retained < read;
     }
     Ok(CapturedStream { bytes, truncated })
@@ -121,7 +155,8 @@ fn trim_process_output(stdout: CapturedStream, stderr: CapturedStream) -> String
     output
 }
 
-fn kill_process_group(pid: u32) {
+/// Kill the process group led by `pid`, ignoring groups that have already exited.
+pub(crate) fn kill_process_group(pid: u32) {
     let Ok(pid) = i32::try_from(pid) else {
         tracing::warn!(pid, "process child pid exceeded process identifier range");
         return;
@@ -176,10 +211,14 @@ fn is_bare_program(program: &str) -> bool {
 }
 
 /// Run one process in an owned process group with bounded output capture.
+///
+/// The run is listed in `runs` under `binding` from spawn until the process is reaped.
 async fn run_process(
     spec: &ProcessSpec,
     notify: ProcessNotify,
     state: &ProcessRunState,
+    runs: &ProcessRuns,
+    binding: &str,
 ) -> Option<ProcessNotification> {
     tracing::info!(
         program = %spec.program,
@@ -200,6 +239,13 @@ async fn run_process(
     if let Some(cwd) = &spec.cwd {
         command_builder.current_dir(cwd);
     }
+    if spec.clear_env {
+        command_builder.env_clear();
+    }
+    command_builder.envs(&spec.env);
+    if spec.stdin.is_some() {
+        command_builder.stdin(Stdio::piped());
+    }
     command_builder
         .as_std_mut()
         .process_group(0)
@@ -241,15 +287,47 @@ async fn run_process(
         );
     };
     state.pid.store(pid, Ordering::SeqCst);
-    let stdout_task = child
-        .stdout
-        .take()
-        .map(|stdout| tokio::spawn(read_bounded(stdout)));
-    let stderr_task = child
-        .stderr
-        .take()
-        .map(|stderr| tokio::spawn(read_bounded(stderr)));
-
+    let live_output = LiveOutput::default();
+    let run_cancel = CancellationToken::new();
+    let run = runs.insert(LiveRun {
+        binding: binding.to_string(),
+        command: spec.command_line(),
+        pid,
+        started: SystemTime::now(),
+        output: live_output.clone(),
+        cancel: run_cancel.clone(),
+    });
+    if let (Some(mut stdin), Some(text)) = (child.stdin.take(), spec.stdin.clone()) {
+        tokio::spawn(async move {
+            // A child that exits without reading its input closes the pipe, so this is not a
+            // failure of the run.
+            if let Err(err) = stdin.write_all(text.as_bytes()).await {
+                tracing::debug!(error = %err, "failed to write process stdin");
+            }
+        });
+    }
+    let stdout_task = child.stdout.take().map(|stdout| {
+        tokio::spawn(read_bounded(
+            stdout,
+            live_output.clone(),
+            Arc::clone(&runs.changed),
+        ))
+    });
+    let stderr_task = child.stderr.take().map(|stderr| {
+        tokio::spawn(read_bounded(
+            stderr,
+            live_output.clone(),
+            Arc::clone(&runs.changed),
+        ))
+    });
+
+    let deadline = async {
+        match spec.timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    let mut timed_out = false;
     let status = tokio::select! {
         status = child.wait() => {
             kill_process_group(pid);
@@ -260,13 +338,27 @@ async fn run_process(
             let _ = child.wait().await;
             None
         }
+        () = run_cancel.cancelled() => {
+            kill_process_group(pid);
+            let _ = child.wait().await;
+            None
+        }
+        () = deadline => {
+            kill_process_group(pid);
+            timed_out = true;
+            Some(child.wait().await)
+        }
     };
     state.pid.store(0, Ordering::SeqCst);
+    runs.remove(run);
     let stdout = collect_stream(stdout_task).await;
     let stderr = collect_stream(stderr_task).await;
-    if state.cancel.is_cancelled() {
+    if state.cancel.is_cancelled() || run_cancel.is_cancelled() {
         return None;
     }
+    if timed_out {
+        return process_timeout(spec, notify, trim_process_output(stdout, stderr));
+    }
     let status = status?;
     let status = match status {
         Ok(status) => status,
@@ -322,6 +414,16 @@ pub(crate) struct ProcessSpec {
     pub(crate) err_notify: NotifyKind,
     /// Notification title for this process class.
     pub(crate) title: &'static str,
+    /// Text written to standard input, which is otherwise inherited.
+    pub(crate) stdin: Option<String>,
+    /// Environment variables set for the process.
+    pub(crate) env: BTreeMap<String, String>,
+    /// Start from an empty environment instead of inheriting Hotki's.
+    pub(crate) clear_env: bool,
+    /// Run time after which the process group is killed.
+    pub(crate) timeout: Option<Duration>,
+    /// Policy for a press while an earlier one-shot run of the same binding is in flight.
+    pub(crate) concurrency: ProcessConcurrency,
 }
 
 impl ProcessSpec {
@@ -341,9 +443,54 @@ impl ProcessSpec {
             ok_notify,
             err_notify,
             title,
+            stdin: None,
+            env: BTreeMap::new(),
+            clear_env: false,
+            timeout: None,
+            concurrency: ProcessConcurrency::Parallel,
         }
     }
 
+    /// Write `text` to the process's standard input and then close it.
+    pub(crate) fn with_stdin(mut self, text: String) -> Self {
+        self.stdin = Some(text);
+        self
+    }
+
+    /// Set `env` on top of the inherited environment, or on an empty one when `clear_env`.
+    pub(crate) fn with_env(mut self, env: BTreeMap<String, String>, clear_env: bool) -> Self {
+        self.env = env;
+        self.clear_env = clear_env;
+        self
+    }
+
+    /// Kill the process group once the process has run for `timeout`.
+    pub(crate) fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Apply `concurrency` to presses while an earlier run is in flight.
+    pub(crate) fn with_concurrency(mut self, concurrency: ProcessConcurrency) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Program and arguments joined for display, quoting arguments that need it.
+    fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .map(|part| {
+                if part.is_empty() || part.contains(|c: char| c.is_whitespace() || c == '\'') {
+                    format!("'{}'", part.replace('\'', "'\\''"))
+                } else {
+                    part.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// Construct the shell-language process using the inherited shell choice.
     pub(crate) fn shell(command: String, ok_notify: NotifyKind, err_notify: NotifyKind) -> Self {
         let shell = env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
@@ -376,6 +523,9 @@ pub struct RepeatSpec {
 ///   allowing the next tick to execute. This guarantees that at most one process
 ///   is in-flight per id, and that the first blocking run effectively
 ///   defers the start of repeating until it finishes.
+/// - One-shot runs with a non-parallel [`ProcessConcurrency`] keep their state in the map
+///   across key release until `pending` drains, so later presses can see the run in flight.
+///   Queued runs wait on `gate` in press order.
 struct ProcessRunState {
     /// Async mutex to serialize execution across initial run and repeats.
     gate: tokio::sync::Mutex<()>,
@@ -383,32 +533,49 @@ struct ProcessRunState {
     running: AtomicBool,
     /// Cancels the running process group and suppresses its notification.
     cancel: CancellationToken,
-    /// Current owned task; at most one process runs per binding id.
-    task: Mutex<Option<JoinHandle<()>>>,
+    /// Owned tasks; at most one of them runs a process at a time.
+    tasks: Mutex<Vec<JoinHandle<()>>>,
     /// Process-group leader while a command is running.
     pid: AtomicU32,
     /// Whether key release cancels the current child process.
     cancel_on_release: bool,
+    /// Whether the state outlives key release to enforce a concurrency policy.
+    exclusive: bool,
+    /// One-shot runs started or queued and not yet finished; changed under the state map lock.
+    pending: AtomicUsize,
 }
 
 impl ProcessRunState {
-    fn set_task(&self, task: JoinHandle<()>) {
-        if let Some(previous) = self.task.lock().replace(task) {
-            debug_assert!(previous.is_finished());
+    fn new(cancel_on_release: bool, exclusive: bool) -> Self {
+        Self {
+            gate: tokio::sync::Mutex::new(()),
+            running: AtomicBool::new(false),
+            cancel: CancellationToken::new(),
+            tasks: Mutex::new(Vec::new()),
+            pid: AtomicU32::new(0),
+            cancel_on_release,
+            exclusive,
+            pending: AtomicUsize::new(0),
         }
     }
 
+    fn add_task(&self, task: JoinHandle<()>) {
+        let mut tasks = self.tasks.lock();
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(task);
+    }
+
     async fn stop(&self) {
         self.cancel.cancel();
-        let task = self.task.lock().take();
-        if let Some(task) = task {
+        let tasks = std::mem::take(&mut *self.tasks.lock());
+        for task in tasks {
             let _ = task.await;
         }
     }
 
     fn abort(&self) {
         self.cancel.cancel();
-        let _ = self.task.lock().take();
+        self.tasks.lock().clear();
         let pid = self.pid.swap(0, Ordering::SeqCst);
         if pid != 0 {
             kill_process_group(pid);
@@ -416,10 +583,88 @@ impl ProcessRunState {
     }
 }
 
+/// One spawned process that has not been reaped yet.
+struct LiveRun {
+    /// Key identifier of the binding that started the run.
+    binding: String,
+    /// Program and arguments joined for display.
+    command: String,
+    /// Process-group leader.
+    pid: u32,
+    /// Wall-clock spawn time.
+    started: SystemTime,
+    /// Output captured so far.
+    output: LiveOutput,
+    /// Kills this run alone and suppresses its notification.
+    cancel: CancellationToken,
+}
+
+/// Registry of running processes across all bindings, keyed by run identifier.
+#[derive(Clone, Default)]
+struct ProcessRuns {
+    /// Last issued run identifier; identifiers are not reused.
+    last: Arc<AtomicU64>,
+    /// Runs in spawn order.
+    live: Arc<Mutex<BTreeMap<u64, LiveRun>>>,
+    /// Woken whenever a run is spawned, produces output, or is reaped, and whenever a one-shot
+    /// run task finishes.
+    changed: Arc<Notify>,
+}
+
+impl ProcessRuns {
+    fn insert(&self, run: LiveRun) -> u64 {
+        let id = self.last.fetch_add(1, Ordering::SeqCst) + 1;
+        self.live.lock().insert(id, run);
+        self.changed.notify_waiters();
+        id
+    }
+
+    fn remove(&self, id: u64) {
+        self.live.lock().remove(&id);
+        self.changed.notify_waiters();
+    }
+
+    fn list(&self) -> Vec<ProcessInfo> {
+        self.live
+            .lock()
+            .iter()
+            .map(|(&id, run)| ProcessInfo {
+                run: id,
+                binding: run.binding.clone(),
+                command: run.command.clone(),
+                pid: run.pid,
+                started_ms: run
+                    .started
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_millis() as u64)
+                    .unwrap_or_default(),
+                output: String::from_utf8_lossy(&run.output.lock()).into_owned(),
+            })
+            .collect()
+    }
+
+    /// Cancel one run, returning the binding that started it.
+    fn cancel(&self, id: u64) -> Option<String> {
+        let live = self.live.lock();
+        let run = live.get(&id)?;
+        run.cancel.cancel();
+        Some(run.binding.clone())
+    }
+
+    fn cancel_all(&self) -> usize {
+        let live = self.live.lock();
+        for run in live.values() {
+            run.cancel.cancel();
+        }
+        live.len()
+    }
+}
+
 struct ProcessRunContext {
     notifier: NotificationDispatcher,
     on_process_repeat: Arc<Mutex<Option<OnProcessRepeat>>>,
     process_states: Arc<Mutex<HashMap<String, Arc<ProcessRunState>>>>,
+    runs: ProcessRuns,
 }
 
 impl ProcessRunContext {
@@ -435,20 +680,30 @@ impl ProcessRunContext {
         let notifier = self.notifier.clone();
         let on_process_repeat = self.on_process_repeat.clone();
         let process_states = self.process_states.clone();
+        let runs = self.runs.clone();
         let task = tokio::spawn(async move {
             let _guard = state.gate.lock().await;
-            let notification = run_process(&spec, notify, state.as_ref()).await;
-            state.task.lock().take();
+            // A restart cancels queued runs of the replaced state before they spawn anything.
+            let notification = if state.cancel.is_cancelled() {
+                None
+            } else {
+                run_process(&spec, notify, state.as_ref(), &runs, &state_id).await
+            };
             state.running.store(false, Ordering::SeqCst);
 
-            if repeat_id.is_none() && !state.cancel_on_release {
+            if repeat_id.is_none() {
                 let mut states = process_states.lock();
-                if states
-                    .get(&state_id)
-                    .is_some_and(|current| Arc::ptr_eq(current, &state))
+                let idle = state.pending.fetch_sub(1, Ordering::SeqCst) == 1;
+                if idle
+                    && !state.cancel_on_release
+                    && states
+                        .get(&state_id)
+                        .is_some_and(|current| Arc::ptr_eq(current, &state))
                 {
                     states.remove(&state_id);
                 }
+                drop(states);
+                runs.changed.notify_waiters();
             }
 
             if let Some(notification) = notification
@@ -469,7 +724,7 @@ impl ProcessRunContext {
                 trace!("repeater_process_run_done" = %id);
             }
         });
-        task_state.set_task(task);
+        task_state.add_task(task);
     }
 }
 
@@ -492,6 +747,8 @@ pub struct Repeater {
     on_process_repeat: Arc<Mutex<Option<OnProcessRepeat>>>,
     /// Per-id state for process execution serialization.
     process_states: Arc<Mutex<HashMap<String, Arc<ProcessRunState>>>>,
+    /// Running processes across all ids.
+    runs: ProcessRuns,
 }
 
 impl Repeater {
@@ -507,25 +764,17 @@ impl Repeater {
             on_relay_repeat: Arc::new(Mutex::new(None)),
             on_process_repeat: Arc::new(Mutex::new(None)),
             process_states: Arc::new(Mutex::new(HashMap::new())),
+            runs: ProcessRuns::default(),
         }
     }
 
     /// Get or create the per-id process run state.
     fn process_state(&self, id: &str, cancel_on_release: bool) -> Arc<ProcessRunState> {
-        let mut map = self.process_states.lock();
-        if let Some(s) = map.get(id) {
-            return s.clone();
-        }
-        let state = Arc::new(ProcessRunState {
-            gate: tokio::sync::Mutex::new(()),
-            running: AtomicBool::new(false),
-            cancel: CancellationToken::new(),
-            task: Mutex::new(None),
-            pid: AtomicU32::new(0),
-            cancel_on_release,
-        });
-        map.insert(id.to_string(), state.clone());
-        state
+        self.process_states
+            .lock()
+            .entry(id.to_string())
+            .or_insert_with(|| Arc::new(ProcessRunState::new(cancel_on_release, false)))
+            .clone()
     }
 
     pub(crate) fn effective_timings(&self, spec: Option<RepeatSpec>) -> (Duration, Duration) {
@@ -573,6 +822,7 @@ impl Repeater {
             notifier: self.notifier.clone(),
             on_process_repeat: self.on_process_repeat.clone(),
             process_states: self.process_states.clone(),
+            runs: self.runs.clone(),
         }
         .spawn(state, spec, notify, repeat_id, state_id);
     }
@@ -600,6 +850,10 @@ impl Repeater {
     ///
     /// Runs the first action immediately and schedules repeats if provided.
     pub fn start(&self, id: String, process: ProcessSpec, repeat: Option<RepeatSpec>) {
+        if repeat.is_none() && process.concurrency != ProcessConcurrency::Parallel {
+            self.start_exclusive_process(id, process);
+            return;
+        }
         self.ticker.abort(&id);
         if let Some(state) = self.process_states.lock().remove(&id) {
             state.abort();
@@ -630,8 +884,43 @@ impl Repeater {
         }
     }
 
+    /// Start a one-shot run under its binding's ignore, restart, or queue policy.
+    fn start_exclusive_process(&self, id: String, spec: ProcessSpec) {
+        let state = {
+            let mut states = self.process_states.lock();
+            let in_flight = states
+                .get(&id)
+                .filter(|state| state.pending.load(Ordering::SeqCst) > 0)
+                .cloned();
+            match (spec.concurrency, in_flight) {
+                (ProcessConcurrency::Ignore, Some(_)) => {
+                    trace!("repeater_process_press_ignored" = %id);
+                    return;
+                }
+                (ProcessConcurrency::Restart, Some(previous)) => {
+                    states.remove(&id);
+                    previous.abort();
+                }
+                _ => {}
+            }
+            let state = states
+                .entry(id.clone())
+                .or_insert_with(|| Arc::new(ProcessRunState::new(false, true)))
+                .clone();
+            state.pending.fetch_add(1, Ordering::SeqCst);
+            state
+        };
+        state.running.store(true, Ordering::SeqCst);
+        let notify = ProcessNotify::Configured {
+            ok_notify: spec.ok_notify,
+            err_notify: spec.err_notify,
+        };
+        self.spawn_process_run(state, spec, notify, None, id);
+    }
+
     fn start_initial_process(&self, id: &str, spec: &ProcessSpec, cancel_on_release: bool) {
         let state = self.process_state(id, cancel_on_release);
+        state.pending.fetch_add(1, Ordering::SeqCst);
         state.running.store(true, Ordering::SeqCst);
         self.spawn_process_run(
             state,
@@ -652,6 +941,7 @@ impl Repeater {
             notifier: self.notifier.clone(),
             on_process_repeat: self.on_process_repeat.clone(),
             process_states: self.process_states.clone(),
+            runs: self.runs.clone(),
         };
         self.spawn_repeat_loop(id, repeat, move || {
             if state.running.swap(true, Ordering::SeqCst) {
@@ -676,7 +966,14 @@ impl Repeater {
     /// Stop any software repeats for `id`.
     pub async fn stop(&self, id: &str) {
         self.ticker.stop(id).await;
-        let state = self.process_states.lock().remove(id);
+        let state = {
+            let mut states = self.process_states.lock();
+            if states.get(id).is_some_and(|state| state.exclusive) {
+                None
+            } else {
+                states.remove(id)
+            }
+        };
         if let Some(state) = state
             && state.cancel_on_release
         {
@@ -709,6 +1006,55 @@ impl Repeater {
         }
     }
 
+    /// Running processes across all bindings, oldest first.
+    pub(crate) fn processes(&self) -> Vec<ProcessInfo> {
+        self.runs.list()
+    }
+
+    /// Kill one running process group without a notification.
+    ///
+    /// Returns false when `run` is no longer running. Runs of the same binding queued behind it
+    /// are dropped.
+    pub(crate) fn cancel_run(&self, run: u64) -> bool {
+        let Some(binding) = self.runs.cancel(run) else {
+            return false;
+        };
+        let queued = {
+            let mut states = self.process_states.lock();
+            if states.get(&binding).is_some_and(|state| state.exclusive) {
+                states.remove(&binding)
+            } else {
+                None
+            }
+        };
+        if let Some(state) = queued {
+            state.cancel.cancel();
+        }
+        true
+    }
+
+    /// Kill every running process group and drop queued runs, returning the number killed.
+    ///
+    /// Held repeats keep ticking until their key is released.
+    pub(crate) fn cancel_all(&self) -> usize {
+        let queued = {
+            let mut states = self.process_states.lock();
+            let exclusive = states
+                .iter()
+                .filter(|&(_id, state)| state.exclusive)
+                .map(|(id, _state)| id.clone())
+                .collect::<Vec<_>>();
+            exclusive
+                .into_iter()
+                .filter_map(|id| states.remove(&id))
+                .collect::<Vec<_>>()
+        };
+        for state in queued {
+            state.cancel.cancel();
+        }
+        self.runs.cancel_all()
+    }
+
     /// Abort all repeat tasks during synchronous owner teardown.
     pub(crate) fn abort_all(&self) {
         self.ticker.abort_all();
@@ -733,10 +1079,21 @@ mod tests {
 
     use super::*;
 
+    /// Wait until `done` holds, re-checking each time the process registry changes.
+    async fn settle(repeater: &Repeater, done: impl Fn(&Repeater) -> bool) {
+        loop {
+            let changed = repeater.runs.changed.notified();
+            if done(repeater) {
+                return;
+            }
+            changed.await;
+        }
+    }
+
     #[tokio::test]
     async fn process_output_capture_is_bounded_and_marks_truncation() {
         let input = vec![b'x'; PROCESS_STREAM_LIMIT_BYTES + 4096];
-        let capture = read_bounded(Cursor::new(input))
+        let capture = read_bounded(Cursor::new(input), LiveOutput::default(), Arc::default())
             .await
             .expect("read capture");
 
@@ -926,6 +1283,188 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn process_environment_stdin_and_timeout_are_applied() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let notifier = crate::notification::NotificationDispatcher::new(tx);
+        let repeater = Repeater::new(notifier);
+
+        repeater.start(
+            "cleared-env".to_string(),
+            ProcessSpec::new(
+                "/bin/sh",
+                vec![
+                    "-c".to_string(),
+                    "printf '%s|%s|' \"$GREETING\" \"${HOME:-unset}\"; cat".to_string(),
+                ],
+                None,
+                NotifyKind::Info,
+                NotifyKind::Warn,
+                "Process",
+            )
+            .with_env(
+                BTreeMap::from([("GREETING".to_string(), "hi".to_string())]),
+                true,
+            )
+            .with_stdin("input".to_string()),
+            None,
+        );
+        let env = rx.recv().await.expect("environment notification");
+        assert!(
+            matches!(
+                &env,
+                hotki_protocol::MsgToUI::Notify {
+                    kind: NotifyKind::Info,
+                    text,
+                    ..
+                } if text == "hi|unset|input"
+            ),
+            "unexpected environment notification: {env:?}"
+        );
+
+        let started = Instant::now();
+        repeater.start(
+            "timed-out".to_string(),
+            ProcessSpec::new(
+                "/bin/sh",
+                vec!["-c".to_string(), "echo started; sleep 30".to_string()],
+                None,
+                NotifyKind::Info,
+                NotifyKind::Warn,
+                "Process",
+            )
+            .with_timeout(Some(Duration::from_millis(100))),
+            None,
+        );
+        let timeout = rx.recv().await.expect("timeout notification");
+        assert!(started.elapsed() < StdDuration::from_secs(10));
+        assert!(
+            matches!(
+                &timeout,
+                hotki_protocol::MsgToUI::Notify {
+                    kind: NotifyKind::Error,
+                    title,
+                    text,
+                } if title == "Process timed out"
+                    && text == "Killed /bin/sh after 100 ms\nstarted"
+            ),
+            "unexpected timeout notification: {timeout:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrency_policies_hold_across_key_release() {
+        fn script(id: &str, policy: ProcessConcurrency, body: &str) -> (String, ProcessSpec) {
+            let spec = ProcessSpec::new(
+                "/bin/sh",
+                vec!["-c".to_string(), body.to_string()],
+                None,
+                NotifyKind::Info,
+                NotifyKind::Warn,
+                "Process",
+            )
+            .with_concurrency(policy);
+            (id.to_string(), spec)
+        }
+        async fn press(repeater: &Repeater, (id, spec): (String, ProcessSpec)) {
+            repeater.start(id.clone(), spec, None);
+            repeater.stop(&id).await;
+        }
+        async fn texts(
+            rx: &mut mpsc::Receiver<hotki_protocol::MsgToUI>,
+            count: usize,
+        ) -> Vec<String> {
+            let mut texts = Vec::new();
+            while texts.len() < count {
+                match rx.recv().await.expect("process notification") {
+                    hotki_protocol::MsgToUI::Notify { text, .. } => texts.push(text),
+                    other => panic!("unexpected message: {other:?}"),
+                }
+            }
+            texts
+        }
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let notifier = crate::notification::NotificationDispatcher::new(tx);
+        let repeater = Repeater::new(notifier);
+
+        press(
+            &repeater,
+            script(
+                "ignore",
+                ProcessConcurrency::Ignore,
+                "sleep 0.3; echo first",
+            ),
+        )
+        .await;
+        press(
+            &repeater,
+            script("ignore", ProcessConcurrency::Ignore, "echo second"),
+        )
+        .await;
+        assert_eq!(texts(&mut rx, 1).await, vec!["first"]);
+        settle(&repeater, |repeater| {
+            !repeater.process_states.lock().contains_key("ignore")
+        })
+        .await;
+        press(
+            &repeater,
+            script("ignore", ProcessConcurrency::Ignore, "echo third"),
+        )
+        .await;
+        assert_eq!(texts(&mut rx, 1).await, vec!["third"]);
+
+        press(
+            &repeater,
+            script(
+                "restart",
+                ProcessConcurrency::Restart,
+                "sleep 5; echo stale",
+            ),
+        )
+        .await;
+        press(
+            &repeater,
+            script("restart", ProcessConcurrency::Restart, "echo fresh"),
+        )
+        .await;
+        assert_eq!(texts(&mut rx, 1).await, vec!["fresh"]);
+
+        press(
+            &repeater,
+            script("queue", ProcessConcurrency::Queue, "sleep 0.2; echo one"),
+        )
+        .await;
+        press(
+            &repeater,
+            script("queue", ProcessConcurrency::Queue, "echo two"),
+        )
+        .await;
+        press(
+            &repeater,
+            script("queue", ProcessConcurrency::Queue, "echo three"),
+        )
+        .await;
+        assert_eq!(texts(&mut rx, 3).await, vec!["one", "two", "three"]);
+
+        press(
+            &repeater,
+            script(
+                "parallel",
+                ProcessConcurrency::Parallel,
+                "sleep 0.3; echo slow",
+            ),
+        )
+        .await;
+        press(
+            &repeater,
+            script("parallel", ProcessConcurrency::Parallel, "echo fast"),
+        )
+        .await;
+        assert_eq!(texts(&mut rx, 2).await, vec!["fast", "slow"]);
+        assert!(rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn one_shot_process_state_is_removed_after_completion() {
         let (tx, mut rx) = mpsc::channel(16);
@@ -955,6 +1494,74 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn running_processes_are_listed_and_cancelled_silently() {
+        async fn wait_for(repeater: &Repeater, done: impl Fn(&[ProcessInfo]) -> bool) {
+            settle(repeater, |repeater| done(&repeater.processes())).await;
+        }
+        fn script(body: &str) -> ProcessSpec {
+            ProcessSpec::new(
+                "/bin/sh",
+                vec!["-c".to_string(), body.to_string()],
+                None,
+                NotifyKind::Info,
+                NotifyKind::Warn,
+                "Process",
+            )
+        }
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let notifier = crate::notification::NotificationDispatcher::new(tx);
+        let repeater = Repeater::new(notifier);
+
+        repeater.start("cmd+l".to_string(), script("echo started; sleep 30"), None);
+        wait_for(&repeater, |runs| {
+            runs.first().is_some_and(|run| run.output == "started\n")
+        })
+        .await;
+        let listed = repeater.processes();
+        assert_eq!(listed.len(), 1);
+        let run = &listed[0];
+        assert_eq!(run.binding, "cmd+l");
+        assert_eq!(run.command, "/bin/sh -c 'echo started; sleep 30'");
+        assert_ne!(run.pid, 0);
+        assert!(run.started_ms > 0);
+
+        assert!(repeater.cancel_run(run.run));
+        wait_for(&repeater, <[ProcessInfo]>::is_empty).await;
+        assert!(!repeater.cancel_run(run.run));
+        // SAFETY: signal zero only probes the process identifier and does not
+        // affect the child or access Rust memory.
+        assert_eq!(unsafe { libc::kill(-(run.pid as i32), 0) }, -1);
+
+        let queued = script("sleep 30").with_concurrency(ProcessConcurrency::Queue);
+        repeater.start("cmd+r".to_string(), queued.clone(), None);
+        repeater.start("cmd+r".to_string(), queued.clone(), None);
+        wait_for(&repeater, |runs| runs.len() == 1).await;
+        let state = repeater.process_states.lock()["cmd+r"].clone();
+        assert!(repeater.cancel_run(repeater.processes()[0].run));
+        assert!(!repeater.process_states.lock().contains_key("cmd+r"));
+        settle(&repeater, |_| state.pending.load(Ordering::SeqCst) == 0).await;
+        assert!(repeater.processes().is_empty());
+
+        repeater.start("cmd+q".to_string(), queued.clone(), None);
+        repeater.start("cmd+q".to_string(), queued, None);
+        wait_for(&repeater, |runs| runs.len() == 1).await;
+        assert_eq!(repeater.cancel_all(), 1);
+        wait_for(&repeater, <[ProcessInfo]>::is_empty).await;
+        settle(&repeater, |repeater| {
+            repeater.process_states.lock().is_empty()
+        })
+        .await;
+        assert!(repeater.processes().is_empty());
+
+        repeater.start("cmd+d".to_string(), script("echo done"), None);
+        match rx.recv().await.expect("process notification") {
+            hotki_protocol::MsgToUI::Notify { text, .. } => assert_eq!(text, "done"),
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn stopping_process_action_terminates_owned_process_group() {
         let (tx, _rx) = mpsc::channel(16);
@@ -13,6 +13,10 @@ pub(crate) struct RuntimeState {
     session: Option<ModeSession>,
     pub(crate) rendered: RenderedState,
     pub(crate) selector: Option<SelectorState>,
+    /// Focus history snapshot exposed to configuration contexts, most recent first.
+    pub(crate) recent_windows: Vec<FocusSnapshot>,
+    /// Clipboard history snapshot exposed to configuration contexts, most recent first.
+    pub(crate) clipboard_history: Vec<String>,
 }
 
 /// Focused window retained for one transient mode-stack session.
@@ -40,6 +44,8 @@ impl RuntimeState {
             session: None,
             rendered: Self::empty_rendered(config::Style::default()),
             selector: None,
+            recent_windows: Vec::new(),
+            clipboard_history: Vec::new(),
         }
     }
 
@@ -49,6 +55,7 @@ impl RuntimeState {
             hud_rows: Vec::new(),
             style,
             capture: false,
+            badge: None,
         }
     }
 
@@ -114,6 +121,11 @@ impl RuntimeState {
         self.session = None;
     }
 
+    /// Whether a mode session or selector currently owns focus, so focus changes are Hotki UI.
+    pub(crate) fn pins_focus(&self) -> bool {
+        self.session.is_some() || self.selector.is_some()
+    }
+
     /// Resolve the focused window visible to the current configuration context.
     pub(crate) fn context_window(
         &self,
@@ -130,14 +142,24 @@ impl RuntimeState {
             &self.context_window(live_window),
             self.hud_visible,
             self.depth(),
+            &self.recent_windows,
+            &self.clipboard_history,
         )
     }
 }
 
-pub(crate) fn mode_ctx(window: &Option<FocusSnapshot>, hud: bool, depth: usize) -> ModeCtx {
+pub(crate) fn mode_ctx(
+    window: &Option<FocusSnapshot>,
+    hud: bool,
+    depth: usize,
+    recent_windows: &[FocusSnapshot],
+    clipboard_history: &[String],
+) -> ModeCtx {
     ModeCtx {
         window: window.clone(),
         hud,
         depth: depth as i64,
+        recent_windows: recent_windows.to_vec(),
+        clipboard_history: clipboard_history.to_vec(),
     }
 }
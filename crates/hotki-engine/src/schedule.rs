@@ -0,0 +1,391 @@
+//! Wall-clock scheduled actions declared by `hotki.schedule`.
+//!
+//! A one-second timer compares the injected [`WallClock`] against each action's next run. Runs
+//! found more than [`MISSED_GRACE`] late, typically after the machine slept, follow the action's
+//! [`MissedRuns`] policy. A run at a local time skipped by a daylight-saving change is due at the
+//! first valid time after the gap, so lateness is measured from there. When daylight saving ends
+//! the repeated local hour keeps the pending runs, so a run that already fired is not repeated.
+
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, TimeDelta};
+use config::{MissedRuns, Schedule, runtime::ScheduledAction};
+use tracing::{debug, info, warn};
+
+use crate::{Engine, Result, deps::WallClock};
+
+/// Interval between wall-clock checks.
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// How late a run may start and still count as on time.
+const MISSED_GRACE: TimeDelta = TimeDelta::seconds(60);
+
+/// Scheduled actions of the committed config and their next run times.
+#[derive(Default)]
+pub(crate) struct Scheduler {
+    /// Actions in declaration order.
+    entries: Vec<Entry>,
+    /// Clock reading and UTC offset taken by the previous check.
+    last_check: Option<(NaiveDateTime, TimeDelta)>,
+    /// Whether a timer task is currently running.
+    timer_running: bool,
+}
+
+/// One scheduled action and when it next runs.
+struct Entry {
+    /// Declared schedule, policy, and handler.
+    action: ScheduledAction,
+    /// Next run, or `None` when the schedule never matches again.
+    next: Option<NaiveDateTime>,
+}
+
+impl Scheduler {
+    /// Replace the actions, scheduling each one strictly after the current time of `clock`.
+    pub(crate) fn replace(&mut self, actions: Vec<ScheduledAction>, clock: &dyn WallClock) {
+        let now = clock.now();
+        self.entries = actions
+            .into_iter()
+            .map(|action| Entry {
+                next: action.schedule.next_after(now),
+                action,
+            })
+            .collect();
+        self.last_check = Some((now, clock.utc_offset()));
+    }
+
+    /// Whether the committed config declares no schedules.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Mark a timer task as started, returning `false` when one is already running.
+    pub(crate) fn claim_timer(&mut self) -> bool {
+        !std::mem::replace(&mut self.timer_running, true)
+    }
+
+    /// Record that the timer task has exited.
+    pub(crate) fn release_timer(&mut self) {
+        self.timer_running = false;
+    }
+
+    /// Actions due at the current time of `clock`, advancing each due entry to its next run.
+    ///
+    /// If the clock moved backwards by more than the grace period, every entry is rescheduled
+    /// from now instead. A local time that only moved back because the UTC offset dropped, as
+    /// when daylight saving ends, keeps each pending run.
+    pub(crate) fn take_due(&mut self, clock: &dyn WallClock) -> Vec<ScheduledAction> {
+        let now = clock.now();
+        let offset = clock.utc_offset();
+        if self
+            .last_check
+            .replace((now, offset))
+            .is_some_and(|(last, last_offset)| now - offset < last - last_offset - MISSED_GRACE)
+        {
+            debug!(%now, "wall clock moved backwards; rescheduling");
+            for entry in &mut self.entries {
+                entry.next = entry.action.schedule.next_after(now);
+            }
+            return Vec::new();
+        }
+        let mut due = Vec::new();
+        for entry in &mut self.entries {
+            let action = &entry.action;
+            if advance(&action.schedule, action.missed, &mut entry.next, now, clock) {
+                due.push(action.clone());
+            }
+        }
+        due
+    }
+}
+
+/// Decide whether a run is due at `now`, moving `next` past `now` when one was.
+///
+/// An on-time run is always due. A late run is due once under [`MissedRuns::Coalesce`] however
+/// many runs were missed, and dropped under [`MissedRuns::Skip`]. Lateness counts from the first
+/// time `clock` considers valid, so a run inside a daylight-saving gap is on time when the gap
+/// ends.
+fn advance(
+    schedule: &Schedule,
+    missed: MissedRuns,
+    next: &mut Option<NaiveDateTime>,
+    now: NaiveDateTime,
+    clock: &dyn WallClock,
+) -> bool {
+    let Some(scheduled) = next.filter(|scheduled| *scheduled <= now) else {
+        return false;
+    };
+    *next = schedule.next_after(now);
+    if now - clock.first_valid(scheduled) <= MISSED_GRACE || missed == MissedRuns::Coalesce {
+        return true;
+    }
+    info!(%schedule, missed = %scheduled, "skipping missed run");
+    false
+}
+
+impl Engine {
+    /// Apply the scheduled actions of a committed config.
+    ///
+    /// The timer task starts once a config declares a schedule and exits once a config declares
+    /// none.
+    pub(crate) fn configure_schedules(&self, actions: Vec<ScheduledAction>) {
+        let start = {
+            let mut scheduler = self.schedules.lock();
+            scheduler.replace(actions, self.clock.as_ref());
+            !scheduler.is_empty() && scheduler.claim_timer()
+        };
+        if start {
+            self.spawn_schedule_timer();
+        }
+    }
+
+    /// Check the wall clock every tick until schedules are removed or the engine shuts down.
+    fn spawn_schedule_timer(&self) {
+        let engine = self.clone_for_background();
+        let cancel = self.background_cancellation_token();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                tokio::select! {
+                    () = cancel.cancelled() => return,
+                    _ = interval.tick() => {}
+                }
+                match engine.run_due_schedules().await {
+                    Ok(true) => {}
+                    Ok(false) => return,
+                    Err(err) => warn!("Scheduled action failed: {}", err),
+                }
+            }
+        });
+        self.register_background_task(task);
+    }
+
+    /// Run every scheduled action due now, returning `false` once no schedules remain.
+    ///
+    /// Handlers run against the config that declared them, so the check and execution happen
+    /// under `config_transaction`; effects are applied after it is released because they may
+    /// reload the config.
+    pub(crate) async fn run_due_schedules(&self) -> Result<bool> {
+        let transaction = self.config_transaction.lock().await;
+        let due = {
+            let mut scheduler = self.schedules.lock();
+            if scheduler.is_empty() {
+                scheduler.release_timer();
+                return Ok(false);
+            }
+            scheduler.take_due(self.clock.as_ref())
+        };
+        if due.is_empty() {
+            return Ok(true);
+        }
+
+        let focus = self.current_focus_snapshot();
+        let ctx = {
+            let mut rt = self.runtime.lock().await;
+            rt.clipboard_history = self.clipboard_entries();
+            rt.mode_ctx(&focus)
+        };
+        let mut results = Vec::new();
+        {
+            let mut cfg_guard = self.config.lock().await;
+            let Some(cfg) = cfg_guard.as_mut() else {
+                return Ok(true);
+            };
+            for action in due {
+                info!(schedule = %action.schedule, "running scheduled action");
+                match cfg.execute_handler(&action.handler, &ctx) {
+                    Ok(result) => results.push((action.schedule, result.effects)),
+                    Err(err) => self.notifier.send_error("Schedule", err.pretty())?,
+                }
+            }
+        }
+        drop(transaction);
+
+        for (schedule, effects) in results {
+            self.apply_effects(&format!("schedule:{schedule}"), effects, ctx.clone())
+                .await?;
+        }
+        self.rebind_and_refresh(&focus).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::{deps::ManualClock, dyn_engine::ConfigRuntime, test_support::write_test_config};
+
+    fn time(day: u32, hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .expect("valid time")
+    }
+
+    /// Clock whose local times from 02:00 up to 03:00 on January 8 do not exist.
+    struct SpringForward;
+
+    impl WallClock for SpringForward {
+        fn now(&self) -> NaiveDateTime {
+            time(8, 3, 0, 0)
+        }
+
+        fn first_valid(&self, local: NaiveDateTime) -> NaiveDateTime {
+            if (time(8, 2, 0, 0)..time(8, 3, 0, 0)).contains(&local) {
+                time(8, 3, 0, 0)
+            } else {
+                local
+            }
+        }
+
+        fn utc_offset(&self) -> TimeDelta {
+            TimeDelta::hours(-5)
+        }
+    }
+
+    /// Clock that tests move along with its UTC offset, to repeat an hour as daylight saving ends.
+    struct FallBack(Mutex<(NaiveDateTime, TimeDelta)>);
+
+    impl FallBack {
+        fn set(&self, now: NaiveDateTime, offset_hours: i64) {
+            *self.0.lock() = (now, TimeDelta::hours(offset_hours));
+        }
+    }
+
+    impl WallClock for FallBack {
+        fn now(&self) -> NaiveDateTime {
+            self.0.lock().0
+        }
+
+        fn first_valid(&self, local: NaiveDateTime) -> NaiveDateTime {
+            local
+        }
+
+        fn utc_offset(&self) -> TimeDelta {
+            self.0.lock().1
+        }
+    }
+
+    #[test]
+    fn on_time_runs_fire_once() {
+        let clock = ManualClock::at(time(5, 8, 59, 0));
+        let schedule = Schedule::at("09:00").expect("parse");
+        let mut next = schedule.next_after(time(5, 8, 59, 0));
+        assert!(!advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut next,
+            time(5, 8, 59, 59),
+            &clock
+        ));
+        assert!(advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut next,
+            time(5, 9, 0, 1),
+            &clock
+        ));
+        assert!(!advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut next,
+            time(5, 9, 0, 2),
+            &clock
+        ));
+        assert_eq!(next, Some(time(6, 9, 0, 0)));
+        assert!(advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut next,
+            time(6, 9, 0, 59),
+            &clock
+        ));
+    }
+
+    #[test]
+    fn missed_runs_skip_or_coalesce_after_sleep() {
+        let clock = ManualClock::at(time(5, 8, 0, 0));
+        let schedule = Schedule::at("17:30").expect("parse");
+        for (missed, expected) in [(MissedRuns::Skip, false), (MissedRuns::Coalesce, true)] {
+            // Asleep across three days of runs.
+            let mut next = schedule.next_after(time(5, 8, 0, 0));
+            assert_eq!(
+                advance(&schedule, missed, &mut next, time(8, 12, 0, 0), &clock),
+                expected
+            );
+            assert_eq!(next, Some(time(8, 17, 30, 0)));
+            assert!(!advance(
+                &schedule,
+                missed,
+                &mut next,
+                time(8, 12, 0, 1),
+                &clock
+            ));
+        }
+    }
+
+    #[test]
+    fn runs_skipped_by_spring_forward_are_due_when_the_gap_ends() {
+        let schedule = Schedule::at("02:30").expect("parse");
+        let mut next = schedule.next_after(time(8, 1, 0, 0));
+        let clock = SpringForward;
+        assert!(!advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut next,
+            time(8, 1, 59, 59),
+            &clock
+        ));
+        assert!(advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut next,
+            time(8, 3, 0, 0),
+            &clock
+        ));
+        assert_eq!(next, Some(time(9, 2, 30, 0)));
+
+        let mut late = schedule.next_after(time(8, 1, 0, 0));
+        assert!(!advance(
+            &schedule,
+            MissedRuns::Skip,
+            &mut late,
+            time(8, 3, 1, 1),
+            &clock
+        ));
+    }
+
+    #[test]
+    fn runs_are_not_repeated_when_daylight_saving_ends() {
+        let path = write_test_config(
+            r#"
+            hotki.schedule({ at = "01:30" }, function(ctx) end)
+            return function(menu, ctx) end
+            "#,
+        );
+        let actions = ConfigRuntime::load(&path).expect("load config").schedules();
+        std::fs::remove_file(&path).expect("remove config");
+
+        let clock = FallBack(Mutex::new((time(8, 1, 29, 0), TimeDelta::hours(-4))));
+        let mut scheduler = Scheduler::default();
+        scheduler.replace(actions, &clock);
+        clock.set(time(8, 1, 30, 5), -4);
+        assert_eq!(scheduler.take_due(&clock).len(), 1);
+        clock.set(time(8, 1, 59, 59), -4);
+        assert!(scheduler.take_due(&clock).is_empty());
+
+        // The hour from 01:00 repeats with the standard-time offset.
+        clock.set(time(8, 1, 0, 0), -5);
+        assert!(scheduler.take_due(&clock).is_empty());
+        clock.set(time(8, 1, 30, 5), -5);
+        assert!(scheduler.take_due(&clock).is_empty());
+        clock.set(time(9, 1, 30, 5), -5);
+        assert_eq!(scheduler.take_due(&clock).len(), 1);
+
+        // A clock set back without an offset change still reschedules from the new time.
+        clock.set(time(9, 1, 0, 0), -5);
+        assert!(scheduler.take_due(&clock).is_empty());
+        clock.set(time(9, 1, 30, 5), -5);
+        assert_eq!(scheduler.take_due(&clock).len(), 1);
+    }
+}
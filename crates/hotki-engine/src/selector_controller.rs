@@ -194,7 +194,13 @@ fn close_selector(
     rt.hud_visible = selector.prev_hud_visible;
     SelectorInput::Close(Box::new(SelectorClose {
         terminal,
-        ctx: mode_ctx(&selector.window, rt.hud_visible, rt.depth()),
+        ctx: mode_ctx(
+            &selector.window,
+            rt.hud_visible,
+            rt.depth(),
+            &rt.recent_windows,
+            &rt.clipboard_history,
+        ),
         config: selector.config,
     }))
 }
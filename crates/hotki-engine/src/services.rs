@@ -0,0 +1,384 @@
+//! Supervised background services declared by `hotki.services`.
+//!
+//! Each service runs as the leader of its own process group, like binding processes in the
+//! repeater, so stopping a service also stops anything it spawned. Services start when a config
+//! is committed and are stopped before the next config's services start or the engine drops.
+
+use std::{
+    os::unix::process::CommandExt,
+    path::{Path, PathBuf},
+    process::{ExitStatus, Stdio},
+    sync::Arc,
+};
+
+use config::ServiceSpec;
+use hotki_protocol::rpc::{ServiceState, ServiceStatus};
+use parking_lot::Mutex;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::{Child, Command},
+    task::JoinHandle,
+    time::{Duration, Instant, sleep},
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::repeater::kill_process_group;
+
+/// Upper bound for the doubling restart delay.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// A run at least this long resets the restart delay to the configured base.
+const STABLE_RUN: Duration = Duration::from_secs(10);
+
+/// Owns the services of the committed config.
+#[derive(Clone, Default)]
+pub(crate) struct ServiceSupervisor {
+    /// Supervision handles in declaration order.
+    services: Arc<Mutex<Vec<SupervisedService>>>,
+}
+
+/// One service's shared status and supervision task.
+struct SupervisedService {
+    /// Status published to clients.
+    status: Arc<Mutex<ServiceStatus>>,
+    /// Stops the supervision loop and the process group it owns.
+    cancel: CancellationToken,
+    /// Supervision loop task.
+    task: JoinHandle<()>,
+}
+
+impl ServiceSupervisor {
+    /// Stop the running services, then start `specs` with relative working directories
+    /// resolved against `base_dir`.
+    pub(crate) async fn replace(&self, specs: Vec<ServiceSpec>, base_dir: &Path) {
+        self.stop_all().await;
+        let started = specs
+            .into_iter()
+            .map(|spec| {
+                let cwd = spec.cwd.as_deref().map(|cwd| base_dir.join(cwd));
+                let status = Arc::new(Mutex::new(ServiceStatus {
+                    name: spec.name.clone(),
+                    state: ServiceState::Starting,
+                    pid: None,
+                    restarts: 0,
+                    detail: None,
+                }));
+                let cancel = CancellationToken::new();
+                let task = tokio::spawn(supervise(spec, cwd, status.clone(), cancel.clone()));
+                SupervisedService {
+                    status,
+                    cancel,
+                    task,
+                }
+            })
+            .collect();
+        *self.services.lock() = started;
+    }
+
+    /// Status of every service in declaration order.
+    pub(crate) fn statuses(&self) -> Vec<ServiceStatus> {
+        self.services
+            .lock()
+            .iter()
+            .map(|service| service.status.lock().clone())
+            .collect()
+    }
+
+    /// Stop every service and wait for its process group to be killed and reaped.
+    async fn stop_all(&self) {
+        let services = std::mem::take(&mut *self.services.lock());
+        for service in &services {
+            service.cancel.cancel();
+        }
+        for service in services {
+            if let Err(err) = service.task.await
+                && !err.is_cancelled()
+            {
+                tracing::warn!(error = %err, "service supervision task failed");
+            }
+        }
+    }
+
+    /// Kill every service's process group without waiting; used when the engine drops.
+    pub(crate) fn abort_all(&self) {
+        for service in self.services.lock().drain(..) {
+            service.cancel.cancel();
+            if let Some(pid) = service.status.lock().pid {
+                kill_process_group(pid);
+            }
+            service.task.abort();
+        }
+    }
+}
+
+/// Run one service until cancelled, restarting it as its policy allows.
+async fn supervise(
+    spec: ServiceSpec,
+    cwd: Option<PathBuf>,
+    status: Arc<Mutex<ServiceStatus>>,
+    cancel: CancellationToken,
+) {
+    let base = Duration::from_millis(spec.backoff_ms);
+    let mut delay = base;
+    loop {
+        set_state(&status, ServiceState::Starting, None, None);
+        let started = Instant::now();
+        let (success, detail) = match spawn(&spec, cwd.as_deref()) {
+            Ok(mut child) => {
+                let Some(pid) = child.id() else {
+                    set_state(
+                        &status,
+                        ServiceState::Failed,
+                        None,
+                        Some("failed to obtain process identifier".to_string()),
+                    );
+                    return;
+                };
+                set_state(&status, ServiceState::Running, Some(pid), None);
+                tracing::info!(service = %spec.name, pid, "service started");
+                forward_output(&spec.name, &mut child);
+                let exit = tokio::select! {
+                    () = cancel.cancelled() => {
+                        kill_process_group(pid);
+                        let _ = child.wait().await;
+                        set_state(&status, ServiceState::Stopped, None, None);
+                        return;
+                    }
+                    exit = child.wait() => {
+                        kill_process_group(pid);
+                        exit
+                    }
+                };
+                match exit {
+                    Ok(exit) => (exit.success(), describe_exit(exit)),
+                    Err(err) => (false, format!("failed to wait for process: {err}")),
+                }
+            }
+            Err(err) => (false, format!("failed to execute {}: {err}", spec.program)),
+        };
+
+        if !spec.restart.restarts(success) {
+            let state = if success {
+                ServiceState::Exited
+            } else {
+                ServiceState::Failed
+            };
+            tracing::info!(service = %spec.name, %detail, "service left stopped");
+            set_state(&status, state, None, Some(detail));
+            return;
+        }
+
+        let wait = if started.elapsed() >= STABLE_RUN {
+            base
+        } else {
+            delay
+        };
+        delay = wait.saturating_mul(2).min(MAX_BACKOFF.max(base));
+        tracing::warn!(service = %spec.name, %detail, ?wait, "service exited; restarting");
+        set_state(&status, ServiceState::Backoff, None, Some(detail));
+        tokio::select! {
+            () = cancel.cancelled() => {
+                set_state(&status, ServiceState::Stopped, None, None);
+                return;
+            }
+            () = sleep(wait) => {}
+        }
+        status.lock().restarts += 1;
+    }
+}
+
+/// Spawn one service process as the leader of a new process group.
+fn spawn(spec: &ServiceSpec, cwd: Option<&Path>) -> std::io::Result<Child> {
+    let mut command = Command::new(&spec.program);
+    command
+        .args(&spec.args)
+        .envs(&spec.env)
+        .kill_on_drop(true)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    command.as_std_mut().process_group(0);
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+    command.spawn()
+}
+
+/// Forward the child's output to the log, one line per event.
+fn forward_output(name: &str, child: &mut Child) {
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(log_lines(name.to_string(), "stdout", stdout));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(log_lines(name.to_string(), "stderr", stderr));
+    }
+}
+
+/// Log each line read from one output stream of a service.
+async fn log_lines(name: String, stream: &'static str, reader: impl AsyncRead + Unpin) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        tracing::info!(service = %name, stream, "{line}");
+    }
+}
+
+/// Describe a process exit for the status detail.
+fn describe_exit(exit: ExitStatus) -> String {
+    match exit.code() {
+        Some(code) => format!("exited with status {code}"),
+        None => format!("terminated by {exit}"),
+    }
+}
+
+/// Record a state transition; a new `detail` replaces the last one, and reaching
+/// [`ServiceState::Running`] clears it.
+fn set_state(
+    status: &Mutex<ServiceStatus>,
+    state: ServiceState,
+    pid: Option<u32>,
+    detail: Option<String>,
+) {
+    let mut status = status.lock();
+    status.state = state;
+    status.pid = pid;
+    if detail.is_some() || state == ServiceState::Running {
+        status.detail = detail;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use config::ServiceRestart;
+
+    use super::*;
+
+    fn script(name: &str, body: &str, restart: ServiceRestart) -> ServiceSpec {
+        ServiceSpec {
+            name: name.to_string(),
+            program: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), body.to_string()],
+            cwd: None,
+            env: BTreeMap::new(),
+            restart,
+            backoff_ms: 10,
+        }
+    }
+
+    async fn wait_for(
+        supervisor: &ServiceSupervisor,
+        done: impl Fn(&[ServiceStatus]) -> bool,
+    ) -> Vec<ServiceStatus> {
+        let mut last = Vec::new();
+        let polled = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let statuses = supervisor.statuses();
+                if done(&statuses) {
+                    return statuses;
+                }
+                last = statuses;
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        polled.unwrap_or_else(|_| panic!("services did not settle; last statuses: {last:#?}"))
+    }
+
+    #[tokio::test]
+    async fn services_restart_per_policy_and_stop_on_replace() {
+        let supervisor = ServiceSupervisor::default();
+        supervisor
+            .replace(
+                vec![
+                    script("crashy", "exit 3", ServiceRestart::OnFailure),
+                    script("once", "exit 0", ServiceRestart::OnFailure),
+                    script("broken", "exit 2", ServiceRestart::Never),
+                    script("daemon", "sleep 30", ServiceRestart::Always),
+                ],
+                Path::new("/"),
+            )
+            .await;
+        let statuses = wait_for(&supervisor, |services| {
+            services[0].restarts >= 2
+                && services[0].state == ServiceState::Backoff
+                && services[1].state == ServiceState::Exited
+                && services[2].state == ServiceState::Failed
+                && services[3].state == ServiceState::Running
+        })
+        .await;
+        assert_eq!(
+            statuses.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ["crashy", "once", "broken", "daemon"]
+        );
+        assert_eq!(statuses[0].detail.as_deref(), Some("exited with status 3"));
+        assert_eq!(statuses[1].restarts, 0);
+        assert_eq!(statuses[2].detail.as_deref(), Some("exited with status 2"));
+        let pid = statuses[3].pid.expect("daemon pid");
+
+        supervisor.replace(Vec::new(), Path::new("/")).await;
+        assert!(supervisor.statuses().is_empty());
+        // SAFETY: signal zero only probes the process group and does not affect
+        // any process or access Rust memory.
+        assert_eq!(unsafe { libc::kill(-(pid as i32), 0) }, -1);
+    }
+
+    #[tokio::test]
+    async fn service_exit_kills_its_process_group() {
+        let pid_file =
+            std::env::temp_dir().join(format!("hotki-service-group-{}", std::process::id()));
+        let mut leaky = script(
+            "leaky",
+            "echo $$ > \"$0\"; sleep 30 & exit 0",
+            ServiceRestart::Never,
+        );
+        leaky.args.push(pid_file.display().to_string());
+
+        let supervisor = ServiceSupervisor::default();
+        supervisor.replace(vec![leaky], Path::new("/")).await;
+        wait_for(&supervisor, |services| {
+            services[0].state == ServiceState::Exited
+        })
+        .await;
+        let pid: i32 = std::fs::read_to_string(&pid_file)
+            .expect("read service pid")
+            .trim()
+            .parse()
+            .expect("parse service pid");
+        std::fs::remove_file(&pid_file).expect("remove service pid");
+        // SAFETY: signal zero only probes the process group and does not affect
+        // any process or access Rust memory.
+        assert_eq!(unsafe { libc::kill(-pid, 0) }, -1);
+        supervisor.abort_all();
+    }
+
+    #[tokio::test]
+    async fn spawn_failures_fail_and_relative_cwd_resolves_against_base() {
+        let base = std::env::temp_dir().join(format!("hotki-services-{}", std::process::id()));
+        std::fs::create_dir_all(base.join("sub")).expect("create service dir");
+        std::fs::write(base.join("sub/marker"), "").expect("write marker");
+        let mut missing = script("missing", "", ServiceRestart::Never);
+        missing.program = "/nonexistent/hotki-service".to_string();
+        let mut relative = script("relative", "test -f marker", ServiceRestart::Never);
+        relative.cwd = Some("sub".to_string());
+
+        let supervisor = ServiceSupervisor::default();
+        supervisor.replace(vec![missing, relative], &base).await;
+        let statuses = wait_for(&supervisor, |services| {
+            services
+                .iter()
+                .all(|service| matches!(service.state, ServiceState::Exited | ServiceState::Failed))
+        })
+        .await;
+        assert_eq!(statuses[0].state, ServiceState::Failed);
+        assert!(
+            statuses[0]
+                .detail
+                .as_deref()
+                .is_some_and(|detail| detail.starts_with("failed to execute"))
+        );
+        assert_eq!(statuses[1].state, ServiceState::Exited);
+        supervisor.abort_all();
+        assert!(supervisor.statuses().is_empty());
+        std::fs::remove_dir_all(base).expect("remove service dir");
+    }
+}
@@ -124,12 +124,27 @@ impl Engine {
                 title = %focus.title,
                 "Engine: world focus context updated"
             );
+            self.record_focus_history(focus).await;
         } else {
             debug!("Engine: world focus context cleared");
         }
         self.rebind_current_context().await
     }
 
+    /// Record a focus change in the MRU history unless Hotki's own UI owns focus.
+    async fn record_focus_history(&self, focus: &hotki_protocol::FocusSnapshot) {
+        if self.runtime.lock().await.pins_focus() {
+            trace!(app = %focus.app, "Focus history skipped during mode session");
+            return;
+        }
+        self.history.lock().record(focus);
+    }
+
+    /// Snapshot the focus history, most recently focused window first.
+    pub(crate) fn recent_windows(&self) -> Vec<hotki_protocol::FocusSnapshot> {
+        self.history.lock().windows()
+    }
+
     pub(crate) async fn refresh_world_focus(&self) -> Result<()> {
         self.world.refresh().await;
         let focus = self.world.focus_snapshot();
@@ -9,13 +9,18 @@ use std::{
     time::{Duration, Instant as StdInstant},
 };
 
+use chrono::NaiveDate;
 use hotki_protocol::{Mode, MsgToUI};
+use hotki_world::TestApplication;
 use tokio::time::{advance, timeout};
 
-use crate::test_support::{
-    capture_all_active, create_test_engine_with_relay, recv_until, run_engine_test,
-    run_engine_test_paused, set_on_relay_repeat, set_world_focus, set_world_focus_window,
-    write_test_config,
+use crate::{
+    deps::{ManualClock, MemoryClipboard},
+    test_support::{
+        capture_all_active, create_test_engine_with_relay, recv_until, run_engine_test,
+        run_engine_test_paused, set_on_relay_repeat, set_world_focus, set_world_focus_window,
+        write_test_config,
+    },
 };
 
 #[test]
@@ -66,6 +71,162 @@ fn focus_change_triggers_rerender() {
     });
 }
 
+#[test]
+fn app_mode_registers_layer_bindings_while_app_is_focused() {
+    run_engine_test(async move {
+        let (engine, mut rx, world) = create_test_engine_with_relay(false).await;
+
+        let path = write_test_config(
+            r#"
+            return function(menu, ctx)
+              menu:bind("cmd+k", "root", function(actx) end)
+              menu:app_mode("Figma", "Figma", function(layer, inner)
+                layer:bind("cmd+shift+l", "layers", function(actx) end)
+              end, { badge = true })
+            end
+            "#,
+        );
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("set config");
+
+        set_world_focus(world.as_ref(), "Figma", "Design", 1).await;
+        let badge = recv_until(
+            &mut rx,
+            200,
+            |m| matches!(m, MsgToUI::HudUpdate { hud, .. } if hud.badge.is_some()),
+        )
+        .await;
+        assert!(badge, "focused app mode should publish its HUD badge");
+        assert!(engine.resolve_id_for_ident("cmd+shift+l").await.is_some());
+        assert!(engine.resolve_id_for_ident("cmd+k").await.is_some());
+        assert_eq!(engine.get_depth().await, 0);
+
+        set_world_focus(world.as_ref(), "Other", "Window", 2).await;
+        let cleared = recv_until(
+            &mut rx,
+            200,
+            |m| matches!(m, MsgToUI::HudUpdate { hud, .. } if hud.badge.is_none()),
+        )
+        .await;
+        assert!(cleared, "leaving the app should clear the HUD badge");
+        assert!(engine.resolve_id_for_ident("cmd+shift+l").await.is_none());
+        assert!(engine.resolve_id_for_ident("cmd+k").await.is_some());
+
+        let _ignored = fs::remove_file(&path);
+    });
+}
+
+#[test]
+fn focus_history_skips_mode_sessions_and_survives_reload() {
+    run_engine_test(async move {
+        let (engine, mut rx, world) = create_test_engine_with_relay(false).await;
+        world.set_running_applications(vec![
+            TestApplication {
+                name: Some("Editor".into()),
+                bundle_id: None,
+                pid: 1,
+                terminated: false,
+            },
+            TestApplication {
+                name: Some("Browser".into()),
+                bundle_id: None,
+                pid: 2,
+                terminated: false,
+            },
+        ]);
+        let path = write_test_config(
+            r#"
+            local a = hotki.actions
+            return function(menu, ctx)
+              menu:bind("cmd+p", "previous app", a.focus_previous_app())
+              menu:submenu("cmd+k", "menu", function(sub)
+                sub:bind("x", "noop", function(actx) end)
+              end)
+            end
+            "#,
+        );
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("set config");
+
+        let history_len = |len: usize| {
+            let engine = engine.clone();
+            async move {
+                timeout(Duration::from_millis(200), async {
+                    while engine.recent_windows().len() != len {
+                        tokio::task::yield_now().await;
+                    }
+                })
+                .await
+                .is_ok()
+            }
+        };
+        set_world_focus(world.as_ref(), "Editor", "Notes", 1).await;
+        assert!(history_len(1).await, "editor focus should enter history");
+        set_world_focus(world.as_ref(), "Browser", "Docs", 2).await;
+        assert!(history_len(2).await, "browser focus should enter history");
+        let _ = recv_until(&mut rx, 200, |m| matches!(m, MsgToUI::HudUpdate { .. })).await;
+
+        let cmd_k = engine
+            .resolve_id_for_ident("cmd+k")
+            .await
+            .expect("id for cmd+k");
+        engine
+            .dispatch(cmd_k, mac_hotkey::EventKind::KeyDown, false)
+            .await
+            .expect("dispatch cmd+k");
+        assert_eq!(engine.get_depth().await, 1);
+        set_world_focus(world.as_ref(), "Hotki", "HUD", 3).await;
+        timeout(Duration::from_millis(200), async {
+            while engine
+                .current_focus_snapshot()
+                .is_none_or(|focus| focus.pid != 3)
+            {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("engine did not observe HUD focus");
+        assert!(
+            engine.recent_windows().iter().all(|window| window.pid != 3),
+            "focus during a mode session must not enter history"
+        );
+
+        engine.auto_exit().await;
+        set_world_focus(world.as_ref(), "Browser", "Docs", 2).await;
+        timeout(Duration::from_millis(200), async {
+            while engine
+                .current_focus_snapshot()
+                .is_none_or(|focus| focus.pid != 2)
+            {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("engine did not observe browser focus");
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("reload config");
+        assert_eq!(engine.recent_windows().len(), 2);
+
+        let cmd_p = engine
+            .resolve_id_for_ident("cmd+p")
+            .await
+            .expect("id for cmd+p");
+        engine
+            .dispatch(cmd_p, mac_hotkey::EventKind::KeyDown, false)
+            .await
+            .expect("dispatch cmd+p");
+        assert_eq!(world.activations(), vec![1]);
+
+        let _ignored = fs::remove_file(&path);
+    });
+}
+
 #[test]
 fn selector_callback_keeps_opening_window_and_rebinds_with_closing_focus() {
     run_engine_test(async move {
@@ -1016,6 +1177,173 @@ fn render_recovery_truncates_bad_child_mode_to_root() {
     });
 }
 
+#[test]
+fn clipboard_callbacks_and_exec_stdin_read_the_clipboard() {
+    run_engine_test(async move {
+        let (engine, mut rx, world) = create_test_engine_with_relay(false).await;
+        let clipboard = Arc::new(MemoryClipboard::with_text("hello"));
+        let engine = engine.with_clipboard(clipboard.clone());
+
+        let path = write_test_config(
+            r#"
+            local a = hotki.actions
+
+            return function(menu, ctx)
+              menu:bind("w", "wrap", a.with_clipboard(function(actx, text)
+                actx:set_clipboard("`" .. (text or "") .. "`")
+              end))
+              menu:bind("c", "cat", a.exec({
+                program = "/bin/cat",
+                stdin = "clipboard",
+                ok_notify = "info",
+              }))
+            end
+            "#,
+        );
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("set config");
+
+        set_world_focus(world.as_ref(), "TestApp", "Window", 123).await;
+        let _ = recv_until(&mut rx, 200, |m| matches!(m, MsgToUI::HudUpdate { .. })).await;
+        drain_ui(&mut rx);
+
+        dispatch_gesture(&engine, "w").await;
+        assert_eq!(clipboard.text().as_deref(), Some("`hello`"));
+
+        dispatch_gesture(&engine, "c").await;
+        assert_eq!(
+            recv_notify_text(&mut rx, 2000, "Process").await.as_deref(),
+            Some("`hello`")
+        );
+
+        let _ignored = fs::remove_file(&path);
+    });
+}
+
+#[test]
+fn clipboard_history_samples_copies_and_feeds_the_selector() {
+    run_engine_test(async move {
+        let (engine, mut rx, world) = create_test_engine_with_relay(false).await;
+        let clipboard = Arc::new(MemoryClipboard::with_text("before"));
+        let engine = engine.with_clipboard(clipboard.clone());
+
+        let path = write_test_config(
+            r#"
+            local a = hotki.actions
+
+            hotki.enable_clipboard_history({ capacity = 2, exclude_apps = { "^Vault$" } })
+
+            return function(menu, ctx)
+              menu:bind("v", "history", a.clipboard_history({ title = "Clips" }))
+            end
+            "#,
+        );
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("set config");
+
+        set_world_focus(world.as_ref(), "TestApp", "Window", 123).await;
+        let _ = recv_until(&mut rx, 200, |m| matches!(m, MsgToUI::HudUpdate { .. })).await;
+        drain_ui(&mut rx);
+
+        assert!(engine.sample_clipboard().await);
+        for text in ["one", "two\nsecond line", "three"] {
+            clipboard.set_text(Some(text));
+            assert!(engine.sample_clipboard().await);
+        }
+        set_world_focus(world.as_ref(), "Vault", "Secrets", 124).await;
+        clipboard.set_text(Some("secret"));
+        assert!(engine.sample_clipboard().await);
+        assert_eq!(
+            engine.clipboard_entries(),
+            vec!["three", "two\nsecond line"]
+        );
+
+        set_world_focus(world.as_ref(), "TestApp", "Window", 123).await;
+        drain_ui(&mut rx);
+        dispatch_gesture(&engine, "v").await;
+        let opened = recv_selector_update(&mut rx, 500)
+            .await
+            .expect("selector should open");
+        assert_eq!(opened.title, "Clips");
+        let labels: Vec<_> = opened
+            .items
+            .iter()
+            .map(|item| (item.label.as_str(), item.sublabel.as_deref()))
+            .collect();
+        assert_eq!(labels, vec![("three", None), ("two", Some("2 lines"))]);
+
+        let _ignored = fs::remove_file(&path);
+    });
+}
+
+#[test]
+fn scheduled_actions_run_on_the_wall_clock_and_apply_the_missed_policy() {
+    run_engine_test(async move {
+        let (engine, mut rx, _world) = create_test_engine_with_relay(false).await;
+        let day = |day: u32, hour: u32, minute: u32, second: u32| {
+            NaiveDate::from_ymd_opt(2026, 1, day)
+                .and_then(|date| date.and_hms_opt(hour, minute, second))
+                .expect("valid time")
+        };
+        let clock = Arc::new(ManualClock::at(day(5, 8, 59, 30)));
+        let engine = engine.with_clock(clock.clone());
+
+        let path = write_test_config(
+            r#"
+            local a = hotki.actions
+
+            hotki.schedule({ at = "09:00" }, a.notify("info", "Stand-up", "standup"))
+            hotki.schedule({ at = "17:30", missed = "coalesce" }, function(ctx)
+              ctx:notify("info", "Wrap-up", "wrap")
+            end)
+
+            return function(menu, ctx) end
+            "#,
+        );
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("set config");
+        drain_ui(&mut rx);
+
+        assert!(engine.run_due_schedules().await.expect("check schedules"));
+        assert_eq!(recv_notify_text(&mut rx, 50, "Stand-up").await, None);
+
+        clock.set(day(5, 9, 0, 10));
+        assert!(engine.run_due_schedules().await.expect("check schedules"));
+        assert_eq!(
+            recv_notify_text(&mut rx, 500, "Stand-up").await.as_deref(),
+            Some("standup")
+        );
+
+        // Asleep from before the 17:30 run until the next morning.
+        clock.set(day(6, 8, 0, 0));
+        assert!(engine.run_due_schedules().await.expect("check schedules"));
+        assert_eq!(
+            recv_notify_text(&mut rx, 500, "Wrap-up").await.as_deref(),
+            Some("wrap")
+        );
+
+        // Asleep across the 09:00 run, which skips missed runs.
+        clock.set(day(6, 10, 0, 0));
+        assert!(engine.run_due_schedules().await.expect("check schedules"));
+        assert_eq!(recv_notify_text(&mut rx, 50, "Stand-up").await, None);
+
+        fs::write(&path, "return function(menu, ctx) end").expect("rewrite config");
+        engine
+            .set_config_path(path.clone())
+            .await
+            .expect("reload config");
+        assert!(!engine.run_due_schedules().await.expect("check schedules"));
+
+        let _ignored = fs::remove_file(&path);
+    });
+}
+
 #[test]
 fn unbound_key_up_is_noop() {
     run_engine_test(async move {
@@ -121,6 +121,7 @@ mod tests {
             breadcrumbs: Vec::new(),
             style,
             capture: false,
+            badge: Some("Figma".to_string()),
         };
         let samples: Vec<MsgToUI> = vec![
             MsgToUI::HudKeyState {
@@ -30,6 +30,8 @@ pub enum RpcErrorCode {
     KeyNotBound,
     /// Engine dispatch failed while handling a key injection.
     EngineDispatch,
+    /// Requested process run is not running.
+    ProcessNotFound,
 }
 
 impl RpcErrorCode {
@@ -44,6 +46,7 @@ impl RpcErrorCode {
             Self::EngineSetConfig => "EngineSetConfig",
             Self::KeyNotBound => "KeyNotBound",
             Self::EngineDispatch => "EngineDispatch",
+            Self::ProcessNotFound => "ProcessNotFound",
         }
     }
 
@@ -58,6 +61,7 @@ impl RpcErrorCode {
             "EngineSetConfig" => Self::EngineSetConfig,
             "KeyNotBound" => Self::KeyNotBound,
             "EngineDispatch" => Self::EngineDispatch,
+            "ProcessNotFound" => Self::ProcessNotFound,
             _ => return None,
         })
     }
@@ -271,6 +275,14 @@ pub enum HotkeyMethod {
     GetServerStatus,
     /// Get the world snapshot (focus + displays).
     GetWorldSnapshot,
+    /// List processes started by bindings that are still running.
+    ListProcesses,
+    /// Cancel one running process by run identifier.
+    CancelProcess,
+    /// Get the status of every supervised service.
+    GetServices,
+    /// Explain how the live mode stack resolves one chord.
+    ExplainChord,
 }
 
 impl HotkeyMethod {
@@ -285,6 +297,10 @@ impl HotkeyMethod {
             Self::GetWorldStatus => "get_world_status",
             Self::GetServerStatus => "get_server_status",
             Self::GetWorldSnapshot => "get_world_snapshot",
+            Self::ListProcesses => "list_processes",
+            Self::CancelProcess => "cancel_process",
+            Self::GetServices => "get_services",
+            Self::ExplainChord => "explain_chord",
         }
     }
 
@@ -299,6 +315,10 @@ impl HotkeyMethod {
             "get_world_status" => Some(Self::GetWorldStatus),
             "get_server_status" => Some(Self::GetServerStatus),
             "get_world_snapshot" => Some(Self::GetWorldSnapshot),
+            "list_processes" => Some(Self::ListProcesses),
+            "cancel_process" => Some(Self::CancelProcess),
+            "get_services" => Some(Self::GetServices),
+            "explain_chord" => Some(Self::ExplainChord),
             _ => None,
         }
     }
@@ -350,6 +370,105 @@ pub struct WorldSnapshotLite {
     pub displays: DisplaysSnapshot,
 }
 
+/// One running process started by a binding, as returned by `list_processes`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProcessInfo {
+    /// Run identifier accepted by `cancel_process`.
+    pub run: u64,
+    /// Key identifier of the binding that started the process.
+    pub binding: String,
+    /// Program and arguments, joined for display.
+    pub command: String,
+    /// Process identifier of the process-group leader.
+    pub pid: u32,
+    /// Start time in milliseconds since the Unix epoch.
+    pub started_ms: u64,
+    /// Captured standard output and error so far; empty when the binding ignores output.
+    pub output: String,
+}
+
+/// Lifecycle state of one supervised service.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    /// Spawning the process.
+    Starting,
+    /// The process is running.
+    Running,
+    /// Waiting out the restart delay after an exit.
+    Backoff,
+    /// Exited successfully and the restart policy leaves it down.
+    Exited,
+    /// Exited unsuccessfully or failed to spawn, and the restart policy leaves it down.
+    Failed,
+    /// Stopped by Hotki on reload or shutdown.
+    Stopped,
+}
+
+/// Status of one supervised service, as returned by `get_services`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServiceStatus {
+    /// Service name from the config declaration.
+    pub name: String,
+    /// Current lifecycle state.
+    pub state: ServiceState,
+    /// Process identifier while running.
+    pub pid: Option<u32>,
+    /// Number of restarts since the config loaded.
+    pub restarts: u32,
+    /// Last exit or spawn failure, when there was one.
+    pub detail: Option<String>,
+}
+
+/// How one chord resolves against a mode stack, as returned by `explain_chord`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChordExplanation {
+    /// Chord in canonical form.
+    pub chord: String,
+    /// Mode titles from the root to the active mode.
+    pub mode: Vec<String>,
+    /// Binding the chord resolves to, if any.
+    pub matched: Option<ExplainedBinding>,
+    /// `global` bindings inherited from parent modes, nearest parent first.
+    pub inherited: Vec<ExplainedBinding>,
+    /// Whether capture-all is active, so an unmatched chord is swallowed rather than passed on.
+    pub capture: bool,
+    /// Window conditions the renderers consulted and `app_mode` layers inactive for this context.
+    pub conditions: Vec<RenderCondition>,
+}
+
+/// One binding reported by `explain_chord`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExplainedBinding {
+    /// Chord in canonical form.
+    pub chord: String,
+    /// Description shown in the HUD.
+    pub desc: String,
+    /// Title of the mode that declared the binding.
+    pub mode: String,
+    /// Whether the binding enters a submenu.
+    pub is_mode: bool,
+    /// Whether the binding is inherited by child modes.
+    pub global: bool,
+    /// Whether the binding is hidden from the HUD.
+    pub hidden: bool,
+    /// Whether the binding suppresses the automatic exit.
+    pub stay: bool,
+    /// Declaration site as `path:line:col`, when known.
+    pub source: Option<String>,
+    /// Whether a nearer mode binds the same chord, so this binding never fires here.
+    pub shadowed: bool,
+}
+
+/// A condition that decided what one mode rendered.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RenderCondition {
+    /// Title of the mode being rendered.
+    pub mode: String,
+    /// The condition, such as `window.app is "Safari"` or `no focused window`.
+    pub condition: String,
+}
+
 /// Inject key request: encoded as msgpack in a single Binary param.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct InjectKeyReq {
@@ -377,9 +496,31 @@ mod tests {
     use mrpc::{ServiceError, Value};
 
     use super::{
-        RpcErrorCode, RpcErrorDecodeError, RpcFailure, decode_rpc_failure, encode_rpc_failure,
+        HotkeyMethod, RpcErrorCode, RpcErrorDecodeError, RpcFailure, decode_rpc_failure,
+        encode_rpc_failure,
     };
 
+    #[test]
+    fn hotkey_methods_round_trip_stable_names() {
+        for method in [
+            HotkeyMethod::Shutdown,
+            HotkeyMethod::SetConfigPath,
+            HotkeyMethod::InjectKey,
+            HotkeyMethod::GetBindings,
+            HotkeyMethod::GetDepth,
+            HotkeyMethod::GetWorldStatus,
+            HotkeyMethod::GetServerStatus,
+            HotkeyMethod::GetWorldSnapshot,
+            HotkeyMethod::ListProcesses,
+            HotkeyMethod::CancelProcess,
+            HotkeyMethod::GetServices,
+            HotkeyMethod::ExplainChord,
+        ] {
+            assert_eq!(HotkeyMethod::try_from_str(method.as_str()), Some(method));
+        }
+        assert_eq!(HotkeyMethod::try_from_str("other"), None);
+    }
+
     #[test]
     fn rpc_error_codes_round_trip_stable_names() {
         for code in [
@@ -391,6 +532,7 @@ mod tests {
             RpcErrorCode::EngineSetConfig,
             RpcErrorCode::KeyNotBound,
             RpcErrorCode::EngineDispatch,
+            RpcErrorCode::ProcessNotFound,
         ] {
             assert_eq!(RpcErrorCode::from_service_name(code.as_str()), Some(code));
         }
@@ -33,6 +33,9 @@ pub struct HudState {
     pub style: Style,
     /// True when capture-all mode is active.
     pub capture: bool,
+    /// Badge title for the active application mode, shown even while the HUD is hidden.
+    #[serde(default)]
+    pub badge: Option<String>,
 }
 
 /// One selector item entry produced by server-side fuzzy matching.
@@ -12,8 +12,8 @@ use async_trait::async_trait;
 use hotki_protocol::{
     MsgToUI,
     rpc::{
-        HotkeyMethod, HotkeyNotification, InjectKeyReq, InjectKind, ServerStatusLite,
-        WorldSnapshotLite, decode_rpc_failure,
+        ChordExplanation, HotkeyMethod, HotkeyNotification, InjectKeyReq, InjectKind, ProcessInfo,
+        ServerStatusLite, ServiceStatus, WorldSnapshotLite, decode_rpc_failure,
     },
 };
 use mrpc::{Client as MrpcClient, Connection as MrpcConnection, RpcError, RpcSender, Value};
@@ -458,6 +458,28 @@ impl Connection {
         self.request_binary(HotkeyMethod::GetWorldSnapshot, &[])
             .await
     }
+
+    /// List processes started by bindings that are still running, oldest first.
+    pub async fn list_processes(&mut self) -> Result<Vec<ProcessInfo>> {
+        self.request_binary(HotkeyMethod::ListProcesses, &[]).await
+    }
+
+    /// Kill one running process by run identifier without a notification.
+    pub async fn cancel_process(&mut self, run: u64) -> Result<()> {
+        self.request_ok(HotkeyMethod::CancelProcess, &[Value::Integer(run.into())])
+            .await
+    }
+
+    /// Get the status of every supervised service, in declaration order.
+    pub async fn get_services(&mut self) -> Result<Vec<ServiceStatus>> {
+        self.request_binary(HotkeyMethod::GetServices, &[]).await
+    }
+
+    /// Explain how `chord` resolves against the live mode stack and focused window.
+    pub async fn explain_chord(&mut self, chord: &str) -> Result<ChordExplanation> {
+        self.request_binary(HotkeyMethod::ExplainChord, &[Value::String(chord.into())])
+            .await
+    }
 }
 
 /// Convert an MRPC request failure into the server crate's error shape.
@@ -35,8 +35,8 @@ use hotki_protocol::rpc::{HotkeyMethod, RpcErrorCode, RpcFailure, ServerStatusLi
 use mrpc::{Connection as MrpcConnection, RpcError, RpcSender, Value};
 pub(crate) use rpc::dec_inject_key_param;
 use rpc::{
-    build_snapshot_payload, enc_server_status, enc_world_snapshot, enc_world_status,
-    inject_kind_to_event, string_param, typed_err,
+    build_snapshot_payload, enc_explanation, enc_processes, enc_server_status, enc_services,
+    enc_world_snapshot, enc_world_status, inject_kind_to_event, string_param, typed_err, u64_param,
 };
 use tokio::sync::OnceCell;
 use tracing::{debug, info, trace, warn};
@@ -241,6 +241,55 @@ impl HotkeyService {
             .map_err(|err| typed_err(RpcFailure::new(RpcErrorCode::InvalidType, err.to_string())))
     }
 
+    async fn handle_list_processes(&self) -> StdResult<Value, RpcError> {
+        let engine = self.engine().await;
+        enc_processes(&engine.running_processes())
+            .map_err(|err| typed_err(RpcFailure::new(RpcErrorCode::InvalidType, err.to_string())))
+    }
+
+    async fn handle_get_services(&self) -> StdResult<Value, RpcError> {
+        let engine = self.engine().await;
+        enc_services(&engine.service_statuses())
+            .map_err(|err| typed_err(RpcFailure::new(RpcErrorCode::InvalidType, err.to_string())))
+    }
+
+    async fn handle_cancel_process(&self, params: &[Value]) -> StdResult<Value, RpcError> {
+        let method = HotkeyMethod::CancelProcess.as_str();
+        let run = u64_param(params, method, "run id")?;
+        let engine = self.engine().await;
+        if engine.cancel_process(run) {
+            Ok(Value::Boolean(true))
+        } else {
+            Err(typed_err(
+                RpcFailure::new(
+                    RpcErrorCode::ProcessNotFound,
+                    format!("process run is not running: {run}"),
+                )
+                .with_method(method),
+            ))
+        }
+    }
+
+    async fn handle_explain_chord(&self, params: &[Value]) -> StdResult<Value, RpcError> {
+        let method = HotkeyMethod::ExplainChord.as_str();
+        let raw = string_param(params, method, "chord", RpcErrorCode::MissingParams)?;
+        let Some(chord) = mac_keycode::Chord::parse(&raw) else {
+            return Err(typed_err(
+                RpcFailure::new(RpcErrorCode::InvalidType, format!("invalid chord: {raw}"))
+                    .with_method(method)
+                    .with_expected("chord"),
+            ));
+        };
+        let engine = self.engine().await;
+        let explanation = engine.explain_chord(&chord).await.map_err(|err| {
+            typed_err(
+                RpcFailure::new(RpcErrorCode::InvalidConfig, err.to_string()).with_method(method),
+            )
+        })?;
+        enc_explanation(&explanation)
+            .map_err(|err| typed_err(RpcFailure::new(RpcErrorCode::InvalidType, err.to_string())))
+    }
+
     async fn route_request(
         &self,
         method: HotkeyMethod,
@@ -255,6 +304,10 @@ impl HotkeyService {
             HotkeyMethod::GetWorldStatus => self.handle_get_world_status().await,
             HotkeyMethod::GetWorldSnapshot => self.handle_get_world_snapshot().await,
             HotkeyMethod::GetServerStatus => self.handle_get_server_status().await,
+            HotkeyMethod::ListProcesses => self.handle_list_processes().await,
+            HotkeyMethod::CancelProcess => self.handle_cancel_process(params).await,
+            HotkeyMethod::GetServices => self.handle_get_services().await,
+            HotkeyMethod::ExplainChord => self.handle_explain_chord(params).await,
         }
     }
 }
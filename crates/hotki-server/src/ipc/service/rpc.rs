@@ -1,7 +1,8 @@
 use hotki_protocol::{
     FocusSnapshot, MsgToUI,
     rpc::{
-        InjectKeyReq, InjectKind, RpcErrorCode, RpcFailure, WorldSnapshotLite, encode_rpc_failure,
+        ChordExplanation, InjectKeyReq, InjectKind, ProcessInfo, RpcErrorCode, RpcFailure,
+        ServiceStatus, WorldSnapshotLite, encode_rpc_failure,
     },
 };
 use mrpc::{RpcError, Value};
@@ -48,6 +49,39 @@ pub(super) fn string_param(
     }
 }
 
+/// Extract a required unsigned integer parameter from an MRPC request.
+pub(super) fn u64_param(params: &[Value], method: &str, expected: &str) -> Result<u64, RpcError> {
+    let Some(value) = params.first() else {
+        return Err(typed_err(
+            RpcFailure::new(
+                RpcErrorCode::MissingParams,
+                format!("{method} requires {expected}"),
+            )
+            .with_method(method)
+            .with_expected(expected),
+        ));
+    };
+
+    match value {
+        Value::Integer(raw) => raw.as_u64().ok_or_else(|| {
+            let expected = format!("unsigned {expected}");
+            typed_err(
+                RpcFailure::new(RpcErrorCode::InvalidType, format!("expected {expected}"))
+                    .with_method(method)
+                    .with_expected(expected),
+            )
+        }),
+        _ => {
+            let expected = format!("integer {expected}");
+            Err(typed_err(
+                RpcFailure::new(RpcErrorCode::InvalidType, format!("expected {expected}"))
+                    .with_method(method)
+                    .with_expected(expected),
+            ))
+        }
+    }
+}
+
 /// Build the lightweight world snapshot payload returned over MRPC.
 pub(super) fn build_snapshot_payload(
     displays: hotki_world::DisplaysSnapshot,
@@ -79,6 +113,21 @@ pub(super) fn enc_world_snapshot(snapshot: &WorldSnapshotLite) -> crate::Result<
     value::binary_param(snapshot)
 }
 
+/// Encode running processes to a msgpack binary value.
+pub(super) fn enc_processes(processes: &[ProcessInfo]) -> crate::Result<Value> {
+    value::binary_param(&processes)
+}
+
+/// Encode supervised service statuses to a msgpack binary value.
+pub(super) fn enc_services(services: &[ServiceStatus]) -> crate::Result<Value> {
+    value::binary_param(&services)
+}
+
+/// Encode a chord explanation to a msgpack binary value.
+pub(super) fn enc_explanation(explanation: &ChordExplanation) -> crate::Result<Value> {
+    value::binary_param(explanation)
+}
+
 /// Decode an `inject_key` parameter from msgpack binary.
 pub(crate) fn dec_inject_key_param(value: &Value) -> Result<InjectKeyReq, RpcError> {
     match value {
@@ -9,8 +9,9 @@
 //! - a lightweight window list
 //! - display geometry snapshots
 //!
-//! There are no mutating commands (place/hide/focus/raise); callers should use
-//! external tooling for window control. The exported surface is intentionally
+//! The only mutating commands are application-level activation, hiding, and
+//! quitting by pid; there is no window placement or per-window raising, and
+//! callers should use external tooling for window control. The exported surface is intentionally
 //! small and stable: [`WorldView`] for querying state, [`World`] helpers for
 //! spawning, and the data carriers defined below.
 
@@ -34,8 +35,8 @@ use polling::PollingWorld;
 #[cfg(any(test, feature = "test-utils"))]
 pub use test_world::{TestApplication, TestWorld};
 pub use types::{
-    ApplicationResolution, FocusChange, WindowKey, WorldCfg, WorldEvent, WorldStatus, WorldView,
-    WorldWindow, focus_snapshot, subscribe_with_snapshot,
+    ApplicationCommand, ApplicationQuery, ApplicationResolution, FocusChange, WindowKey, WorldCfg,
+    WorldEvent, WorldStatus, WorldView, WorldWindow, focus_snapshot, subscribe_with_snapshot,
 };
 
 /// Public helpers to spawn world views.
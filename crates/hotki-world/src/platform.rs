@@ -1,9 +1,12 @@
+use std::ptr;
+
 use core_foundation::{
     array::CFArray,
-    base::{CFType, TCFType},
+    base::{CFType, CFTypeRef, TCFType},
+    boolean::CFBoolean,
     dictionary::CFDictionary,
     number::CFNumber,
-    string::CFString,
+    string::{CFString, CFStringRef},
 };
 use core_graphics::{
     geometry::{CGPoint, CGRect, CGSize},
@@ -13,42 +16,138 @@ use core_graphics::{
         kCGWindowNumber, kCGWindowOwnerName, kCGWindowOwnerPID,
     },
 };
-use objc2_app_kit::{NSRunningApplication, NSWorkspace};
+use objc2_app_kit::{NSApplicationActivationOptions, NSRunningApplication, NSWorkspace};
 use objc2_foundation::NSString;
 use permissions::{accessibility_ok, input_monitoring_ok, screen_recording_ok};
 
 use crate::{
-    ApplicationResolution, Capabilities, DisplayFrame, DisplaysSnapshot,
+    ApplicationCommand, ApplicationQuery, ApplicationResolution, Capabilities, DisplayFrame,
+    DisplaysSnapshot,
     geometry::{display_for_rect, gather_displays},
     types::{RunningApplication, resolve_application},
 };
 
-/// Resolve a running application by exact AppKit localized name.
+/// Status returned by accessibility calls that succeed.
+const AX_SUCCESS: i32 = 0;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+unsafe extern "C" {
+    fn AXUIElementCreateApplication(pid: i32) -> CFTypeRef;
+    fn AXUIElementCopyAttributeValue(
+        element: CFTypeRef,
+        attribute: CFStringRef,
+        value: *mut CFTypeRef,
+    ) -> i32;
+    fn AXUIElementSetAttributeValue(
+        element: CFTypeRef,
+        attribute: CFStringRef,
+        value: CFTypeRef,
+    ) -> i32;
+    fn AXUIElementPerformAction(element: CFTypeRef, action: CFStringRef) -> i32;
+    /// Private, long-stable call mapping an accessibility window to its CoreGraphics number.
+    fn _AXUIElementGetWindow(element: CFTypeRef, window: *mut u32) -> i32;
+}
+
+/// Resolve a running application by exact localized name, bundle identifier, or pid.
 ///
 /// The installed AppKit binding permits these calls on background threads. Its
 /// running-application list is maintained by the process's main run loop, so
 /// callers rely on Hotki's active tao loop for launch and termination freshness.
-pub(crate) fn resolve_running_application(app_name: &str) -> ApplicationResolution {
+pub(crate) fn resolve_running_application(query: ApplicationQuery<'_>) -> ApplicationResolution {
     let applications = NSWorkspace::sharedWorkspace().runningApplications();
     let applications: Vec<_> = applications
         .iter()
         .map(|application| RunningApplication {
             name: localized_application_name(&application),
+            bundle_id: application
+                .bundleIdentifier()
+                .map(|bundle_id| bundle_id.to_string()),
             pid: application.processIdentifier(),
             terminated: application.isTerminated(),
         })
         .collect();
-    let matching_pids: Vec<_> = applications
-        .iter()
-        .filter(|application| application.name.as_deref() == Some(app_name))
-        .map(|application| application.pid)
-        .collect();
-    tracing::trace!(
-        app_name,
-        ?matching_pids,
-        "resolved_running_application_name"
-    );
-    resolve_application(&applications, app_name)
+    let resolution = resolve_application(&applications, query);
+    tracing::trace!(?query, ?resolution, "resolved_running_application");
+    resolution
+}
+
+/// Apply a lifecycle command to a running application by process identifier.
+///
+/// Activation is application-level: AppKit brings the application's own
+/// frontmost window forward. Raising a window first makes it the application's
+/// main window through the accessibility API. Quitting asks the application to
+/// terminate normally, so it may still prompt for unsaved work.
+pub(crate) fn control_running_application(pid: i32, command: ApplicationCommand) -> bool {
+    let Some(application) = NSRunningApplication::runningApplicationWithProcessIdentifier(pid)
+    else {
+        tracing::trace!(pid, ?command, "control_application_not_running");
+        return false;
+    };
+    if application.isTerminated() {
+        return false;
+    }
+    match command {
+        ApplicationCommand::Activate => {
+            application.activateWithOptions(NSApplicationActivationOptions::ActivateAllWindows)
+        }
+        ApplicationCommand::RaiseWindow(id) => {
+            raise_window(pid, id)
+                && application.activateWithOptions(NSApplicationActivationOptions::empty())
+        }
+        ApplicationCommand::Hide => application.hide(),
+        ApplicationCommand::Quit => application.terminate(),
+    }
+}
+
+/// Raise the window of `pid` numbered `id` and make it the application's main window,
+/// returning false when the application has no such window.
+fn raise_window(pid: i32, id: u32) -> bool {
+    // SAFETY: creating an application element has no preconditions, and the returned
+    // reference follows the create rule.
+    let application = unsafe { CFType::wrap_under_create_rule(AXUIElementCreateApplication(pid)) };
+    let windows_key = CFString::from_static_string("AXWindows");
+    let mut value: CFTypeRef = ptr::null();
+    // SAFETY: `application` is a live element and `value` is a valid out pointer. A successful
+    // copy hands back an owned reference, which is wrapped under the create rule below.
+    let status = unsafe {
+        AXUIElementCopyAttributeValue(
+            application.as_CFTypeRef(),
+            windows_key.as_concrete_TypeRef(),
+            &mut value,
+        )
+    };
+    if status != AX_SUCCESS || value.is_null() {
+        tracing::trace!(pid, id, status, "raise_window_no_windows");
+        return false;
+    }
+    // SAFETY: `AXWindows` is an array of window elements, and this function owns `value`.
+    let windows: CFArray<CFType> = unsafe { CFArray::wrap_under_create_rule(value as _) };
+    let Some(window) = windows.iter().find(|window| {
+        let mut number = 0;
+        // SAFETY: `window` is an element retained by `windows`, and `number` is a valid out
+        // pointer.
+        let status = unsafe { _AXUIElementGetWindow(window.as_CFTypeRef(), &mut number) };
+        status == AX_SUCCESS && number == id
+    }) else {
+        tracing::trace!(pid, id, "raise_window_not_found");
+        return false;
+    };
+    let main_key = CFString::from_static_string("AXMain");
+    let raise_action = CFString::from_static_string("AXRaise");
+    // SAFETY: `window` stays retained by `windows`, and the attribute, action, and boolean
+    // values are live Core Foundation objects for the duration of both calls.
+    let (main, raised) = unsafe {
+        (
+            AXUIElementSetAttributeValue(
+                window.as_CFTypeRef(),
+                main_key.as_concrete_TypeRef(),
+                CFBoolean::true_value().as_CFTypeRef(),
+            ),
+            AXUIElementPerformAction(window.as_CFTypeRef(), raise_action.as_concrete_TypeRef()),
+        )
+    };
+    tracing::trace!(pid, id, main, raised, "raise_window");
+    raised == AX_SUCCESS
 }
 
 fn localized_application_name(application: &NSRunningApplication) -> Option<String> {
@@ -93,8 +93,15 @@ impl CoreWorldView for PollingWorld {
         &self.core
     }
 
-    fn resolve_application_impl(&self, app_name: &str) -> crate::ApplicationResolution {
-        crate::platform::resolve_running_application(app_name)
+    fn resolve_application_impl(
+        &self,
+        query: crate::ApplicationQuery<'_>,
+    ) -> crate::ApplicationResolution {
+        crate::platform::resolve_running_application(query)
+    }
+
+    fn control_application_impl(&self, pid: i32, command: crate::ApplicationCommand) -> bool {
+        crate::platform::control_running_application(pid, command)
     }
 
     async fn refresh_impl(&self) {
@@ -166,7 +166,12 @@ impl WorldPollChanges {
 pub(crate) trait CoreWorldView {
     fn core(&self) -> &Arc<WorldCore>;
 
-    fn resolve_application_impl(&self, app_name: &str) -> crate::ApplicationResolution;
+    fn resolve_application_impl(
+        &self,
+        query: crate::ApplicationQuery<'_>,
+    ) -> crate::ApplicationResolution;
+
+    fn control_application_impl(&self, pid: i32, command: crate::ApplicationCommand) -> bool;
 
     async fn refresh_impl(&self);
 }
@@ -208,8 +213,15 @@ where
         self.core().state.status()
     }
 
-    fn resolve_application(&self, app_name: &str) -> crate::ApplicationResolution {
-        self.resolve_application_impl(app_name)
+    fn resolve_application(
+        &self,
+        query: crate::ApplicationQuery<'_>,
+    ) -> crate::ApplicationResolution {
+        self.resolve_application_impl(query)
+    }
+
+    fn control_application(&self, pid: i32, command: crate::ApplicationCommand) -> bool {
+        self.control_application_impl(pid, command)
     }
 
     fn displays(&self) -> DisplaysSnapshot {
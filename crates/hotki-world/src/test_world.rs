@@ -4,7 +4,8 @@ use async_trait::async_trait;
 use parking_lot::RwLock;
 
 use crate::{
-    ApplicationResolution, DisplaysSnapshot, WorldEvent, WorldWindow,
+    ApplicationCommand, ApplicationQuery, ApplicationResolution, DisplaysSnapshot, WorldEvent,
+    WorldWindow,
     state::{CoreWorldView, WorldCore},
     types::{RunningApplication, WindowKey, resolve_application},
 };
@@ -14,6 +15,8 @@ use crate::{
 pub struct TestApplication {
     /// Exact localized application name, or `None` when unavailable.
     pub name: Option<String>,
+    /// Bundle identifier, or `None` when unavailable.
+    pub bundle_id: Option<String>,
     /// Process identifier reported by AppKit.
     pub pid: i32,
     /// Whether AppKit reports that the process has terminated.
@@ -24,6 +27,7 @@ pub struct TestApplication {
 pub struct TestWorld {
     core: Arc<WorldCore>,
     applications: RwLock<Vec<RunningApplication>>,
+    commands: RwLock<Vec<(i32, ApplicationCommand)>>,
 }
 
 impl TestWorld {
@@ -33,6 +37,7 @@ impl TestWorld {
         Self {
             core: WorldCore::new(),
             applications: RwLock::new(Vec::new()),
+            commands: RwLock::new(Vec::new()),
         }
     }
 
@@ -64,11 +69,30 @@ impl TestWorld {
             .into_iter()
             .map(|application| RunningApplication {
                 name: application.name,
+                bundle_id: application.bundle_id,
                 pid: application.pid,
                 terminated: application.terminated,
             })
             .collect();
     }
+
+    /// Process identifiers passed to successful activation requests, in order.
+    #[must_use]
+    pub fn activations(&self) -> Vec<i32> {
+        self.commands
+            .read()
+            .iter()
+            .filter(|(_, command)| *command == ApplicationCommand::Activate)
+            .map(|(pid, _)| *pid)
+            .collect()
+    }
+
+    /// Successful lifecycle commands as `(pid, command)` pairs, in order. Raising a window
+    /// succeeds only when the current snapshot contains it.
+    #[must_use]
+    pub fn application_commands(&self) -> Vec<(i32, ApplicationCommand)> {
+        self.commands.read().clone()
+    }
 }
 
 impl Default for TestWorld {
@@ -83,8 +107,36 @@ impl CoreWorldView for TestWorld {
         &self.core
     }
 
-    fn resolve_application_impl(&self, app_name: &str) -> ApplicationResolution {
-        resolve_application(&self.applications.read(), app_name)
+    fn resolve_application_impl(&self, query: ApplicationQuery<'_>) -> ApplicationResolution {
+        resolve_application(&self.applications.read(), query)
+    }
+
+    fn control_application_impl(&self, pid: i32, command: ApplicationCommand) -> bool {
+        if let ApplicationCommand::RaiseWindow(id) = command
+            && !self
+                .core
+                .state
+                .snapshot()
+                .iter()
+                .any(|window| window.pid == pid && window.id == id)
+        {
+            return false;
+        }
+        let mut applications = self.applications.write();
+        let mut running = false;
+        for application in applications
+            .iter_mut()
+            .filter(|application| application.pid == pid && !application.terminated)
+        {
+            running = true;
+            if command == ApplicationCommand::Quit {
+                application.terminated = true;
+            }
+        }
+        if running {
+            self.commands.write().push((pid, command));
+        }
+        running
     }
 
     async fn refresh_impl(&self) {}
@@ -98,6 +150,7 @@ mod tests {
     fn application(name: Option<&str>, pid: i32, terminated: bool) -> TestApplication {
         TestApplication {
             name: name.map(str::to_string),
+            bundle_id: None,
             pid,
             terminated,
         }
@@ -117,11 +170,11 @@ mod tests {
         ]);
 
         assert_eq!(
-            world.resolve_application("YouTube Music"),
+            world.resolve_application(ApplicationQuery::Name("YouTube Music")),
             ApplicationResolution::Found(41)
         );
         assert_eq!(
-            world.resolve_application("Missing"),
+            world.resolve_application(ApplicationQuery::Name("Missing")),
             ApplicationResolution::NotRunning
         );
 
@@ -131,8 +184,93 @@ mod tests {
             application(Some("YouTube Music"), 45, false),
         ]);
         assert_eq!(
-            world.resolve_application("YouTube Music"),
+            world.resolve_application(ApplicationQuery::Name("YouTube Music")),
+            ApplicationResolution::Ambiguous(2)
+        );
+    }
+
+    #[test]
+    fn bundle_and_pid_resolution_cover_missing_and_ambiguous_processes() {
+        let world = TestWorld::new();
+        let mut helper = application(Some("Helper"), 51, false);
+        helper.bundle_id = Some("com.example.helper".to_string());
+        let mut second = application(Some("Helper"), 52, false);
+        second.bundle_id = Some("com.example.helper".to_string());
+        let mut exited = application(Some("Editor"), 53, true);
+        exited.bundle_id = Some("com.example.editor".to_string());
+        world.set_running_applications(vec![helper.clone(), exited]);
+
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::BundleId("com.example.helper")),
+            ApplicationResolution::Found(51)
+        );
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::BundleId("com.example.editor")),
+            ApplicationResolution::NotRunning
+        );
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::BundleId("Helper")),
+            ApplicationResolution::NotRunning
+        );
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::Pid(51)),
+            ApplicationResolution::Found(51)
+        );
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::Pid(53)),
+            ApplicationResolution::NotRunning
+        );
+
+        world.set_running_applications(vec![helper, second]);
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::BundleId("com.example.helper")),
             ApplicationResolution::Ambiguous(2)
         );
     }
+
+    #[test]
+    fn activation_records_only_running_processes() {
+        let world = TestWorld::new();
+        world.set_running_applications(vec![
+            application(Some("Safari"), 10, false),
+            application(Some("Mail"), 11, true),
+        ]);
+
+        assert!(world.control_application(10, ApplicationCommand::Activate));
+        assert!(!world.control_application(11, ApplicationCommand::Activate));
+        assert!(!world.control_application(12, ApplicationCommand::Activate));
+        assert_eq!(world.activations(), vec![10]);
+    }
+
+    #[test]
+    fn lifecycle_commands_are_recorded_and_quit_terminates() {
+        let world = TestWorld::new();
+        let mut music = application(Some("Music"), 20, false);
+        music.bundle_id = Some("com.apple.Music".to_string());
+        world.set_running_applications(vec![music, application(Some("Notes"), 21, false)]);
+
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::NameOrBundleId("com.apple.Music")),
+            ApplicationResolution::Found(20)
+        );
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::NameOrBundleId("Music")),
+            ApplicationResolution::Found(20)
+        );
+        assert!(world.control_application(21, ApplicationCommand::Hide));
+        assert!(world.control_application(20, ApplicationCommand::Quit));
+        assert!(!world.control_application(20, ApplicationCommand::Activate));
+
+        assert_eq!(
+            world.application_commands(),
+            vec![
+                (21, ApplicationCommand::Hide),
+                (20, ApplicationCommand::Quit)
+            ]
+        );
+        assert_eq!(
+            world.resolve_application(ApplicationQuery::NameOrBundleId("Music")),
+            ApplicationResolution::NotRunning
+        );
+    }
 }
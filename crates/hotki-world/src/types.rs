@@ -4,14 +4,41 @@ use tokio::time::Instant as TokioInstant;
 
 use crate::{Capabilities, DisplaysSnapshot, EventCursor, FocusSnapshot};
 
-/// Result of resolving one exact running application name.
+/// Identity used to resolve one running application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApplicationQuery<'a> {
+    /// Exact AppKit localized name.
+    Name(&'a str),
+    /// Exact bundle identifier.
+    BundleId(&'a str),
+    /// Exact AppKit localized name or exact bundle identifier.
+    NameOrBundleId(&'a str),
+    /// Process identifier.
+    Pid(i32),
+}
+
+/// Lifecycle command applied to one running application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ApplicationCommand {
+    /// Bring the application and its windows to the front.
+    Activate,
+    /// Bring the application to the front with one window, identified by its CoreGraphics
+    /// window number, in front of its other windows.
+    RaiseWindow(u32),
+    /// Hide every window of the application.
+    Hide,
+    /// Ask the application to quit normally.
+    Quit,
+}
+
+/// Result of resolving one running application query.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ApplicationResolution {
-    /// Exactly one running process has the requested localized name.
+    /// Exactly one running process matches the query.
     Found(i32),
-    /// No running process has the requested localized name.
+    /// No running process matches the query.
     NotRunning,
-    /// Multiple distinct running processes have the requested localized name.
+    /// Multiple distinct running processes match the query.
     Ambiguous(usize),
 }
 
@@ -19,21 +46,34 @@ pub enum ApplicationResolution {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct RunningApplication {
     pub(crate) name: Option<String>,
+    pub(crate) bundle_id: Option<String>,
     pub(crate) pid: i32,
     pub(crate) terminated: bool,
 }
 
-/// Resolve an exact localized name from a running-application snapshot.
+impl RunningApplication {
+    /// Whether this record satisfies `query`.
+    fn matches(&self, query: ApplicationQuery<'_>) -> bool {
+        match query {
+            ApplicationQuery::Name(name) => self.name.as_deref() == Some(name),
+            ApplicationQuery::BundleId(bundle_id) => self.bundle_id.as_deref() == Some(bundle_id),
+            ApplicationQuery::NameOrBundleId(value) => {
+                self.name.as_deref() == Some(value) || self.bundle_id.as_deref() == Some(value)
+            }
+            ApplicationQuery::Pid(pid) => self.pid == pid,
+        }
+    }
+}
+
+/// Resolve a query against a running-application snapshot.
 pub(crate) fn resolve_application(
     applications: &[RunningApplication],
-    app_name: &str,
+    query: ApplicationQuery<'_>,
 ) -> ApplicationResolution {
     let mut pids: Vec<_> = applications
         .iter()
         .filter(|application| {
-            !application.terminated
-                && application.pid > 0
-                && application.name.as_deref() == Some(app_name)
+            !application.terminated && application.pid > 0 && application.matches(query)
         })
         .map(|application| application.pid)
         .collect();
@@ -183,8 +223,12 @@ pub trait WorldView: Send + Sync {
     /// Fetch comprehensive world status diagnostics.
     fn status(&self) -> WorldStatus;
 
-    /// Resolve one exact AppKit localized name to a running process.
-    fn resolve_application(&self, app_name: &str) -> ApplicationResolution;
+    /// Resolve a localized name, bundle identifier, or pid to one running process.
+    fn resolve_application(&self, query: ApplicationQuery<'_>) -> ApplicationResolution;
+
+    /// Apply `command` to the running application owning `pid`, returning false when it is
+    /// unavailable or refuses the request.
+    fn control_application(&self, pid: i32, command: ApplicationCommand) -> bool;
 
     /// Retrieve the tracked display geometry snapshot.
     fn displays(&self) -> DisplaysSnapshot;
@@ -2,17 +2,27 @@
 //! Command-line entrypoint for Hotki tooling.
 
 use std::{
+    fs,
+    io::{self, BufRead, Write},
     path::{Path, PathBuf},
     process,
 };
 
 use clap::{Parser, Subcommand, ValueEnum};
 use config::{
-    LuauApiSurface, StyleResolver, check_luau_config, default_style_source, luau_api_markdown,
-    luau_api_text, resolve_config_path,
+    CheatSheet, ChordExplanation, EDITOR_CONFIG_DEFINITIONS, EDITOR_STYLE_DEFINITIONS,
+    FocusSnapshot, LintFinding, LuauApiSurface, LuauCheckReport, ReplSession, Severity,
+    StyleResolver, binding_tree, check_luau_config, default_style_source, discover_specs,
+    explain_chord, import_skhd, lint_config, luau_api_markdown, luau_api_text, resolve_config_path,
+    run_spec_file, write_editor_setup,
 };
 use tracing_subscriber::{fmt, prelude::*};
 
+/// Exit status when the configuration is rejected.
+const EXIT_CONFIG: i32 = 1;
+/// Exit status when the configuration could not be read (`EX_IOERR`).
+const EXIT_IO: i32 = 74;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "hotki",
@@ -52,7 +62,48 @@ enum Command {
         #[arg(long, value_name = "TEXT")]
         filter: Option<String>,
     },
+    /// Print every binding reachable from the root mode for a synthetic focused window.
+    Bindings {
+        /// Path to configuration file (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
+
+        /// Application name of the synthetic focused window.
+        #[arg(long, value_name = "NAME")]
+        app: Option<String>,
+
+        /// Title of the synthetic focused window.
+        #[arg(long, value_name = "TEXT", requires = "app")]
+        title: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = BindingsFormat::Text)]
+        format: BindingsFormat,
+    },
+    /// Print a cheat sheet of every reachable menu, styled with the effective HUD colours.
+    Cheatsheet {
+        /// Path to configuration file (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = CheatSheetFormat::Markdown)]
+        format: CheatSheetFormat,
+
+        /// Include bindings hidden from the HUD.
+        #[arg(long)]
+        include_hidden: bool,
+
+        /// Application name of the synthetic focused window.
+        #[arg(long, value_name = "NAME")]
+        app: Option<String>,
+
+        /// Title of the synthetic focused window.
+        #[arg(long, value_name = "TEXT", requires = "app")]
+        title: Option<String>,
+    },
     /// Load and validate the configuration then exit.
+    ///
+    /// Every handler binding runs to validate the exec specs it queues; the actions are
+    /// discarded, never executed.
     Check {
         /// Path to configuration file to check (defaults to ~/.hotki/config.luau).
         path: Option<String>,
@@ -60,6 +111,70 @@ enum Command {
         /// Dump the parsed configuration as JSON to stdout.
         #[arg(long)]
         dump: bool,
+
+        /// Fail when binding analysis reports any warning.
+        #[arg(long)]
+        strict: bool,
+
+        /// Output format; `json` prints every diagnostic and the report as one JSON object.
+        #[arg(long, value_enum, default_value_t = CheckFormat::Text)]
+        format: CheckFormat,
+    },
+    /// Write luau-lsp definition files and editor settings into the config directory.
+    EditorSetup {
+        /// Path to configuration file (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
+    },
+    /// Explain how a chord resolves for a synthetic focused window and mode path.
+    Explain {
+        /// Chord to explain, such as `cmd+shift+k`.
+        chord: String,
+
+        /// Path to configuration file (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
+
+        /// Application name of the synthetic focused window.
+        #[arg(long, value_name = "NAME")]
+        app: Option<String>,
+
+        /// Title of the synthetic focused window.
+        #[arg(long, value_name = "TEXT", requires = "app")]
+        title: Option<String>,
+
+        /// Title of a submenu to enter, matched exactly; repeat to enter nested submenus in order.
+        #[arg(long = "path", value_name = "MODE")]
+        mode: Vec<String>,
+
+        /// Print the explanation as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Translate another hotkey tool's configuration into a Hotki config.
+    Import {
+        /// Configuration format to import.
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// Report best-practice lints; silence one with `-- hotki-lint: allow(<id>)`.
+    ///
+    /// Every handler binding runs to collect the actions it queues; the actions are discarded,
+    /// never executed.
+    Lint {
+        /// Path to configuration file to lint (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
+
+        /// Fail on warnings as well as errors.
+        #[arg(long)]
+        strict: bool,
+
+        /// Output format; `json` prints every finding as one JSON object.
+        #[arg(long, value_enum, default_value_t = CheckFormat::Text)]
+        format: CheckFormat,
+    },
+    /// Evaluate Luau lines against the configuration; effects are printed, never executed.
+    Repl {
+        /// Path to configuration file (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
     },
     /// Print style source or the resolved effective style.
     Style {
@@ -70,6 +185,25 @@ enum Command {
         #[arg(long)]
         default: bool,
     },
+    /// Run the `*.spec.luau` files next to the configuration.
+    Test {
+        /// Path to configuration file to test (defaults to ~/.hotki/config.luau).
+        path: Option<String>,
+    },
+}
+
+/// Configuration formats accepted by `hotki import`.
+#[derive(Subcommand, Debug)]
+enum ImportSource {
+    /// Translate an skhdrc file; untranslated constructs are kept as comments.
+    Skhd {
+        /// Path to the skhdrc file.
+        file: PathBuf,
+
+        /// Write the config to this path instead of stdout; an existing file is not replaced.
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
 }
 
 /// Luau API surfaces exposed by `hotki api`.
@@ -83,6 +217,35 @@ enum ApiSurface {
     All,
 }
 
+/// Output formats for `hotki bindings`.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum BindingsFormat {
+    /// Indented plain-text tree.
+    Text,
+    /// Pretty-printed JSON.
+    Json,
+    /// One markdown table per mode.
+    Markdown,
+}
+
+/// Output formats for `hotki cheatsheet`.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CheatSheetFormat {
+    /// One markdown table per menu.
+    Markdown,
+    /// Self-contained HTML page for printing.
+    Html,
+}
+
+/// Output formats for `hotki check` and `hotki lint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum CheckFormat {
+    /// Human-readable diagnostics on stderr.
+    Text,
+    /// One JSON object on stdout.
+    Json,
+}
+
 impl From<ApiSurface> for LuauApiSurface {
     fn from(surface: ApiSurface) -> Self {
         match surface {
@@ -102,12 +265,75 @@ fn main() {
             markdown,
             filter,
         } => run_api_command(*surface, *markdown, filter.as_deref()),
-        Command::Check { path, dump } => {
-            run_check_command(path.as_deref(), cli.config.as_deref(), *dump);
+        Command::Bindings {
+            path,
+            app,
+            title,
+            format,
+        } => run_bindings_command(
+            path.as_deref(),
+            cli.config.as_deref(),
+            app.as_deref(),
+            title.as_deref(),
+            *format,
+        ),
+        Command::Cheatsheet {
+            path,
+            format,
+            include_hidden,
+            app,
+            title,
+        } => {
+            let window = synthetic_window(app.as_deref(), title.as_deref());
+            let resolved = resolve_cli_config_path(path.as_deref(), cli.config.as_deref());
+            run_cheatsheet_command(&resolved, window, *format, *include_hidden);
         }
+        Command::Check {
+            path,
+            dump,
+            strict,
+            format,
+        } => {
+            let options = CheckOptions {
+                dump: *dump,
+                strict: *strict,
+                format: *format,
+            };
+            run_check_command(path.as_deref(), cli.config.as_deref(), options);
+        }
+        Command::EditorSetup { path } => {
+            run_editor_setup_command(path.as_deref(), cli.config.as_deref());
+        }
+        Command::Explain {
+            chord,
+            path,
+            app,
+            title,
+            mode,
+            json,
+        } => run_explain_command(
+            path.as_deref(),
+            cli.config.as_deref(),
+            chord,
+            app.as_deref(),
+            title.as_deref(),
+            mode,
+            *json,
+        ),
+        Command::Import { source } => run_import_command(source),
+        Command::Lint {
+            path,
+            strict,
+            format,
+        } => {
+            let resolved = resolve_cli_config_path(path.as_deref(), cli.config.as_deref());
+            run_lint_command(&resolved, *strict, *format);
+        }
+        Command::Repl { path } => run_repl_command(path.as_deref(), cli.config.as_deref()),
         Command::Style { path, default } => {
             run_style_command(path.as_deref(), cli.config.as_deref(), *default);
         }
+        Command::Test { path } => run_test_command(path.as_deref(), cli.config.as_deref()),
     }
 }
 
@@ -136,23 +362,438 @@ fn run_api_command(surface: ApiSurface, markdown: bool, filter: Option<&str>) {
     print!("{output}");
 }
 
-/// Validate a config file and optionally dump its resolved style.
-fn run_check_command(path: Option<&str>, cli_config: Option<&Path>, dump: bool) {
+/// Flags accepted by `hotki check`.
+#[derive(Clone, Copy, Debug)]
+struct CheckOptions {
+    /// Dump the resolved style instead of the summary line.
+    dump: bool,
+    /// Fail when binding analysis reports any warning.
+    strict: bool,
+    /// Output format.
+    format: CheckFormat,
+}
+
+/// Validate a config file, report binding warnings, and optionally dump its resolved style.
+///
+/// Exits with [`EXIT_CONFIG`] when the config is rejected (or has warnings under `--strict`) and
+/// with [`EXIT_IO`] when it could not be read.
+fn run_check_command(path: Option<&str>, cli_config: Option<&Path>, options: CheckOptions) {
     let resolved = resolve_cli_config_path(path, cli_config);
-    let report = match check_luau_config(&resolved) {
+    let result = check_luau_config(&resolved);
+    if options.format == CheckFormat::Json {
+        print_check_json(&result, options.strict);
+        return;
+    }
+    let report = match result {
         Ok(report) => report,
         Err(e) => {
             eprintln!("{}", e.pretty());
-            process::exit(1);
+            process::exit(error_exit_code(&e));
         }
     };
-    if dump {
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
+    for path in &report.stale_definitions {
+        eprintln!(
+            "warning: {} is out of date; rerun `hotki editor-setup`",
+            path.display()
+        );
+    }
+    if options.strict && !report.warnings.is_empty() {
+        eprintln!(
+            "{} binding warning(s); failing because of --strict",
+            report.warnings.len()
+        );
+        process::exit(EXIT_CONFIG);
+    }
+    if options.dump {
         dump_resolved_style(&report.resolved_style);
     } else {
         println!("OK (modules: {}, style: {})", report.modules, report.style);
     }
 }
 
+/// Print a check result as one JSON object and exit with the matching status.
+fn print_check_json(result: &Result<LuauCheckReport, config::Error>, strict: bool) {
+    let (diagnostics, report, code) = match result {
+        Ok(report) => {
+            let code = if strict && !report.warnings.is_empty() {
+                EXIT_CONFIG
+            } else {
+                0
+            };
+            (report.diagnostics(), Some(report), code)
+        }
+        Err(e) => (e.diagnostics(), None, error_exit_code(e)),
+    };
+    let output = serde_json::json!({
+        "ok": code == 0,
+        "diagnostics": diagnostics,
+        "report": report,
+    });
+    match serde_json::to_string_pretty(&output) {
+        Ok(json) => println!("{json}"),
+        Err(e) => {
+            eprintln!("failed to serialize check result: {e}");
+            process::exit(EXIT_CONFIG);
+        }
+    }
+    if code != 0 {
+        process::exit(code);
+    }
+}
+
+/// Lint a config file and print its findings.
+///
+/// Exits with [`EXIT_CONFIG`] when the config is rejected or a lint of error severity fires (any
+/// lint under `--strict`) and with [`EXIT_IO`] when it could not be read.
+fn run_lint_command(resolved: &Path, strict: bool, format: CheckFormat) {
+    let (diagnostics, code) = match lint_config(resolved) {
+        Ok(findings) => {
+            let failing = findings
+                .iter()
+                .filter(|finding| strict || finding.kind.severity() == Severity::Error)
+                .count();
+            if format == CheckFormat::Text {
+                for finding in &findings {
+                    let label = match finding.kind.severity() {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                    };
+                    eprintln!("{label}: {finding}");
+                }
+                if findings.is_empty() {
+                    println!("OK (no lint findings)");
+                } else if failing > 0 {
+                    eprintln!("{failing} failing lint finding(s)");
+                }
+            }
+            let diagnostics = findings.iter().map(LintFinding::to_diagnostic).collect();
+            (diagnostics, if failing > 0 { EXIT_CONFIG } else { 0 })
+        }
+        Err(e) => {
+            if format == CheckFormat::Text {
+                eprintln!("{}", e.pretty());
+            }
+            (e.diagnostics(), error_exit_code(&e))
+        }
+    };
+    if format == CheckFormat::Json {
+        let output = serde_json::json!({
+            "ok": code == 0,
+            "diagnostics": diagnostics,
+        });
+        match serde_json::to_string_pretty(&output) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("failed to serialize lint result: {e}");
+                process::exit(EXIT_CONFIG);
+            }
+        }
+    }
+    if code != 0 {
+        process::exit(code);
+    }
+}
+
+/// Exit status for a config error, distinguishing filesystem failures.
+fn error_exit_code(error: &config::Error) -> i32 {
+    if error.is_io() { EXIT_IO } else { EXIT_CONFIG }
+}
+
+/// Print the binding tree rendered for an optional synthetic focused window.
+fn run_bindings_command(
+    path: Option<&str>,
+    cli_config: Option<&Path>,
+    app: Option<&str>,
+    title: Option<&str>,
+    format: BindingsFormat,
+) {
+    let resolved = resolve_cli_config_path(path, cli_config);
+    let window = synthetic_window(app, title);
+    let tree = match binding_tree(&resolved, window) {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!("{}", e.pretty());
+            process::exit(1);
+        }
+    };
+    match format {
+        BindingsFormat::Text => print!("{}", tree.to_text()),
+        BindingsFormat::Markdown => print!("{}", tree.to_markdown()),
+        BindingsFormat::Json => match serde_json::to_string_pretty(&tree) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("failed to serialize bindings: {e}");
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Print a cheat sheet of the binding tree styled with the resolved HUD style.
+fn run_cheatsheet_command(
+    resolved: &Path,
+    window: Option<FocusSnapshot>,
+    format: CheatSheetFormat,
+    include_hidden: bool,
+) {
+    let loaded = binding_tree(resolved, window).and_then(|tree| {
+        let style = StyleResolver::from_config_path(resolved)?.resolve()?.style;
+        Ok((tree, style))
+    });
+    let (tree, style) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e.pretty());
+            process::exit(error_exit_code(&e));
+        }
+    };
+    let sheet = CheatSheet::new(&tree, &style.hud).include_hidden(include_hidden);
+    match format {
+        CheatSheetFormat::Markdown => print!("{}", sheet.to_markdown()),
+        CheatSheetFormat::Html => print!("{}", sheet.to_html()),
+    }
+}
+
+/// Synthetic focused window for commands that render menus without a live focus.
+fn synthetic_window(app: Option<&str>, title: Option<&str>) -> Option<FocusSnapshot> {
+    app.map(|app| FocusSnapshot {
+        id: 0,
+        app: app.to_string(),
+        title: title.unwrap_or_default().to_string(),
+        pid: 0,
+        display_id: None,
+    })
+}
+
+/// Write editor integration files next to the configuration.
+fn run_editor_setup_command(path: Option<&str>, cli_config: Option<&Path>) {
+    let resolved = resolve_cli_config_path(path, cli_config);
+    let Some(dir) = resolved.parent() else {
+        eprintln!(
+            "config path has no parent directory: {}",
+            resolved.display()
+        );
+        process::exit(EXIT_IO);
+    };
+    let setup = match write_editor_setup(dir) {
+        Ok(setup) => setup,
+        Err(e) => {
+            eprintln!("{}", e.pretty());
+            process::exit(error_exit_code(&e));
+        }
+    };
+    for path in &setup.written {
+        println!("wrote {}", path.display());
+    }
+    for path in &setup.kept {
+        println!("kept {}", path.display());
+    }
+    for path in &setup.manual {
+        println!(
+            "left {} unchanged: it is not plain JSON (JSONC comments or trailing commas are \
+             common), so add \"{EDITOR_CONFIG_DEFINITIONS}\" and \"{EDITOR_STYLE_DEFINITIONS}\" \
+             to \"luau-lsp.types.definitionFiles\" by hand",
+            path.display()
+        );
+    }
+    println!(
+        "Neovim and other editors: pass `--definitions={EDITOR_CONFIG_DEFINITIONS}` and \
+         `--definitions={EDITOR_STYLE_DEFINITIONS}` to luau-lsp."
+    );
+}
+
+/// Explain how a chord resolves in the configuration for a synthetic context.
+fn run_explain_command(
+    path: Option<&str>,
+    cli_config: Option<&Path>,
+    chord: &str,
+    app: Option<&str>,
+    title: Option<&str>,
+    mode: &[String],
+    json: bool,
+) {
+    let resolved = resolve_cli_config_path(path, cli_config);
+    let window = synthetic_window(app, title);
+    let explanation = match explain_chord(&resolved, window, mode, chord) {
+        Ok(explanation) => explanation,
+        Err(e) => {
+            eprintln!("{}", e.pretty());
+            process::exit(1);
+        }
+    };
+    if json {
+        match serde_json::to_string_pretty(&explanation) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("failed to serialize explanation: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        print_explanation(&explanation);
+    }
+}
+
+/// Print a chord explanation as a readable report.
+fn print_explanation(explanation: &ChordExplanation) {
+    println!("chord: {}", explanation.chord);
+    println!("mode: {}", explanation.mode.join(" › "));
+    match &explanation.matched {
+        Some(binding) => {
+            let kind = if binding.is_mode {
+                "enters submenu"
+            } else {
+                "runs"
+            };
+            println!(
+                "matched: \"{}\" ({kind}, declared in {})",
+                binding.desc, binding.mode
+            );
+            if let Some(source) = &binding.source {
+                println!("  at {source}");
+            }
+        }
+        None if explanation.capture => {
+            println!("matched: nothing; capture mode swallows the chord");
+        }
+        None => println!("matched: nothing; the chord passes through to the focused app"),
+    }
+    if !explanation.inherited.is_empty() {
+        println!("inherited globals:");
+        for binding in &explanation.inherited {
+            let shadowed = if binding.shadowed { "  [shadowed]" } else { "" };
+            println!(
+                "  {}  {}  (from {}){shadowed}",
+                binding.chord, binding.desc, binding.mode
+            );
+        }
+    }
+    if !explanation.conditions.is_empty() {
+        println!("render conditions:");
+        for condition in &explanation.conditions {
+            println!("  {}  (in {})", condition.condition, condition.mode);
+        }
+    }
+}
+
+/// Translate a foreign configuration and print or write the generated config.
+fn run_import_command(source: &ImportSource) {
+    let ImportSource::Skhd { file, output } = source;
+    let skhdrc = match fs::read_to_string(file) {
+        Ok(skhdrc) => skhdrc,
+        Err(e) => {
+            eprintln!("{}: {e}", file.display());
+            process::exit(EXIT_IO);
+        }
+    };
+    let import = import_skhd(&skhdrc);
+    for note in &import.unsupported {
+        eprintln!("unsupported: {note}");
+    }
+    let Some(output) = output else {
+        print!("{}", import.config);
+        return;
+    };
+    if output.exists() {
+        eprintln!("{} already exists; not replacing it", output.display());
+        process::exit(EXIT_IO);
+    }
+    if let Err(e) = fs::write(output, &import.config) {
+        eprintln!("{}: {e}", output.display());
+        process::exit(EXIT_IO);
+    }
+    println!("wrote {}", output.display());
+}
+
+/// Read Luau lines from stdin and evaluate each against the configuration until EOF or `.exit`.
+fn run_repl_command(path: Option<&str>, cli_config: Option<&Path>) {
+    let resolved = resolve_cli_config_path(path, cli_config);
+    let mut repl = match ReplSession::new(&resolved) {
+        Ok(repl) => repl,
+        Err(e) => {
+            eprintln!("{}", e.pretty());
+            process::exit(error_exit_code(&e));
+        }
+    };
+    println!(
+        "hotki repl for {}; `t` is a TestContext, `ctx` its ModeContext, `.exit` quits",
+        resolved.display()
+    );
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush().ok();
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            return;
+        };
+        if line.trim() == ".exit" {
+            return;
+        }
+        match repl.eval(&line) {
+            Ok(output) => {
+                for value in output {
+                    println!("{value}");
+                }
+            }
+            Err(e) => eprintln!("{}", e.pretty()),
+        }
+    }
+}
+
+/// Run every spec file next to the configuration and exit non-zero on any failure.
+fn run_test_command(path: Option<&str>, cli_config: Option<&Path>) {
+    let resolved = resolve_cli_config_path(path, cli_config);
+    let specs = match discover_specs(&resolved) {
+        Ok(specs) => specs,
+        Err(e) => {
+            eprintln!("{}", e.pretty());
+            process::exit(1);
+        }
+    };
+    if specs.is_empty() {
+        println!("no *.spec.luau files found next to {}", resolved.display());
+        return;
+    }
+    let (mut passed, mut failed) = (0, 0);
+    for spec in specs {
+        println!("{}", spec.display());
+        match run_spec_file(&resolved, &spec) {
+            Ok(cases) => {
+                for case in cases {
+                    match case.failure {
+                        None => {
+                            passed += 1;
+                            println!("  PASS {}", case.name);
+                        }
+                        Some(failure) => {
+                            failed += 1;
+                            println!("  FAIL {}", case.name);
+                            for line in failure.lines() {
+                                println!("    {line}");
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  ERROR");
+                for line in e.pretty().lines() {
+                    println!("    {line}");
+                }
+            }
+        }
+    }
+    println!("{passed} passed, {failed} failed");
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
 /// Dump the embedded default style source or the resolved effective style.
 fn run_style_command(path: Option<&str>, cli_config: Option<&Path>, default: bool) {
     if default {
@@ -170,7 +811,7 @@ fn resolve_cli_config_path(path: Option<&str>, cli_config: Option<&Path>) -> Pat
         Ok(path) => path,
         Err(e) => {
             eprintln!("{}", e.pretty());
-            process::exit(1);
+            process::exit(error_exit_code(&e));
         }
     }
 }
@@ -7,6 +7,7 @@
 //! - Scancode conversion through `TryFrom<Scancode> for Key` and
 //!   `From<Key> for Scancode`.
 //! - Canonical chord formatting through `Chord`'s `Display` implementation.
+//! - `MediaKey`: Media and system-defined keys with their `NX_KEYTYPE_*` codes.
 //!
 //! The `Key` enum is generated from the macOS SDK HIToolbox header and
 //! assigned the exact hardware codes. Variant names are normalized (ANSI_
@@ -29,3 +30,6 @@ pub use chord::Chord;
 
 mod scancode;
 pub use scancode::Scancode;
+
+mod media;
+pub use media::MediaKey;
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// Media and system-defined keys posted as NX auxiliary control events.
+///
+/// These keys have no virtual keycode; macOS delivers them as system-defined
+/// events that reach whichever application owns media playback.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaKey {
+    /// Toggle playback.
+    PlayPause,
+    /// Skip to the next track.
+    Next,
+    /// Return to the previous track.
+    Previous,
+    /// Fast-forward playback.
+    FastForward,
+    /// Rewind playback.
+    Rewind,
+    /// Raise the output volume one step.
+    VolumeUp,
+    /// Lower the output volume one step.
+    VolumeDown,
+    /// Toggle output mute.
+    Mute,
+    /// Raise the display brightness one step.
+    BrightnessUp,
+    /// Lower the display brightness one step.
+    BrightnessDown,
+    /// Raise the keyboard backlight one step.
+    IlluminationUp,
+    /// Lower the keyboard backlight one step.
+    IlluminationDown,
+}
+
+impl MediaKey {
+    /// Every media key, in declaration order.
+    pub const ALL: [Self; 12] = [
+        Self::PlayPause,
+        Self::Next,
+        Self::Previous,
+        Self::FastForward,
+        Self::Rewind,
+        Self::VolumeUp,
+        Self::VolumeDown,
+        Self::Mute,
+        Self::BrightnessUp,
+        Self::BrightnessDown,
+        Self::IlluminationUp,
+        Self::IlluminationDown,
+    ];
+
+    /// `NX_KEYTYPE_*` code from IOKit's `ev_keymap.h`.
+    #[must_use]
+    pub fn nx_key_type(self) -> u32 {
+        match self {
+            Self::VolumeUp => 0,
+            Self::VolumeDown => 1,
+            Self::BrightnessUp => 2,
+            Self::BrightnessDown => 3,
+            Self::Mute => 7,
+            Self::PlayPause => 16,
+            Self::Next => 17,
+            Self::Previous => 18,
+            Self::FastForward => 19,
+            Self::Rewind => 20,
+            Self::IlluminationUp => 21,
+            Self::IlluminationDown => 22,
+        }
+    }
+
+    /// Snake-case name used in configuration.
+    #[must_use]
+    pub fn to_spec(self) -> &'static str {
+        match self {
+            Self::PlayPause => "play_pause",
+            Self::Next => "next",
+            Self::Previous => "previous",
+            Self::FastForward => "fast_forward",
+            Self::Rewind => "rewind",
+            Self::VolumeUp => "volume_up",
+            Self::VolumeDown => "volume_down",
+            Self::Mute => "mute",
+            Self::BrightnessUp => "brightness_up",
+            Self::BrightnessDown => "brightness_down",
+            Self::IlluminationUp => "illumination_up",
+            Self::IlluminationDown => "illumination_down",
+        }
+    }
+
+    /// Parse a snake-case configuration name.
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|key| key.to_spec() == spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn specs_round_trip() {
+        for key in MediaKey::ALL {
+            assert_eq!(MediaKey::from_spec(key.to_spec()), Some(key));
+        }
+        assert_eq!(MediaKey::from_spec("PlayPause"), None);
+    }
+
+    #[test]
+    fn nx_key_types_are_distinct() {
+        let codes: HashSet<u32> = MediaKey::ALL.iter().map(|key| key.nx_key_type()).collect();
+        assert_eq!(codes.len(), MediaKey::ALL.len());
+    }
+}
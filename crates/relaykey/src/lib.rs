@@ -6,12 +6,25 @@
 //! Each gesture pins its chord and destination at [`RelayKey::begin`], forwards
 //! explicit repeats through [`RelayKey::repeat`], and balances the gesture through
 //! [`RelayKey::end`]. Process destinations carry chord flags on the main key but
-//! do not post separate modifier transitions.
+//! do not post separate modifier transitions unless the gesture was started with
+//! [`RelayKey::begin_with_modifier_events`].
+//!
+//! [`RelayKey::type_text`] posts literal text as Unicode keyboard events that are
+//! independent of the active keyboard layout and of any held modifiers.
+//!
+//! [`RelayKey::media`] posts system-defined media key events, which reach the
+//! application that owns media playback regardless of focus.
+//!
+//! [`RelayKey::sequence`] taps several chords in order on a background thread.
+//! Every step posts its complete down/up pair before the next delay, so no step
+//! is left pressed; [`RelayKey::release_all`] cancels outstanding sequences.
 #![warn(missing_docs)]
 #![warn(unsafe_op_in_unsafe_fn)]
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, Mutex},
+    sync::{Arc, Condvar, Mutex, Weak},
+    thread::{self, JoinHandle},
+    time::Duration,
 };
 
 use core_graphics::{
@@ -19,10 +32,14 @@ use core_graphics::{
     event_source::{CGEventSource, CGEventSourceStateID},
 };
 use mac_hotkey::HOTK_TAG;
-use mac_keycode::{Chord, Modifier, Scancode};
+use mac_keycode::{Chord, MediaKey, Modifier, Scancode};
 use tracing::{info, trace, warn};
 mod error;
 pub use error::{Error, Result};
+mod media;
+
+/// Maximum UTF-16 code units CoreGraphics accepts in one Unicode keyboard event.
+const MAX_UNICODE_UNITS: usize = 20;
 
 /// Destination for one relayed key gesture.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -48,6 +65,14 @@ pub(crate) trait Poster: Send + Sync {
     ) -> Result<()> {
         Ok(())
     }
+    /// Post a key down/up pair carrying `text` as its Unicode string.
+    fn post_unicode(&self, _text: &str, _destination: RelayDestination) -> Result<()> {
+        Ok(())
+    }
+    /// Post one system-defined media key transition.
+    fn post_media(&self, _key: MediaKey, _down: bool) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Default system poster that uses CoreGraphics to inject events.
@@ -90,10 +115,7 @@ impl MacPoster {
     fn build_event(&self, chord: &Chord, down: bool, is_repeat: bool) -> Result<cge::CGEvent> {
         let e = self.build_keycode_event(Scancode::from(chord.key), down)?;
         // Apply modifier flags
-        let bits: u64 = chord
-            .modifiers
-            .iter()
-            .fold(0_u64, |acc, m| acc | m.cg_flag_bits());
+        let bits = modifier_flag_bits(chord.modifiers.iter());
         e.set_flags(cge::CGEventFlags::from_bits_retain(bits));
         if is_repeat {
             e.set_integer_value_field(cge::EventField::KEYBOARD_EVENT_AUTOREPEAT, 1);
@@ -123,8 +145,14 @@ fn needs_hotki_tag(destination: RelayDestination) -> bool {
     matches!(destination, RelayDestination::Hid)
 }
 
-/// Map a modifier set to virtual keycodes.
-fn mod_keycodes(mods: &HashSet<Modifier>) -> Vec<u16> {
+/// Combined CoreGraphics flag bits for `mods`.
+fn modifier_flag_bits<'a>(mods: impl IntoIterator<Item = &'a Modifier>) -> u64 {
+    mods.into_iter()
+        .fold(0_u64, |acc, m| acc | m.cg_flag_bits())
+}
+
+/// Modifiers present in `mods`, in the order their key-downs are posted.
+fn ordered_modifiers(mods: &HashSet<Modifier>) -> Vec<Modifier> {
     let mut v = Vec::new();
     for m in [
         Modifier::Control,
@@ -137,7 +165,7 @@ fn mod_keycodes(mods: &HashSet<Modifier>) -> Vec<u16> {
         Modifier::RightCommand,
     ] {
         if mods.contains(&m) {
-            v.push(m.keycode());
+            v.push(m);
         }
     }
     v
@@ -177,17 +205,69 @@ impl Poster for MacPoster {
         down: bool,
         destination: RelayDestination,
     ) -> Result<()> {
-        let mut codes = mod_keycodes(mods);
+        let mut order = ordered_modifiers(mods);
+        let mut flags = if down { 0 } else { modifier_flag_bits(&order) };
         if !down {
             // Release in reverse order
-            codes.reverse();
+            order.reverse();
         }
-        for code in codes {
-            let event = self.build_keycode_event(code, down)?;
+        for modifier in order {
+            if down {
+                flags |= modifier.cg_flag_bits();
+            } else {
+                flags &= !modifier.cg_flag_bits();
+            }
+            let event = self.build_keycode_event(modifier.keycode(), down)?;
+            // Process delivery bypasses the HID system's modifier state, so each
+            // transition carries the flags held after it.
+            if matches!(destination, RelayDestination::Process(_)) {
+                event.set_flags(cge::CGEventFlags::from_bits_retain(flags));
+            }
             post_event(&event, destination);
         }
         Ok(())
     }
+
+    fn post_unicode(&self, text: &str, destination: RelayDestination) -> Result<()> {
+        for down in [true, false] {
+            let event = self.build_keycode_event(0, down)?;
+            event.set_flags(cge::CGEventFlags::empty());
+            event.set_string(text);
+            post_event(&event, destination);
+        }
+        trace!(
+            units = text.encode_utf16().count(),
+            ?destination,
+            "relayed_unicode"
+        );
+        Ok(())
+    }
+
+    fn post_media(&self, key: MediaKey, down: bool) -> Result<()> {
+        media::post_media_event(key, down)?;
+        trace!(?key, down, "relayed_media_key");
+        Ok(())
+    }
+}
+
+/// Split `text` into pieces of at most `max_units` UTF-16 code units without splitting a character.
+fn unicode_chunks(text: &str, max_units: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut units = 0;
+    for (idx, ch) in text.char_indices() {
+        let width = ch.len_utf16();
+        if units + width > max_units && idx > start {
+            chunks.push(&text[start..idx]);
+            start = idx;
+            units = 0;
+        }
+        units += width;
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
 }
 
 /// Chord and destination retained for one active keyed gesture.
@@ -197,23 +277,107 @@ struct ActiveGesture {
     chord: Chord,
     /// Delivery destination pinned when the gesture began.
     destination: RelayDestination,
+    /// Whether modifier key transitions wrap the main key.
+    modifier_events: bool,
+}
+
+impl ActiveGesture {
+    /// Pin a gesture, always sending modifier transitions on the HID path.
+    fn new(chord: Chord, destination: RelayDestination, process_modifier_events: bool) -> Self {
+        Self {
+            chord,
+            destination,
+            modifier_events: matches!(destination, RelayDestination::Hid)
+                || process_modifier_events,
+        }
+    }
+}
+
+/// Cancellation flag shared between a running chord sequence and its owner.
+#[derive(Default)]
+struct SequenceCancel {
+    /// Whether the sequence must stop before its next step.
+    cancelled: Mutex<bool>,
+    /// Wakes a sequence sleeping between steps.
+    wake: Condvar,
+}
+
+impl SequenceCancel {
+    /// Stop the sequence and wake it if it is sleeping.
+    fn cancel(&self) {
+        *self.cancelled.lock().expect("sequence cancel lock") = true;
+        self.wake.notify_all();
+    }
+
+    /// Whether the sequence has been cancelled.
+    fn is_cancelled(&self) -> bool {
+        *self.cancelled.lock().expect("sequence cancel lock")
+    }
+
+    /// Sleep for up to `delay`, returning true when cancelled.
+    fn wait(&self, delay: Duration) -> bool {
+        let guard = self.cancelled.lock().expect("sequence cancel lock");
+        let (guard, _) = self
+            .wake
+            .wait_timeout_while(guard, delay, |cancelled| !*cancelled)
+            .expect("sequence cancel lock");
+        *guard
+    }
 }
 
 /// Last-owner gesture state and its optional system-event backend.
 struct RelayState {
     /// Active gestures indexed by caller-supplied identity.
     active: Mutex<HashMap<String, ActiveGesture>>,
+    /// Running chord sequences indexed by caller-supplied identity.
+    sequences: Mutex<HashMap<String, Arc<SequenceCancel>>>,
     /// Event backend, or `None` when relay posting is disabled.
     poster: Option<Arc<dyn Poster>>,
 }
 
 impl RelayState {
-    /// Release and remove every active gesture.
+    /// Tap one sequence step unless `cancel` fired, returning false when cancelled.
+    ///
+    /// The check and the tap share the sequence lock so no step posts after a
+    /// cancellation has returned.
+    fn tap_step(
+        &self,
+        cancel: &SequenceCancel,
+        chord: &Chord,
+        destination: RelayDestination,
+    ) -> bool {
+        let _sequences = self.sequences.lock().expect("relay sequence lock");
+        if cancel.is_cancelled() {
+            return false;
+        }
+        if let Some(poster) = &self.poster {
+            let gesture = ActiveGesture::new(chord.clone(), destination, false);
+            post_down(poster.as_ref(), &gesture, false);
+            post_up(poster.as_ref(), &gesture);
+        }
+        true
+    }
+
+    /// Remove `cancel` from the running sequences if it still owns `id`.
+    fn finish_sequence(&self, id: &str, cancel: &Arc<SequenceCancel>) {
+        let mut sequences = self.sequences.lock().expect("relay sequence lock");
+        if sequences
+            .get(id)
+            .is_some_and(|current| Arc::ptr_eq(current, cancel))
+        {
+            sequences.remove(id);
+        }
+    }
+
+    /// Cancel every running sequence, then release and remove every active gesture.
     fn release_all(&self) {
+        for (_, cancel) in self.sequences.lock().expect("relay sequence lock").drain() {
+            cancel.cancel();
+        }
         let mut active = self.active.lock().expect("relay state lock");
         if let Some(poster) = &self.poster {
             for (id, gesture) in active.drain() {
-                post_up(poster.as_ref(), &gesture.chord, gesture.destination);
+                post_up(poster.as_ref(), &gesture);
                 trace!(?gesture.destination, id = %id, "relay_release_all");
             }
         } else {
@@ -263,6 +427,7 @@ impl RelayKey {
         Self {
             state: Arc::new(RelayState {
                 active: Mutex::new(HashMap::new()),
+                sequences: Mutex::new(HashMap::new()),
                 poster,
             }),
         }
@@ -270,17 +435,32 @@ impl RelayKey {
 
     /// Begin a keyed gesture and pin its chord and destination.
     pub fn begin(&self, id: String, chord: Chord, destination: RelayDestination) {
+        self.begin_gesture(id, ActiveGesture::new(chord, destination, false));
+    }
+
+    /// Begin a keyed gesture that wraps the main key in modifier transitions at any destination.
+    pub fn begin_with_modifier_events(
+        &self,
+        id: String,
+        chord: Chord,
+        destination: RelayDestination,
+    ) {
+        self.begin_gesture(id, ActiveGesture::new(chord, destination, true));
+    }
+
+    /// Replace any gesture under `id` and post the new gesture's initial key-down.
+    fn begin_gesture(&self, id: String, gesture: ActiveGesture) {
         let mut active = self.state.active.lock().expect("relay state lock");
         if let Some(previous) = active.remove(&id)
             && let Some(poster) = &self.state.poster
         {
-            post_up(poster.as_ref(), &previous.chord, previous.destination);
+            post_up(poster.as_ref(), &previous);
         }
         if let Some(poster) = &self.state.poster {
-            post_down(poster.as_ref(), &chord, false, destination);
+            post_down(poster.as_ref(), &gesture, false);
         }
-        trace!(?destination, id = %id, "relay_begin");
-        active.insert(id, ActiveGesture { chord, destination });
+        trace!(?gesture.destination, id = %id, modifier_events = gesture.modifier_events, "relay_begin");
+        active.insert(id, gesture);
     }
 
     /// Repeat the gesture identified by `id`.
@@ -288,7 +468,7 @@ impl RelayKey {
         let active = self.state.active.lock().expect("relay state lock");
         if let Some(gesture) = active.get(id) {
             if let Some(poster) = &self.state.poster {
-                post_down(poster.as_ref(), &gesture.chord, true, gesture.destination);
+                post_down(poster.as_ref(), gesture, true);
             }
             true
         } else {
@@ -306,7 +486,7 @@ impl RelayKey {
             .remove(id);
         if let Some(gesture) = gesture {
             if let Some(poster) = &self.state.poster {
-                post_up(poster.as_ref(), &gesture.chord, gesture.destination);
+                post_up(poster.as_ref(), &gesture);
             }
             trace!(?gesture.destination, id = %id, "relay_end");
             true
@@ -319,13 +499,100 @@ impl RelayKey {
     pub fn release_all(&self) {
         self.state.release_all();
     }
+
+    /// Tap `chords` in order at `destination`, waiting `delay` between steps.
+    ///
+    /// Starting a sequence cancels any earlier sequence with the same `id`.
+    pub fn sequence(
+        &self,
+        id: String,
+        chords: Vec<Chord>,
+        delay: Duration,
+        destination: RelayDestination,
+    ) {
+        let _ = self.start_sequence(id, chords, delay, destination);
+    }
+
+    /// Start a sequence thread, returning its handle when any step will be posted.
+    fn start_sequence(
+        &self,
+        id: String,
+        chords: Vec<Chord>,
+        delay: Duration,
+        destination: RelayDestination,
+    ) -> Option<JoinHandle<()>> {
+        if self.state.poster.is_none() || chords.is_empty() {
+            return None;
+        }
+        let cancel = Arc::new(SequenceCancel::default());
+        if let Some(previous) = self
+            .state
+            .sequences
+            .lock()
+            .expect("relay sequence lock")
+            .insert(id.clone(), cancel.clone())
+        {
+            previous.cancel();
+        }
+        trace!(?destination, id = %id, steps = chords.len(), "relay_sequence");
+        let state: Weak<RelayState> = Arc::downgrade(&self.state);
+        Some(thread::spawn(move || {
+            for (idx, chord) in chords.iter().enumerate() {
+                if idx > 0 && cancel.wait(delay) {
+                    break;
+                }
+                let Some(state) = state.upgrade() else {
+                    return;
+                };
+                if !state.tap_step(&cancel, chord, destination) {
+                    break;
+                }
+            }
+            if let Some(state) = state.upgrade() {
+                state.finish_sequence(&id, &cancel);
+            }
+        }))
+    }
+
+    /// Tap one media key, returning false when either transition failed to post.
+    pub fn media(&self, key: MediaKey) -> bool {
+        let Some(poster) = &self.state.poster else {
+            return true;
+        };
+        for down in [true, false] {
+            if let Err(error) = poster.post_media(key, down) {
+                warn!(?error, ?key, down, "media_key_failed");
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Type literal text at `destination`, returning false when any event failed to post.
+    pub fn type_text(&self, text: &str, destination: RelayDestination) -> bool {
+        let Some(poster) = &self.state.poster else {
+            return true;
+        };
+        for chunk in unicode_chunks(text, MAX_UNICODE_UNITS) {
+            if let Err(error) = poster.post_unicode(chunk, destination) {
+                warn!(?error, "type_text_failed");
+                return false;
+            }
+        }
+        trace!(?destination, "type_text");
+        true
+    }
 }
 
-/// Post one initial or repeated key-down with destination-specific modifiers.
-fn post_down(poster: &dyn Poster, chord: &Chord, is_repeat: bool, destination: RelayDestination) {
+/// Post one initial or repeated key-down, preceded by modifier transitions when enabled.
+fn post_down(poster: &dyn Poster, gesture: &ActiveGesture, is_repeat: bool) {
+    let ActiveGesture {
+        chord, destination, ..
+    } = gesture;
+    let destination = *destination;
     trace!(code = ?chord.key, mods = ?chord.modifiers, is_repeat, ?destination, "on_key_down");
     if !is_repeat
-        && matches!(destination, RelayDestination::Hid)
+        && gesture.modifier_events
         && let Err(error) = poster.post_modifiers(&chord.modifiers, true, destination)
     {
         warn!(?error, "post_modifiers_failed");
@@ -335,13 +602,17 @@ fn post_down(poster: &dyn Poster, chord: &Chord, is_repeat: bool, destination: R
     }
 }
 
-/// Post one key-up and balance destination-specific modifiers.
-fn post_up(poster: &dyn Poster, chord: &Chord, destination: RelayDestination) {
+/// Post one key-up, followed by balancing modifier transitions when enabled.
+fn post_up(poster: &dyn Poster, gesture: &ActiveGesture) {
+    let ActiveGesture {
+        chord, destination, ..
+    } = gesture;
+    let destination = *destination;
     trace!(code = ?chord.key, mods = ?chord.modifiers, ?destination, "on_key_up");
     if let Err(error) = poster.post_up(chord, destination) {
         warn!(?error, "relay_up_failed");
     }
-    if matches!(destination, RelayDestination::Hid)
+    if gesture.modifier_events
         && let Err(error) = poster.post_modifiers(&chord.modifiers, false, destination)
     {
         warn!(?error, "post_modifiers_failed");
@@ -487,11 +758,13 @@ mod tests {
         assert_eq!(poster.ups(), 1);
     }
 
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
     enum Posted {
         Modifiers(bool, RelayDestination),
         Down(bool, RelayDestination),
         Up(RelayDestination),
+        Unicode(String, RelayDestination),
+        Media(MediaKey, bool),
     }
 
     #[derive(Default)]
@@ -537,6 +810,22 @@ mod tests {
                 .push(Posted::Modifiers(down, destination));
             Ok(())
         }
+
+        fn post_unicode(&self, text: &str, destination: RelayDestination) -> Result<()> {
+            self.0
+                .lock()
+                .expect("recording poster lock")
+                .push(Posted::Unicode(text.to_string(), destination));
+            Ok(())
+        }
+
+        fn post_media(&self, key: MediaKey, down: bool) -> Result<()> {
+            self.0
+                .lock()
+                .expect("recording poster lock")
+                .push(Posted::Media(key, down));
+            Ok(())
+        }
     }
 
     #[test]
@@ -589,6 +878,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn process_modifier_events_wrap_the_key_through_repeat_and_release() {
+        let poster = Arc::new(RecordingPoster::default());
+        let relay = RelayKey::new_with_poster(poster.clone());
+        let destination = RelayDestination::Process(731);
+        let chord = Chord {
+            key: Key::A,
+            modifiers: HashSet::from([Modifier::Command]),
+        };
+
+        relay.begin_with_modifier_events("process".to_string(), chord.clone(), destination);
+        assert!(relay.repeat("process"));
+        assert!(relay.repeat("process"));
+        assert!(relay.end("process"));
+        relay.begin_with_modifier_events("held".to_string(), chord, destination);
+        relay.release_all();
+
+        let wrapped = |repeats: usize| {
+            let mut events = vec![
+                Posted::Modifiers(true, destination),
+                Posted::Down(false, destination),
+            ];
+            events.extend((0..repeats).map(|_| Posted::Down(true, destination)));
+            events.extend([
+                Posted::Up(destination),
+                Posted::Modifiers(false, destination),
+            ]);
+            events
+        };
+        let mut expected = wrapped(2);
+        expected.extend(wrapped(0));
+        assert_eq!(poster.events(), expected);
+    }
+
     #[test]
     fn replacing_gesture_balances_previous_destination_first() {
         let poster = Arc::new(RecordingPoster::default());
@@ -611,6 +934,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sequence_taps_each_chord_in_order() {
+        let poster = Arc::new(RecordingPoster::default());
+        let relay = RelayKey::new_with_poster(poster.clone());
+        let destination = RelayDestination::Process(9);
+        let chords = vec![Chord::parse("cmd+k").unwrap(), Chord::parse("s").unwrap()];
+
+        relay
+            .start_sequence("seq".into(), chords, Duration::ZERO, destination)
+            .expect("sequence thread")
+            .join()
+            .expect("sequence join");
+
+        assert_eq!(
+            poster.events(),
+            vec![
+                Posted::Down(false, destination),
+                Posted::Up(destination),
+                Posted::Down(false, destination),
+                Posted::Up(destination),
+            ]
+        );
+        assert!(relay.state.sequences.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn release_all_cancels_a_sleeping_sequence_without_stuck_keys() {
+        let poster = Arc::new(RecordingPoster::default());
+        let relay = RelayKey::new_with_poster(poster.clone());
+        let chords = vec![Chord::parse("a").unwrap(), Chord::parse("b").unwrap()];
+
+        let handle = relay
+            .start_sequence(
+                "seq".into(),
+                chords,
+                Duration::from_secs(60),
+                RelayDestination::Hid,
+            )
+            .expect("sequence thread");
+        while poster.events().len() < 2 {
+            thread::yield_now();
+        }
+        relay.release_all();
+        handle.join().expect("sequence join");
+
+        assert_eq!(
+            poster.events(),
+            vec![
+                Posted::Modifiers(true, RelayDestination::Hid),
+                Posted::Down(false, RelayDestination::Hid),
+                Posted::Up(RelayDestination::Hid),
+                Posted::Modifiers(false, RelayDestination::Hid),
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_or_empty_sequences_start_no_thread() {
+        let relay = RelayKey::disabled();
+        let chords = vec![Chord::parse("a").unwrap()];
+        assert!(
+            relay
+                .start_sequence("seq".into(), chords, Duration::ZERO, RelayDestination::Hid)
+                .is_none()
+        );
+        let relay = RelayKey::new_with_poster(Arc::new(RecordingPoster::default()));
+        assert!(
+            relay
+                .start_sequence(
+                    "seq".into(),
+                    Vec::new(),
+                    Duration::ZERO,
+                    RelayDestination::Hid
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn media_taps_post_down_then_up_without_key_events() {
+        let poster = Arc::new(RecordingPoster::default());
+        let relay = RelayKey::new_with_poster(poster.clone());
+
+        assert!(relay.media(MediaKey::PlayPause));
+        assert!(relay.media(MediaKey::BrightnessUp));
+        assert!(RelayKey::disabled().media(MediaKey::Next));
+
+        assert_eq!(
+            poster.events(),
+            vec![
+                Posted::Media(MediaKey::PlayPause, true),
+                Posted::Media(MediaKey::PlayPause, false),
+                Posted::Media(MediaKey::BrightnessUp, true),
+                Posted::Media(MediaKey::BrightnessUp, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn type_text_posts_bounded_unicode_chunks_without_modifiers() {
+        let poster = Arc::new(RecordingPoster::default());
+        let relay = RelayKey::new_with_poster(poster.clone());
+        let destination = RelayDestination::Process(5);
+        let text = format!("{}é😀", "a".repeat(18));
+
+        assert!(relay.type_text(&text, destination));
+
+        assert_eq!(
+            poster.events(),
+            vec![
+                Posted::Unicode(format!("{}é", "a".repeat(18)), destination),
+                Posted::Unicode("😀".to_string(), destination),
+            ]
+        );
+    }
+
+    #[test]
+    fn unicode_chunks_never_split_surrogate_pairs() {
+        assert_eq!(unicode_chunks("", 2), Vec::<&str>::new());
+        assert_eq!(unicode_chunks("ab😀c", 2), vec!["ab", "😀", "c"]);
+        assert_eq!(unicode_chunks("😀", 1), vec!["😀"]);
+    }
+
     #[test]
     fn only_hid_events_need_the_hotki_marker() {
         assert!(needs_hotki_tag(RelayDestination::Hid));
@@ -717,7 +1163,7 @@ mod tests {
     }
 
     #[test]
-    fn test_mod_keycodes_preserves_both_variants() {
+    fn test_ordered_modifiers_preserves_both_variants() {
         let mods = HashSet::from([
             Modifier::Shift,
             Modifier::RightShift,
@@ -725,7 +1171,10 @@ mod tests {
             Modifier::RightCommand,
         ]);
 
-        let keycodes = mod_keycodes(&mods);
+        let keycodes: Vec<u16> = ordered_modifiers(&mods)
+            .into_iter()
+            .map(Modifier::keycode)
+            .collect();
         assert_eq!(keycodes.len(), 4);
         assert!(keycodes.contains(&Modifier::Shift.keycode()));
         assert!(keycodes.contains(&Modifier::RightShift.keycode()));
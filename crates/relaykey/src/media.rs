@@ -0,0 +1,66 @@
+//! System-defined media key events.
+//!
+//! Media keys have no virtual keycode. AppKit represents them as
+//! `NSEventTypeSystemDefined` events with the auxiliary-control subtype, whose
+//! `data1` packs the `NX_KEYTYPE_*` code with the key state. CoreGraphics has
+//! no constructor for these, so the event is built through `NSEvent` and posted
+//! from its CoreGraphics form.
+
+use mac_keycode::MediaKey;
+use objc2_app_kit::{NSEvent, NSEventModifierFlags, NSEventType};
+use objc2_core_graphics::{CGEvent, CGEventTapLocation};
+use objc2_foundation::NSPoint;
+
+use crate::{Error, Result};
+
+/// `NX_SUBTYPE_AUX_CONTROL_BUTTONS` from IOKit's `ev_keymap.h`.
+const NX_SUBTYPE_AUX_CONTROL_BUTTONS: i16 = 8;
+
+/// Key-down state nibble for auxiliary control events.
+const NX_KEYDOWN: isize = 0xA;
+
+/// Key-up state nibble for auxiliary control events.
+const NX_KEYUP: isize = 0xB;
+
+/// State nibble for one key transition.
+fn key_state(down: bool) -> isize {
+    if down { NX_KEYDOWN } else { NX_KEYUP }
+}
+
+/// Pack an auxiliary control event's `data1` field.
+pub(crate) fn aux_data1(key: MediaKey, down: bool) -> isize {
+    ((key.nx_key_type() as isize) << 16) | (key_state(down) << 8)
+}
+
+/// Post one media key transition through the HID event stream.
+pub(crate) fn post_media_event(key: MediaKey, down: bool) -> Result<()> {
+    let data1 = aux_data1(key, down);
+    let flags = NSEventModifierFlags((key_state(down) << 8) as usize);
+    let event = NSEvent::otherEventWithType_location_modifierFlags_timestamp_windowNumber_context_subtype_data1_data2(
+        NSEventType::SystemDefined,
+        NSPoint::new(0.0, 0.0),
+        flags,
+        0.0,
+        0,
+        None,
+        NX_SUBTYPE_AUX_CONTROL_BUTTONS,
+        data1,
+        -1,
+    )
+    .ok_or(Error::EventCreate)?;
+    let cg_event = event.CGEvent().ok_or(Error::EventCreate)?;
+    CGEvent::post(CGEventTapLocation::HIDEventTap, Some(&cg_event));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aux_data1_packs_key_type_and_state() {
+        assert_eq!(aux_data1(MediaKey::PlayPause, true), 0x0010_0A00);
+        assert_eq!(aux_data1(MediaKey::PlayPause, false), 0x0010_0B00);
+        assert_eq!(aux_data1(MediaKey::VolumeUp, true), 0x0000_0A00);
+    }
+}